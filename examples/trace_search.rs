@@ -0,0 +1,51 @@
+use rust_2048_game::ai::evaluate_moves_traced;
+use rust_2048_game::engine::{apply_move_traced, Direction, Merge, Slide};
+use rust_2048_game::research::{NodeKind, SearchNode, Tracer};
+
+#[derive(Default)]
+struct Counter {
+    slides: usize,
+    merges: usize,
+    max_nodes: usize,
+    chance_nodes: usize,
+    leaves: usize,
+}
+
+impl Tracer for Counter {
+    fn on_slide(&mut self, _slide: &Slide) {
+        self.slides += 1;
+    }
+
+    fn on_merge(&mut self, _merge: &Merge) {
+        self.merges += 1;
+    }
+
+    fn on_search_node(&mut self, node: &SearchNode) {
+        match node.kind {
+            NodeKind::Max => self.max_nodes += 1,
+            NodeKind::Chance => self.chance_nodes += 1,
+            NodeKind::Leaf => self.leaves += 1,
+        }
+    }
+}
+
+fn main() {
+    let mut board = vec![
+        vec![2, 2, 4, 0],
+        vec![0, 4, 0, 0],
+        vec![0, 0, 8, 0],
+        vec![2, 0, 0, 2],
+    ];
+    let mut counter = Counter::default();
+
+    let evaluations = evaluate_moves_traced(&board, 2, &mut counter);
+    for evaluation in &evaluations {
+        println!("{:?}: {:.3}", evaluation.direction, evaluation.expected);
+    }
+    apply_move_traced(&mut board, Direction::Left, &mut counter);
+
+    println!(
+        "slides {}, merges {}, max nodes {}, chance nodes {}, leaves {}",
+        counter.slides, counter.merges, counter.max_nodes, counter.chance_nodes, counter.leaves
+    );
+}
@@ -0,0 +1,21 @@
+/* C API of the 2048 engine, built with `cargo build --release --lib --features cdylib`. */
+#ifndef RUST_2048_GAME_H
+#define RUST_2048_GAME_H
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+typedef struct CGame CGame;
+
+enum { GAME_UP = 0, GAME_DOWN = 1, GAME_LEFT = 2, GAME_RIGHT = 3 };
+
+CGame *game_new(size_t size, uint64_t seed);
+int32_t game_move(CGame *game, uint32_t direction);
+const uint64_t *game_board_ptr(const CGame *game);
+size_t game_size(const CGame *game);
+uint64_t game_score(const CGame *game);
+bool game_is_over(const CGame *game);
+void game_free(CGame *game);
+
+#endif
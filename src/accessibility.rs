@@ -0,0 +1,175 @@
+use crate::i18n::{self, t, tf};
+use rust_2048_game::engine::{apply_move, Direction, MoveResult, OBSTACLE};
+use rust_2048_game::game::GameObserver;
+use std::{
+    borrow::Cow,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex,
+    },
+};
+
+static ASCII: AtomicBool = AtomicBool::new(false);
+static ACCESSIBLE: AtomicBool = AtomicBool::new(false);
+static RENDERED: Mutex<Vec<Vec<u64>>> = Mutex::new(Vec::new());
+static LAST_MOVE: Mutex<Option<Move>> = Mutex::new(None);
+
+struct Move {
+    before: Vec<Vec<u64>>,
+    direction: Direction,
+    merged: Vec<u64>,
+}
+
+pub fn configure(no_color: bool, ascii: bool, accessible: bool) {
+    let no_color_env = std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+    if no_color || no_color_env || accessible {
+        colored::control::set_override(false);
+    }
+    ASCII.store(ascii || accessible, Ordering::Relaxed);
+    ACCESSIBLE.store(accessible, Ordering::Relaxed);
+}
+
+pub fn ascii() -> bool {
+    ASCII.load(Ordering::Relaxed)
+}
+
+pub fn accessible() -> bool {
+    ACCESSIBLE.load(Ordering::Relaxed)
+}
+
+pub fn colorless() -> bool {
+    !colored::control::SHOULD_COLORIZE.should_colorize()
+}
+
+pub fn to_ascii(text: &str) -> Cow<'_, str> {
+    if !ASCII.load(Ordering::Relaxed) || text.is_ascii() {
+        return Cow::Borrowed(text);
+    }
+    Cow::Owned(text.chars().filter_map(fold).collect())
+}
+
+/// Spells translated text without accents, dropping the opening `¿` and `¡`.
+fn fold(c: char) -> Option<char> {
+    let folded = match c {
+        'á' | 'à' => 'a',
+        'é' | 'è' => 'e',
+        'í' => 'i',
+        'ó' => 'o',
+        'ú' | 'ü' => 'u',
+        'ñ' => 'n',
+        'Á' => 'A',
+        'É' => 'E',
+        'Í' => 'I',
+        'Ó' => 'O',
+        'Ú' => 'U',
+        'Ñ' => 'N',
+        '¿' | '¡' => return None,
+        c if c.is_ascii() => c,
+        _ => '?',
+    };
+    Some(folded)
+}
+
+pub fn record_move(direction: Direction, result: &MoveResult) {
+    if !accessible() {
+        return;
+    }
+    let before = RENDERED
+        .lock()
+        .map(|board| board.clone())
+        .unwrap_or_default();
+    if let Ok(mut last) = LAST_MOVE.lock() {
+        *last = Some(Move {
+            before,
+            direction,
+            merged: result.merges.iter().map(|merge| merge.value / 2).collect(),
+        });
+    }
+}
+
+fn merge_phrase(merged: &[u64]) -> String {
+    let mut merged = merged.to_vec();
+    merged.sort_unstable();
+    let parts: Vec<String> = merged
+        .iter()
+        .map(|value| tf("narration.pair", &[("value", value)]))
+        .collect();
+    let tiles = match parts.len() {
+        0 => return t("narration.no_merges").to_string(),
+        1 => parts[0].clone(),
+        n => tf(
+            "narration.list",
+            &[
+                ("rest", &parts[..n - 1].join(", ")),
+                ("last", &parts[n - 1]),
+            ],
+        ),
+    };
+    tf("narration.merged", &[("tiles", &tiles)])
+}
+
+fn announcement(game_board: &[Vec<u64>], last: &Move) -> String {
+    let mut sentence = tf(
+        "narration.moved",
+        &[
+            ("direction", &i18n::direction(last.direction).to_lowercase()),
+            ("merges", &merge_phrase(&last.merged)),
+        ],
+    );
+    let mut moved = last.before.clone();
+    if moved.len() == game_board.len() {
+        apply_move(&mut moved, last.direction);
+        let spawned: Vec<(usize, usize)> = game_board
+            .iter()
+            .enumerate()
+            .flat_map(|(i, row)| row.iter().enumerate().map(move |(j, &val)| (i, j, val)))
+            .filter(|&(i, j, val)| val != 0 && moved[i][j] == 0)
+            .map(|(i, j, _)| (i, j))
+            .collect();
+        if let [(i, j)] = spawned[..] {
+            sentence.push_str(", ");
+            sentence.push_str(&tf(
+                "narration.spawned",
+                &[
+                    ("value", &game_board[i][j]),
+                    ("row", &(i + 1)),
+                    ("col", &(j + 1)),
+                ],
+            ));
+        }
+    }
+    sentence
+}
+
+pub fn describe(game_board: &[Vec<u64>]) -> Vec<String> {
+    let mut lines = Vec::new();
+    if let Some(last) = LAST_MOVE.lock().ok().and_then(|mut last| last.take()) {
+        lines.push(format!("{}.", announcement(game_board, &last)));
+    }
+    for (i, row) in game_board.iter().enumerate() {
+        let cells: Vec<String> = row
+            .iter()
+            .map(|&val| match val {
+                0 => t("narration.blank").to_string(),
+                OBSTACLE => t("narration.blocked").to_string(),
+                _ => val.to_string(),
+            })
+            .collect();
+        lines.push(tf(
+            "narration.row",
+            &[("row", &(i + 1)), ("cells", &cells.join(", "))],
+        ));
+    }
+    if let Ok(mut rendered) = RENDERED.lock() {
+        *rendered = game_board.to_vec();
+    }
+    lines
+}
+
+pub struct Narrator;
+
+impl GameObserver for Narrator {
+    fn on_move(&mut self, direction: Direction, result: &MoveResult) {
+        record_move(direction, result);
+    }
+}
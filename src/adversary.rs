@@ -0,0 +1,103 @@
+use crate::accessibility::Narrator;
+use crate::broadcast::BroadcastObserver;
+use crate::config::load_config;
+use crate::help;
+use crate::i18n::{label, t, tf};
+use crate::sound::Sound;
+use crate::theme::initialize_colors;
+use crate::{campaign, integrations, key_direction, render_board};
+use crossterm::{
+    event::{read, Event, KeyCode},
+    terminal::{disable_raw_mode, enable_raw_mode},
+};
+use rust_2048_game::engine::max_tile;
+use rust_2048_game::game::Game;
+use rust_2048_game::spawn::Minimax;
+use std::collections::HashMap;
+
+pub const MAX_DEPTH: u32 = 4;
+
+pub fn run(depth: u32) -> crossterm::Result<()> {
+    let mut colors: HashMap<u64, colored::Color> = HashMap::new();
+    initialize_colors(&mut colors, campaign::active_theme());
+    let config = load_config();
+    let mut sound = Sound::new(&config.sound);
+    let mut show_risk = false;
+    let mut depth = depth.clamp(1, MAX_DEPTH);
+
+    let mut game = Game::new(config.game.board_size);
+    game.set_spawn_strategy(Box::new(Minimax { depth }));
+    game.subscribe(Box::new(BroadcastObserver));
+    game.subscribe(Box::new(Narrator));
+    for observer in integrations(t("mode.adversary")) {
+        game.subscribe(observer);
+    }
+    let mut moves: u32 = 0;
+
+    enable_raw_mode()?;
+    loop {
+        let messages = vec![
+            format!("{} {}", label("label.adversary"), t("adversary.status")),
+            format!(
+                "{} {}",
+                label("label.opponent"),
+                tf("adversary.depth", &[("depth", &depth), ("max", &MAX_DEPTH)])
+            ),
+            format!(
+                "{} {}",
+                label("label.survived"),
+                tf("adversary.moves", &[("moves", &moves)])
+            ),
+        ];
+        let best = max_tile(game.board());
+        render_board(
+            game.board(),
+            &colors,
+            game.score(),
+            best,
+            show_risk,
+            &messages,
+        )?;
+        if game.is_over() {
+            println!(" >> {} <<", tf("adversary.over", &[("moves", &moves)]));
+            break;
+        }
+
+        if let Event::Key(key_event) = read()? {
+            match key_event.code {
+                KeyCode::Char('e') | KeyCode::Char('E') => break,
+                KeyCode::Char('r') | KeyCode::Char('R') => show_risk = !show_risk,
+                KeyCode::Char('m') | KeyCode::Char('M') => {
+                    sound.toggle_mute();
+                }
+                KeyCode::Char('[') | KeyCode::Char(']') => {
+                    depth = if key_event.code == KeyCode::Char(']') {
+                        (depth + 1).min(MAX_DEPTH)
+                    } else {
+                        (depth - 1).max(1)
+                    };
+                    game.set_spawn_strategy(Box::new(Minimax { depth }));
+                }
+                KeyCode::Char('?') | KeyCode::F(1) => {
+                    help::show(
+                        t("mode.adversary"),
+                        t("adversary.objective"),
+                        &[("[ ]", t("adversary.action.depth"))],
+                    )?;
+                }
+                code => {
+                    let Some(direction) = key_direction(code) else {
+                        continue;
+                    };
+                    let result = game.play(direction);
+                    if result.moved {
+                        sound.play_move(&result);
+                        moves += 1;
+                    }
+                }
+            }
+        }
+    }
+    disable_raw_mode()?;
+    Ok(())
+}
@@ -0,0 +1,317 @@
+use crate::engine::{apply_move, Direction, OBSTACLE};
+use crate::heuristic::{self, Weights};
+use crate::research::{NoTrace, NodeKind, SearchNode, Tracer};
+use std::collections::HashMap;
+
+const MIN_PROBABILITY: f64 = 0.0001;
+const LOSS: f64 = -1.0e12;
+
+pub struct Evaluation {
+    pub direction: Direction,
+    pub expected: f64,
+}
+
+pub fn evaluate_moves(game_board: &[Vec<u64>], depth: u32) -> Vec<Evaluation> {
+    search(game_board, depth, &Weights::default(), &mut NoTrace)
+}
+
+pub fn evaluate_moves_with(
+    game_board: &[Vec<u64>],
+    depth: u32,
+    weights: &Weights,
+) -> Vec<Evaluation> {
+    search(game_board, depth, weights, &mut NoTrace)
+}
+
+pub fn adaptive_depth(game_board: &[Vec<u64>], base: u32) -> u32 {
+    match heuristic::empty_cells(game_board) {
+        0..=2 => base + 2,
+        3..=5 => base + 1,
+        _ => base,
+    }
+}
+
+pub fn evaluate_moves_adaptive(game_board: &[Vec<u64>], base: u32) -> Vec<Evaluation> {
+    evaluate_moves(game_board, adaptive_depth(game_board, base))
+}
+
+pub struct PlanStep {
+    pub direction: Direction,
+    pub expected: f64,
+    pub risk: f64,
+}
+
+pub fn recovery_plan(game_board: &[Vec<u64>], depth: u32, steps: usize) -> Vec<PlanStep> {
+    let mut board = game_board.to_vec();
+    let mut plan: Vec<PlanStep> = Vec::new();
+    for _ in 0..steps {
+        let Some(best) = evaluate_moves(&board, depth)
+            .into_iter()
+            .max_by(|a, b| a.expected.total_cmp(&b.expected))
+        else {
+            break;
+        };
+        apply_move(&mut board, best.direction);
+        place_worst_spawn(&mut board);
+        plan.push(PlanStep {
+            direction: best.direction,
+            expected: best.expected,
+            risk: heuristic::risk(&board),
+        });
+    }
+    plan
+}
+
+fn place_worst_spawn(game_board: &mut [Vec<u64>]) {
+    let mut worst: Option<((usize, usize), f64)> = None;
+    for i in 0..game_board.len() {
+        for j in 0..game_board[i].len() {
+            if game_board[i][j] != 0 {
+                continue;
+            }
+            game_board[i][j] = 2;
+            let value = heuristic::evaluate(game_board);
+            game_board[i][j] = 0;
+            if worst.is_none_or(|(_, lowest)| value < lowest) {
+                worst = Some(((i, j), value));
+            }
+        }
+    }
+    if let Some(((i, j), _)) = worst {
+        game_board[i][j] = 2;
+    }
+}
+
+/// Finds the empty cell where a new `value` tile hurts the player most, looking `depth`
+/// player moves ahead. The opponent is assumed to keep placing 2s after this one, and a
+/// position that is lost sooner counts as worse.
+pub fn worst_placement(game_board: &[Vec<u64>], value: u64, depth: u32) -> Option<(usize, usize)> {
+    let mut board = game_board.to_vec();
+    let mut worst: Option<((usize, usize), f64)> = None;
+    for (i, j) in empty_positions(&board) {
+        board[i][j] = value;
+        let beta = worst.map_or(f64::INFINITY, |(_, score)| score);
+        let score = player_node(&board, depth.max(1), f64::NEG_INFINITY, beta);
+        board[i][j] = 0;
+        if worst.is_none_or(|(_, lowest)| score < lowest) {
+            worst = Some(((i, j), score));
+        }
+    }
+    worst.map(|(cell, _)| cell)
+}
+
+fn empty_positions(game_board: &[Vec<u64>]) -> Vec<(usize, usize)> {
+    let mut positions = Vec::new();
+    for (i, row) in game_board.iter().enumerate() {
+        for (j, &val) in row.iter().enumerate() {
+            if val == 0 {
+                positions.push((i, j));
+            }
+        }
+    }
+    positions
+}
+
+fn player_node(game_board: &[Vec<u64>], depth: u32, mut alpha: f64, beta: f64) -> f64 {
+    let mut best = LOSS - depth as f64;
+    for direction in Direction::ALL {
+        let mut next = game_board.to_vec();
+        if !apply_move(&mut next, direction).moved {
+            continue;
+        }
+        let score = if depth <= 1 {
+            heuristic::evaluate(&next)
+        } else {
+            placement_node(&mut next, depth - 1, alpha, beta)
+        };
+        best = best.max(score);
+        alpha = alpha.max(best);
+        if alpha >= beta {
+            break;
+        }
+    }
+    best
+}
+
+fn placement_node(game_board: &mut [Vec<u64>], depth: u32, alpha: f64, mut beta: f64) -> f64 {
+    let mut worst = f64::INFINITY;
+    for (i, j) in empty_positions(game_board) {
+        game_board[i][j] = 2;
+        let score = player_node(game_board, depth, alpha, beta);
+        game_board[i][j] = 0;
+        worst = worst.min(score);
+        beta = beta.min(worst);
+        if alpha >= beta {
+            break;
+        }
+    }
+    if worst == f64::INFINITY {
+        heuristic::evaluate(game_board)
+    } else {
+        worst
+    }
+}
+
+#[cfg(feature = "research")]
+pub fn evaluate_moves_traced<T: Tracer>(
+    game_board: &[Vec<u64>],
+    depth: u32,
+    tracer: &mut T,
+) -> Vec<Evaluation> {
+    search(game_board, depth, &Weights::default(), tracer)
+}
+
+fn search<T: Tracer>(
+    game_board: &[Vec<u64>],
+    depth: u32,
+    weights: &Weights,
+    tracer: &mut T,
+) -> Vec<Evaluation> {
+    let mut search = Search {
+        weights,
+        tracer,
+        table: HashMap::new(),
+    };
+    let mut evaluations: Vec<Evaluation> = Vec::new();
+    for direction in Direction::ALL {
+        let mut next_board = game_board.to_vec();
+        if apply_move(&mut next_board, direction).moved {
+            evaluations.push(Evaluation {
+                direction,
+                expected: search.chance_node(&next_board, depth.saturating_sub(1), 1.0),
+            });
+        }
+    }
+    evaluations
+}
+
+fn zobrist(cell: usize, exponent: u32) -> u64 {
+    let mut key = (cell as u64) << 6 | exponent as u64;
+    key = key.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    key = (key ^ (key >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    key = (key ^ (key >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    key ^ (key >> 31)
+}
+
+fn exponent(val: u64) -> u32 {
+    if val == OBSTACLE {
+        0
+    } else {
+        val.trailing_zeros()
+    }
+}
+
+pub fn board_hash(game_board: &[Vec<u64>]) -> u64 {
+    game_board
+        .iter()
+        .flatten()
+        .enumerate()
+        .filter(|&(_, &val)| val != 0)
+        .fold(0, |hash, (cell, &val)| hash ^ zobrist(cell, exponent(val)))
+}
+
+// Bit 0 flips the rows, bit 1 flips the columns and bit 2 transposes.
+fn symmetric_cell(size: usize, (i, j): (usize, usize), symmetry: usize) -> usize {
+    let (i, j) = if symmetry & 4 != 0 { (j, i) } else { (i, j) };
+    let i = if symmetry & 1 != 0 { size - 1 - i } else { i };
+    let j = if symmetry & 2 != 0 { size - 1 - j } else { j };
+    i * size + j
+}
+
+/// Hashes a square board the same way as all its rotations and reflections.
+pub fn canonical_hash(game_board: &[Vec<u64>]) -> u64 {
+    let size = game_board.len();
+    if game_board.iter().any(|row| row.len() != size) {
+        return board_hash(game_board);
+    }
+    let mut hashes = [0u64; 8];
+    for (i, row) in game_board.iter().enumerate() {
+        for (j, &val) in row.iter().enumerate().filter(|&(_, &val)| val != 0) {
+            for (symmetry, hash) in hashes.iter_mut().enumerate() {
+                *hash ^= zobrist(symmetric_cell(size, (i, j), symmetry), exponent(val));
+            }
+        }
+    }
+    hashes.into_iter().min().unwrap_or(0)
+}
+
+struct Search<'a, T: Tracer> {
+    weights: &'a Weights,
+    tracer: &'a mut T,
+    table: HashMap<(u64, u32), f64>,
+}
+
+impl<T: Tracer> Search<'_, T> {
+    fn leaf(&mut self, game_board: &[Vec<u64>], depth: u32, probability: f64) -> f64 {
+        let value = heuristic::evaluate_with(game_board, self.weights);
+        self.tracer.on_search_node(&SearchNode {
+            kind: NodeKind::Leaf,
+            depth,
+            probability,
+            value,
+        });
+        value
+    }
+
+    fn max_node(&mut self, game_board: &[Vec<u64>], depth: u32, probability: f64) -> f64 {
+        let mut best: Option<f64> = None;
+        for direction in Direction::ALL {
+            let mut next_board = game_board.to_vec();
+            if apply_move(&mut next_board, direction).moved {
+                let value = self.chance_node(&next_board, depth.saturating_sub(1), probability);
+                best = Some(best.map_or(value, |b: f64| b.max(value)));
+            }
+        }
+        let Some(value) = best else {
+            return self.leaf(game_board, depth, probability);
+        };
+        self.tracer.on_search_node(&SearchNode {
+            kind: NodeKind::Max,
+            depth,
+            probability,
+            value,
+        });
+        value
+    }
+
+    fn chance_node(&mut self, game_board: &[Vec<u64>], depth: u32, probability: f64) -> f64 {
+        if depth == 0 || probability < MIN_PROBABILITY {
+            return self.leaf(game_board, depth, probability);
+        }
+        let key = (canonical_hash(game_board), depth);
+        if let Some(&value) = self.table.get(&key) {
+            return value;
+        }
+
+        let mut empty_cells: Vec<(usize, usize)> = Vec::new();
+        for (i, row) in game_board.iter().enumerate() {
+            for (j, &cell) in row.iter().enumerate() {
+                if cell == 0 {
+                    empty_cells.push((i, j));
+                }
+            }
+        }
+        if empty_cells.is_empty() {
+            return self.leaf(game_board, depth, probability);
+        }
+
+        let cell_probability = 1.0 / empty_cells.len() as f64;
+        let mut expected = 0.0;
+        for &(i, j) in &empty_cells {
+            for (value, spawn_probability) in [(2, 0.9), (4, 0.1)] {
+                let mut next_board = game_board.to_vec();
+                next_board[i][j] = value;
+                let weight = cell_probability * spawn_probability;
+                expected += weight * self.max_node(&next_board, depth, probability * weight);
+            }
+        }
+        self.tracer.on_search_node(&SearchNode {
+            kind: NodeKind::Chance,
+            depth,
+            probability,
+            value: expected,
+        });
+        self.table.insert(key, expected);
+        expected
+    }
+}
@@ -0,0 +1,155 @@
+use crate::i18n::{self, label, t, tf};
+use crate::{board_to_text, vault, GameState};
+use rust_2048_game::{ai, heuristic, tablebase::Tablebase};
+use std::{
+    path::{Path, PathBuf},
+    time::Instant,
+};
+
+const ON_DEMAND_LIMIT: usize = 2_000_000;
+
+pub fn default_tablebase(size: usize) -> PathBuf {
+    PathBuf::from(format!("tablebase-{}x{}.bin", size, size))
+}
+
+pub fn generate_tablebase(size: usize, output: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    println!(" > {}", tf("analysis.solving", &[("size", &size)]));
+    let started = Instant::now();
+    let tablebase = Tablebase::generate(size);
+    tablebase.save(output)?;
+    println!(
+        " > {}",
+        tf(
+            "analysis.saved",
+            &[
+                ("positions", &tablebase.len()),
+                ("path", &output.display()),
+                ("secs", &format!("{:.1}", started.elapsed().as_secs_f64()))
+            ]
+        )
+    );
+    Ok(())
+}
+
+fn open_tablebase(size: usize, path: Option<&Path>) -> Result<Tablebase, std::io::Error> {
+    let path = path.map_or_else(|| default_tablebase(size), Path::to_path_buf);
+    if path.exists() {
+        let tablebase = Tablebase::load(&path)?;
+        if tablebase.size() == size {
+            return Ok(tablebase.limited(ON_DEMAND_LIMIT));
+        }
+        println!(
+            " > {}",
+            tf("analysis.wrong_size", &[("path", &path.display())])
+        );
+    }
+    Ok(Tablebase::new(size).limited(ON_DEMAND_LIMIT))
+}
+
+fn print_tablebase(game_board: &[Vec<u64>], path: Option<&Path>) -> Result<(), std::io::Error> {
+    let size = game_board.len();
+    if !(2..=rust_2048_game::tablebase::MAX_SIZE).contains(&size) {
+        return Ok(());
+    }
+    let mut tablebase = open_tablebase(size, path)?;
+    if !tablebase.supports(game_board) {
+        return Ok(());
+    }
+
+    let values = tablebase.move_values(game_board);
+    if tablebase.exhausted() {
+        println!();
+        println!(" > {}", tf("analysis.too_many", &[("size", &size)]));
+        return Ok(());
+    }
+    let best = values
+        .iter()
+        .map(|value| value.expected)
+        .fold(f64::MIN, f64::max);
+    println!();
+    println!(" > {}", t("analysis.tablebase"));
+    for value in &values {
+        let optimal = if value.expected == best {
+            format!("  {}", t("analysis.optimal"))
+        } else {
+            String::new()
+        };
+        println!(
+            "   {:<9} {:.2}{}",
+            i18n::direction(value.direction),
+            value.expected,
+            optimal
+        );
+    }
+    Ok(())
+}
+
+pub fn run(
+    input: &Path,
+    depth: u32,
+    tablebase: Option<&Path>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let data = vault::read(input)?;
+    let state: GameState = serde_json::from_str(&data)?;
+    let game_board = state.game_board;
+    let metrics = heuristic::metrics(&game_board);
+
+    println!(" > {}", tf("analysis.title", &[("path", &input.display())]));
+    println!();
+    print!("{}", board_to_text(&game_board));
+    println!();
+    println!(" > {} {}", label("label.empty_cells"), metrics.empty_cells);
+    println!(
+        " > {} {:.3}",
+        label("label.monotonicity"),
+        metrics.monotonicity
+    );
+    println!(" > {} {:.3}", label("label.smoothness"), metrics.smoothness);
+    println!(
+        " > {} {}",
+        label("label.entropy"),
+        tf(
+            "analysis.bits",
+            &[("bits", &format!("{:.3}", metrics.entropy))]
+        )
+    );
+    println!(" > {} {:.0}%", label("label.risk"), metrics.risk * 100.0);
+    println!();
+
+    let mut evaluations = ai::evaluate_moves(&game_board, depth);
+    if evaluations.is_empty() {
+        println!(" > {}", t("analysis.over"));
+        return Ok(());
+    }
+    evaluations.sort_by(|a, b| b.expected.total_cmp(&a.expected));
+    println!(" > {}", tf("analysis.moves", &[("depth", &depth)]));
+    for evaluation in &evaluations {
+        println!(
+            "   {:<9} {:.3}",
+            i18n::direction(evaluation.direction),
+            evaluation.expected
+        );
+    }
+
+    print_tablebase(&game_board, tablebase)?;
+
+    if metrics.risk >= heuristic::COMPROMISED_RISK {
+        println!();
+        println!(" > {}", t("analysis.compromised"));
+        for (number, step) in ai::recovery_plan(&game_board, depth, 5).iter().enumerate() {
+            println!(
+                "   {}. {:<9} {}",
+                number + 1,
+                i18n::direction(step.direction),
+                tf(
+                    "analysis.step",
+                    &[
+                        ("expected", &format!("{:.3}", step.expected)),
+                        ("risk", &format!("{:.0}", step.risk * 100.0))
+                    ]
+                )
+            );
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,123 @@
+use crate::bot::{self, BotStrategy};
+use rand::{rngs::StdRng, SeedableRng};
+use rayon::prelude::*;
+use rust_2048_game::ai::Evaluation;
+use rust_2048_game::engine::max_tile;
+use rust_2048_game::game::Game;
+use rust_2048_game::mcts::MctsConfig;
+use std::{
+    ops::Range,
+    time::{Duration, Instant},
+};
+
+pub struct Outcome {
+    pub score: u64,
+    pub max_tile: u64,
+    pub moves: u32,
+    pub thinking: Duration,
+}
+
+pub fn play_game(seed: u64, mut choose: impl FnMut(&[Vec<u64>]) -> Vec<Evaluation>) -> Outcome {
+    let mut game = Game::seeded(4, seed);
+    let mut moves = 0;
+    let started = Instant::now();
+    loop {
+        let evaluations = choose(game.board());
+        let Some(best) = bot::best_move(&evaluations) else {
+            break;
+        };
+        game.play(best.direction);
+        moves += 1;
+    }
+    Outcome {
+        score: game.score(),
+        max_tile: max_tile(game.board()),
+        moves,
+        thinking: started.elapsed(),
+    }
+}
+
+pub struct Summary {
+    pub games: usize,
+    pub average_score: f64,
+    pub best_score: u64,
+    pub max_tile: u64,
+    pub rate_2048: f64,
+    pub average_moves: f64,
+    pub ms_per_move: f64,
+}
+
+impl Summary {
+    pub fn of(outcomes: &[Outcome]) -> Self {
+        let games = outcomes.len().max(1) as f64;
+        let moves: u32 = outcomes.iter().map(|outcome| outcome.moves).sum();
+        let thinking: Duration = outcomes.iter().map(|outcome| outcome.thinking).sum();
+        let reached = outcomes
+            .iter()
+            .filter(|outcome| outcome.max_tile >= 2048)
+            .count();
+        Summary {
+            games: outcomes.len(),
+            average_score: outcomes.iter().map(|outcome| outcome.score).sum::<u64>() as f64 / games,
+            best_score: outcomes
+                .iter()
+                .map(|outcome| outcome.score)
+                .max()
+                .unwrap_or(0),
+            max_tile: outcomes
+                .iter()
+                .map(|outcome| outcome.max_tile)
+                .max()
+                .unwrap_or(0),
+            rate_2048: reached as f64 / games,
+            average_moves: moves as f64 / games,
+            ms_per_move: thinking.as_secs_f64() * 1000.0 / moves.max(1) as f64,
+        }
+    }
+}
+
+pub fn play_many(seeds: Range<u64>, play: impl Fn(u64) -> Outcome + Send + Sync) -> Vec<Outcome> {
+    seeds.into_par_iter().map(play).collect()
+}
+
+fn play_one(strategy: BotStrategy, seed: u64, config: &MctsConfig) -> Outcome {
+    let mut rng = StdRng::seed_from_u64(seed);
+    play_game(seed, |game_board| {
+        bot::evaluate(game_board, strategy, config, &mut rng)
+    })
+}
+
+pub fn run(games: u32, seed: u64, config: MctsConfig) {
+    let games = games.max(1) as u64;
+    println!(
+        " > {} games per strategy from seed {} on {} threads, MCTS with {} rollouts{}",
+        games,
+        seed,
+        rayon::current_num_threads(),
+        config.rollouts,
+        config.budget.map_or(String::new(), |budget| format!(
+            " or {} ms per move",
+            budget.as_millis()
+        ))
+    );
+    println!();
+    println!(
+        "   {:<11} {:>10} {:>8} {:>9} {:>9} {:>9} {:>9}",
+        "strategy", "avg score", "best", "max tile", "2048 rate", "ms/move", "wall time"
+    );
+    for strategy in [BotStrategy::Expectimax, BotStrategy::Mcts] {
+        let started = Instant::now();
+        let outcomes = play_many(seed..seed + games, |seed| play_one(strategy, seed, &config));
+        let summary = Summary::of(&outcomes);
+        println!(
+            "   {:<11} {:>10.0} {:>8} {:>9} {:>8.0}% {:>9.2} {:>8.1}s",
+            format!("{:?}", strategy).to_lowercase(),
+            summary.average_score,
+            summary.best_score,
+            summary.max_tile,
+            summary.rate_2048 * 100.0,
+            summary.ms_per_move,
+            started.elapsed().as_secs_f64()
+        );
+    }
+}
@@ -0,0 +1,259 @@
+use crate::accessibility::Narrator;
+use crate::broadcast::BroadcastObserver;
+use crate::config::load_config;
+use crate::help;
+use crate::i18n::{self, label, t, tf};
+use crate::sound::Sound;
+use crate::theme::initialize_colors;
+use crate::{campaign, integrations, read_high_score, render_board};
+use clap::ValueEnum;
+use crossterm::{
+    event::{poll, read, Event, KeyCode},
+    terminal::{disable_raw_mode, enable_raw_mode},
+};
+use rand::{rngs::StdRng, thread_rng, Rng, SeedableRng};
+use rust_2048_game::ai::{self, Evaluation};
+use rust_2048_game::game::Game;
+use rust_2048_game::mcts::{self, MctsConfig};
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+const DEPTH: u32 = 2;
+const DEMO_STEP: Duration = Duration::from_millis(250);
+const DEMO_RESTART: Duration = Duration::from_secs(3);
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, ValueEnum)]
+pub enum BotStrategy {
+    Expectimax,
+    Mcts,
+}
+
+pub fn evaluate(
+    game_board: &[Vec<u64>],
+    strategy: BotStrategy,
+    config: &MctsConfig,
+    rng: &mut StdRng,
+) -> Vec<Evaluation> {
+    match strategy {
+        BotStrategy::Expectimax => ai::evaluate_moves_adaptive(game_board, DEPTH),
+        BotStrategy::Mcts => mcts::evaluate_moves(game_board, config, rng),
+    }
+}
+
+pub fn best_move(evaluations: &[Evaluation]) -> Option<&Evaluation> {
+    evaluations
+        .iter()
+        .max_by(|a, b| a.expected.total_cmp(&b.expected))
+}
+const SPEEDS_MS: [u64; 6] = [1000, 500, 250, 100, 50, 10];
+
+struct Scheduler {
+    speed: usize,
+    paused: bool,
+    next_step: Instant,
+}
+
+impl Scheduler {
+    fn delay(&self) -> Duration {
+        Duration::from_millis(SPEEDS_MS[self.speed])
+    }
+
+    fn wait(&self) -> Option<Duration> {
+        (!self.paused).then(|| self.next_step.saturating_duration_since(Instant::now()))
+    }
+
+    fn due(&self) -> bool {
+        !self.paused && Instant::now() >= self.next_step
+    }
+
+    fn stepped(&mut self) {
+        self.next_step = Instant::now() + self.delay();
+    }
+
+    fn faster(&mut self) {
+        self.speed = (self.speed + 1).min(SPEEDS_MS.len() - 1);
+        self.stepped();
+    }
+
+    fn slower(&mut self) {
+        self.speed = self.speed.saturating_sub(1);
+        self.stepped();
+    }
+
+    fn toggle_pause(&mut self) {
+        self.paused = !self.paused;
+        self.stepped();
+    }
+}
+
+fn evaluation_line(evaluations: &[Evaluation]) -> String {
+    let best = evaluations
+        .iter()
+        .map(|evaluation| evaluation.expected)
+        .fold(f64::NEG_INFINITY, f64::max);
+    let scores: Vec<String> = evaluations
+        .iter()
+        .map(|evaluation| {
+            let marker = if evaluation.expected == best { "*" } else { "" };
+            format!(
+                "{} {:.1}{}",
+                i18n::direction(evaluation.direction),
+                evaluation.expected,
+                marker
+            )
+        })
+        .collect();
+    format!("{} {}", label("label.evaluations"), scores.join("  "))
+}
+
+pub fn run(strategy: BotStrategy, config: MctsConfig) -> crossterm::Result<()> {
+    let mut colors: HashMap<u64, colored::Color> = HashMap::new();
+    initialize_colors(&mut colors, campaign::active_theme());
+    let mut sound = Sound::new(&load_config().sound);
+    let high_score = read_high_score();
+    let mut show_risk = false;
+
+    let mut game = Game::new(4);
+    game.subscribe(Box::new(BroadcastObserver));
+    game.subscribe(Box::new(Narrator));
+    for observer in integrations(t("presence.bot")) {
+        game.subscribe(observer);
+    }
+    let mut rng = StdRng::seed_from_u64(thread_rng().gen());
+    let mut evaluations = evaluate(game.board(), strategy, &config, &mut rng);
+    let mut played: u32 = 0;
+    let mut scheduler = Scheduler {
+        speed: 2,
+        paused: false,
+        next_step: Instant::now(),
+    };
+    scheduler.stepped();
+
+    enable_raw_mode()?;
+    loop {
+        let state = if scheduler.paused {
+            t("bot.paused").to_string()
+        } else {
+            tf("bot.running", &[("ms", &SPEEDS_MS[scheduler.speed])])
+        };
+        let messages = vec![
+            format!(
+                "{} {}",
+                label("label.bot"),
+                tf(
+                    "bot.status",
+                    &[
+                        ("strategy", &format!("{:?}", strategy)),
+                        ("state", &state),
+                        ("moves", &played)
+                    ]
+                )
+            ),
+            evaluation_line(&evaluations),
+            t("bot.hint").to_string(),
+        ];
+        render_board(
+            game.board(),
+            &colors,
+            game.score(),
+            high_score,
+            show_risk,
+            &messages,
+        )?;
+        if evaluations.is_empty() {
+            println!(" >> {} <<", t("game.over"));
+            break;
+        }
+
+        let mut step = scheduler.due();
+        if !step {
+            let key = match scheduler.wait() {
+                Some(timeout) if !poll(timeout)? => None,
+                _ => Some(read()?),
+            };
+            if let Some(Event::Key(key_event)) = key {
+                match key_event.code {
+                    KeyCode::Char('e') | KeyCode::Char('E') => break,
+                    KeyCode::Char('r') | KeyCode::Char('R') => show_risk = !show_risk,
+                    KeyCode::Char('m') | KeyCode::Char('M') => {
+                        sound.toggle_mute();
+                    }
+                    KeyCode::Char('p') | KeyCode::Char('P') => scheduler.toggle_pause(),
+                    KeyCode::Char('+') | KeyCode::Char('=') => scheduler.faster(),
+                    KeyCode::Char('-') => scheduler.slower(),
+                    KeyCode::Char(' ') => {
+                        scheduler.paused = true;
+                        step = true;
+                    }
+                    KeyCode::Char('?') | KeyCode::F(1) => {
+                        help::show(
+                            t("mode.bot"),
+                            t("bot.objective"),
+                            &[
+                                (t("help.key.space"), t("bot.action.step")),
+                                ("P", t("bot.action.pause")),
+                                ("+ / -", t("bot.action.speed")),
+                            ],
+                        )?;
+                        scheduler.stepped();
+                    }
+                    _ => {}
+                }
+            }
+        }
+        if !step {
+            continue;
+        }
+
+        let Some(best) = best_move(&evaluations) else {
+            continue;
+        };
+        let direction = best.direction;
+        let result = game.play(direction);
+        if result.moved {
+            sound.play_move(&result);
+            played += 1;
+        }
+        evaluations = evaluate(game.board(), strategy, &config, &mut rng);
+        scheduler.stepped();
+    }
+    disable_raw_mode()?;
+    Ok(())
+}
+
+pub fn demo() -> crossterm::Result<()> {
+    let mut colors: HashMap<u64, colored::Color> = HashMap::new();
+    initialize_colors(&mut colors, campaign::active_theme());
+    let high_score = read_high_score();
+    let banner = format!("{} {}", label("label.demo"), t("bot.demo"));
+
+    let mut game = Game::new(4);
+    loop {
+        render_board(
+            game.board(),
+            &colors,
+            game.score(),
+            high_score,
+            false,
+            std::slice::from_ref(&banner),
+        )?;
+        let evaluations = ai::evaluate_moves_adaptive(game.board(), DEPTH);
+        let delay = if evaluations.is_empty() {
+            DEMO_RESTART
+        } else {
+            DEMO_STEP
+        };
+        if poll(delay)? {
+            read()?;
+            return Ok(());
+        }
+        match best_move(&evaluations) {
+            Some(best) => {
+                game.play(best.direction);
+            }
+            None => game = Game::new(4),
+        }
+    }
+}
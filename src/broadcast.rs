@@ -0,0 +1,176 @@
+use crate::i18n::t;
+use rust_2048_game::engine::{Direction, MoveResult};
+use rust_2048_game::game::GameObserver;
+use serde::Serialize;
+#[cfg(unix)]
+use std::os::unix::net::UnixListener;
+use std::{
+    fs,
+    io::{self, Write},
+    net::TcpListener,
+    path::PathBuf,
+    sync::Mutex,
+    time::Duration,
+};
+
+const WRITE_TIMEOUT: Duration = Duration::from_millis(50);
+
+enum Listener {
+    #[cfg(unix)]
+    Unix(UnixListener, PathBuf),
+    Tcp(TcpListener),
+}
+
+struct Broadcaster {
+    listener: Listener,
+    clients: Vec<Box<dyn Write + Send>>,
+}
+
+static BROADCASTER: Mutex<Option<Broadcaster>> = Mutex::new(None);
+
+#[derive(Serialize)]
+struct MergeEvent {
+    position: (usize, usize),
+    value: u64,
+}
+
+#[derive(Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+enum BroadcastEvent<'a> {
+    Board {
+        board: &'a [Vec<u64>],
+        score: u64,
+    },
+    Move {
+        direction: String,
+        merges: Vec<MergeEvent>,
+        slides: usize,
+    },
+    GameOver {
+        score: u64,
+        reason: &'a str,
+    },
+}
+
+pub fn start(target: &str) -> io::Result<()> {
+    let listener = match target.strip_prefix("tcp://") {
+        Some(address) => {
+            let listener = TcpListener::bind(address)?;
+            listener.set_nonblocking(true)?;
+            Listener::Tcp(listener)
+        }
+        #[cfg(unix)]
+        None => {
+            let listener = UnixListener::bind(target)?;
+            listener.set_nonblocking(true)?;
+            Listener::Unix(listener, PathBuf::from(target))
+        }
+        #[cfg(not(unix))]
+        None => {
+            return Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "Unix sockets are not available here, use tcp://host:port",
+            ))
+        }
+    };
+    if let Ok(mut broadcaster) = BROADCASTER.lock() {
+        *broadcaster = Some(Broadcaster {
+            listener,
+            clients: Vec::new(),
+        });
+    }
+    Ok(())
+}
+
+pub fn stop() {
+    let Ok(mut broadcaster) = BROADCASTER.lock() else {
+        return;
+    };
+    #[cfg(unix)]
+    if let Some(Broadcaster {
+        listener: Listener::Unix(_, path),
+        ..
+    }) = broadcaster.take()
+    {
+        let _ = fs::remove_file(path);
+    }
+}
+
+fn accept_clients(broadcaster: &mut Broadcaster) {
+    loop {
+        let client: Box<dyn Write + Send> = match &broadcaster.listener {
+            #[cfg(unix)]
+            Listener::Unix(listener, _) => match listener.accept() {
+                Ok((stream, _)) if stream.set_write_timeout(Some(WRITE_TIMEOUT)).is_ok() => {
+                    Box::new(stream)
+                }
+                _ => return,
+            },
+            Listener::Tcp(listener) => match listener.accept() {
+                Ok((stream, _)) if stream.set_write_timeout(Some(WRITE_TIMEOUT)).is_ok() => {
+                    Box::new(stream)
+                }
+                _ => return,
+            },
+        };
+        broadcaster.clients.push(client);
+    }
+}
+
+fn send(event: &BroadcastEvent) {
+    let Ok(mut guard) = BROADCASTER.lock() else {
+        return;
+    };
+    #[cfg(feature = "web")]
+    if crate::web::serving() {
+        if let Ok(text) = serde_json::to_string(event) {
+            crate::web::publish(&text, matches!(event, BroadcastEvent::Board { .. }));
+        }
+    }
+    let Some(broadcaster) = guard.as_mut() else {
+        return;
+    };
+    accept_clients(broadcaster);
+    let Ok(mut line) = serde_json::to_string(event) else {
+        return;
+    };
+    line.push('\n');
+    broadcaster
+        .clients
+        .retain_mut(|client| client.write_all(line.as_bytes()).is_ok());
+}
+
+pub fn send_board(board: &[Vec<u64>], score: u64) {
+    send(&BroadcastEvent::Board { board, score });
+}
+
+pub fn send_move(direction: Direction, result: &MoveResult) {
+    send(&BroadcastEvent::Move {
+        direction: format!("{:?}", direction).to_lowercase(),
+        merges: result
+            .merges
+            .iter()
+            .map(|merge| MergeEvent {
+                position: merge.position,
+                value: merge.value,
+            })
+            .collect(),
+        slides: result.slides.len(),
+    });
+}
+
+pub fn send_game_over(score: u64, reason: &str) {
+    send(&BroadcastEvent::GameOver { score, reason });
+}
+
+pub struct BroadcastObserver;
+
+impl GameObserver for BroadcastObserver {
+    fn on_move(&mut self, direction: Direction, result: &MoveResult) {
+        send_move(direction, result);
+    }
+
+    fn on_game_over(&mut self, score: u64) {
+        send_game_over(score, t("broadcast.no_moves"));
+    }
+}
@@ -0,0 +1,290 @@
+use crate::error::GameError;
+use crate::i18n::{t, tf};
+use crate::logging;
+use crate::profile;
+use crate::signing::{self, KEY_FILE};
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fs::{self, File},
+    io::Read,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// Bumped whenever a bundle stops being readable by an older build.
+const FORMAT: u32 = 1;
+const MANIFEST_FILE: &str = "manifest.json";
+
+#[derive(Serialize, Deserialize)]
+struct Manifest {
+    format: u32,
+    version: String,
+    profile: Option<String>,
+    created: u64,
+    files: Vec<String>,
+}
+
+pub struct Imported {
+    pub file: String,
+    pub backup: Option<PathBuf>,
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or(0)
+}
+
+fn append(
+    builder: &mut tar::Builder<GzEncoder<File>>,
+    name: &str,
+    data: &[u8],
+) -> Result<(), GameError> {
+    let mut header = tar::Header::new_gnu();
+    header.set_size(data.len() as u64);
+    header.set_mode(0o644);
+    header.set_mtime(now());
+    header.set_cksum();
+    builder.append_data(&mut header, name, data)?;
+    Ok(())
+}
+
+/// Packs the active profile's saves, stats, history and config into one tar.gz. Files
+/// protected by the vault go in still encrypted, so the same passphrase opens them again.
+pub fn export(path: &Path) -> Result<Vec<&'static str>, GameError> {
+    let mut contents = Vec::new();
+    for file in profile::DATA_FILES {
+        match fs::read(profile::path(file)) {
+            Ok(data) => contents.push((file, data)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e.into()),
+        }
+    }
+    // Signed scores only verify against the key that signed them.
+    if signing::has_install_key() {
+        contents.push((KEY_FILE, fs::read(KEY_FILE)?));
+    }
+    let manifest = Manifest {
+        format: FORMAT,
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        profile: profile::active().map(str::to_string),
+        created: now(),
+        files: contents.iter().map(|(file, _)| file.to_string()).collect(),
+    };
+
+    let mut builder =
+        tar::Builder::new(GzEncoder::new(File::create(path)?, Compression::default()));
+    append(
+        &mut builder,
+        MANIFEST_FILE,
+        &serde_json::to_vec_pretty(&manifest)?,
+    )?;
+    for (file, data) in &contents {
+        append(&mut builder, file, data)?;
+    }
+    builder.into_inner()?.finish()?;
+    Ok(contents.into_iter().map(|(file, _)| file).collect())
+}
+
+fn read_entries(path: &Path) -> Result<BTreeMap<String, Vec<u8>>, GameError> {
+    let mut archive = tar::Archive::new(GzDecoder::new(File::open(path)?));
+    let mut entries = BTreeMap::new();
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+        let name = entry.path()?.to_string_lossy().into_owned();
+        let mut data = Vec::new();
+        entry.read_to_end(&mut data)?;
+        entries.insert(name, data);
+    }
+    Ok(entries)
+}
+
+fn backup(path: &Path, file: &str, stamp: u64) -> Result<PathBuf, GameError> {
+    let backup = path.with_file_name(format!("{}.before-import-{}", file, stamp));
+    fs::copy(path, &backup)?;
+    Ok(backup)
+}
+
+/// Unpacks a bundle into the active profile. Files that differ from the bundle are kept
+/// as backups first, and a bundle from a newer format is refused before anything is written.
+pub fn import(path: &Path) -> Result<Vec<Imported>, GameError> {
+    let mut entries = read_entries(path)?;
+    let manifest: Manifest = match entries.remove(MANIFEST_FILE) {
+        Some(data) => serde_json::from_slice(&data)?,
+        None => {
+            return Err(GameError::Config(tf(
+                "bundle.not_bundle",
+                &[("path", &path.display()), ("manifest", &MANIFEST_FILE)],
+            )))
+        }
+    };
+    if manifest.format > FORMAT {
+        return Err(GameError::Config(tf(
+            "bundle.too_new",
+            &[
+                ("path", &path.display()),
+                ("version", &manifest.version),
+                ("format", &manifest.format),
+                ("supported", &FORMAT),
+            ],
+        )));
+    }
+    if manifest.version != env!("CARGO_PKG_VERSION") {
+        tracing::info!(version = %manifest.version, "importing a bundle from another version");
+    }
+
+    let stamp = now();
+    let mut imported = Vec::new();
+    for file in profile::DATA_FILES {
+        let Some(data) = entries.get(file) else {
+            continue;
+        };
+        let target = profile::path(file);
+        let backup = match fs::read(&target) {
+            Ok(current) if current == *data => continue,
+            Ok(_) => Some(backup(&target, file, stamp)?),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+            Err(e) => return Err(e.into()),
+        };
+        if let Some(parent) = target
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+        {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&target, data)?;
+        imported.push(Imported {
+            file: file.to_string(),
+            backup,
+        });
+    }
+    // The install key is shared by every profile, so only a fresh install takes it over.
+    if let Some(key) = entries.get(KEY_FILE) {
+        if !signing::has_install_key() {
+            fs::write(KEY_FILE, key)?;
+            imported.push(Imported {
+                file: KEY_FILE.to_string(),
+                backup: None,
+            });
+        } else if fs::read(KEY_FILE)? != *key {
+            println!(" > {}", t("bundle.own_key"));
+        }
+    }
+    Ok(imported)
+}
+
+pub fn show_export(path: &Path) {
+    match export(path) {
+        Ok(files) if files.is_empty() => {
+            println!(" > {}", tf("bundle.nothing", &[("path", &path.display())]))
+        }
+        Ok(files) => println!(
+            " > {}",
+            tf(
+                "bundle.exported",
+                &[("files", &files.join(", ")), ("path", &path.display())]
+            )
+        ),
+        Err(e) => {
+            logging::error(tf("error.export_data", &[("error", &e)]));
+            std::process::exit(1);
+        }
+    }
+}
+
+pub fn show_import(path: &Path) {
+    match import(path) {
+        Ok(imported) if imported.is_empty() => {
+            println!(" > {}", tf("bundle.matches", &[("path", &path.display())]))
+        }
+        Ok(imported) => {
+            for file in &imported {
+                match &file.backup {
+                    Some(backup) => println!(
+                        " > {}",
+                        tf(
+                            "bundle.imported_backup",
+                            &[("file", &file.file), ("backup", &backup.display())]
+                        )
+                    ),
+                    None => println!(" > {}", tf("bundle.imported", &[("file", &file.file)])),
+                }
+            }
+        }
+        Err(e) => {
+            logging::error(tf("error.import_data", &[("error", &e)]));
+            std::process::exit(1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scratch;
+
+    #[test]
+    fn import_backs_up_files_that_differ() {
+        scratch::run(|dir| {
+            let bundle = dir.join("saves.tar.gz");
+            fs::write("stats.json", "exported").unwrap();
+            fs::write("config.toml", "same").unwrap();
+            assert_eq!(export(&bundle).unwrap(), ["stats.json", "config.toml"]);
+
+            fs::write("stats.json", "played since").unwrap();
+            let imported = import(&bundle).unwrap();
+            assert_eq!(imported.len(), 1);
+            assert_eq!(imported[0].file, "stats.json");
+            assert_eq!(fs::read_to_string("stats.json").unwrap(), "exported");
+            let backup = imported[0].backup.as_ref().unwrap();
+            assert_eq!(fs::read_to_string(backup).unwrap(), "played since");
+        });
+    }
+
+    #[test]
+    fn import_writes_missing_files_without_backups() {
+        scratch::run(|dir| {
+            let bundle = dir.join("saves.tar.gz");
+            fs::write("history.json", "[]").unwrap();
+            export(&bundle).unwrap();
+            fs::remove_file("history.json").unwrap();
+
+            let imported = import(&bundle).unwrap();
+            assert_eq!(imported.len(), 1);
+            assert!(imported[0].backup.is_none());
+            assert_eq!(fs::read_to_string("history.json").unwrap(), "[]");
+        });
+    }
+
+    #[test]
+    fn newer_format_is_refused_before_writing() {
+        scratch::run(|dir| {
+            let bundle = dir.join("future.tar.gz");
+            let manifest = Manifest {
+                format: FORMAT + 1,
+                version: "99.0.0".to_string(),
+                profile: None,
+                created: now(),
+                files: vec!["stats.json".to_string()],
+            };
+            let mut builder = tar::Builder::new(GzEncoder::new(
+                File::create(&bundle).unwrap(),
+                Compression::default(),
+            ));
+            let manifest = serde_json::to_vec(&manifest).unwrap();
+            append(&mut builder, MANIFEST_FILE, &manifest).unwrap();
+            append(&mut builder, "stats.json", b"from the future").unwrap();
+            builder.into_inner().unwrap().finish().unwrap();
+
+            fs::write("stats.json", "mine").unwrap();
+            assert!(matches!(import(&bundle), Err(GameError::Config(_))));
+            assert_eq!(fs::read_to_string("stats.json").unwrap(), "mine");
+        });
+    }
+}
@@ -0,0 +1,315 @@
+use crate::accessibility;
+use crate::broadcast;
+use crate::config::load_config;
+use crate::error::GameError;
+use crate::help;
+use crate::i18n::{label, t, tf};
+use crate::logging;
+use crate::profile;
+use crate::sound::Sound;
+use crate::theme::{initialize_colors, Theme};
+use crate::vault;
+use crate::{key_direction, read_high_score, render_board};
+use crossterm::{
+    event::{read, Event, KeyCode},
+    terminal::{disable_raw_mode, enable_raw_mode},
+};
+use rust_2048_game::engine::{
+    apply_move, calculate_score, legal_moves, max_tile, spawn_random_tile, OBSTACLE,
+};
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, fs};
+
+const PROGRESS_FILE: &str = "campaign.json";
+
+pub struct Level {
+    /// Locale key of the level's name.
+    pub name: &'static str,
+    pub size: usize,
+    pub goal: u64,
+    pub move_limit: Option<u32>,
+    pub obstacles: &'static [(usize, usize)],
+    pub reward: Option<Theme>,
+}
+
+pub const LEVELS: [Level; 6] = [
+    Level {
+        name: "campaign.level.first_steps",
+        size: 4,
+        goal: 128,
+        move_limit: None,
+        obstacles: &[],
+        reward: None,
+    },
+    Level {
+        name: "campaign.level.tight_quarters",
+        size: 3,
+        goal: 64,
+        move_limit: None,
+        obstacles: &[],
+        reward: None,
+    },
+    Level {
+        name: "campaign.level.roadblock",
+        size: 4,
+        goal: 256,
+        move_limit: None,
+        obstacles: &[(1, 1)],
+        reward: Some(Theme::Ocean),
+    },
+    Level {
+        name: "campaign.level.against_the_clock",
+        size: 4,
+        goal: 256,
+        move_limit: Some(180),
+        obstacles: &[],
+        reward: None,
+    },
+    Level {
+        name: "campaign.level.pillars",
+        size: 5,
+        goal: 512,
+        move_limit: Some(400),
+        obstacles: &[(1, 1), (3, 3)],
+        reward: None,
+    },
+    Level {
+        name: "campaign.level.the_gauntlet",
+        size: 4,
+        goal: 1024,
+        move_limit: Some(700),
+        obstacles: &[(0, 3)],
+        reward: Some(Theme::Ember),
+    },
+];
+
+#[derive(Serialize, Deserialize)]
+pub struct CampaignProgress {
+    pub completed: usize,
+    pub unlocked_themes: Vec<Theme>,
+    pub active_theme: Theme,
+}
+
+impl Default for CampaignProgress {
+    fn default() -> Self {
+        CampaignProgress {
+            completed: 0,
+            unlocked_themes: vec![Theme::Classic],
+            active_theme: Theme::Classic,
+        }
+    }
+}
+
+pub fn load_progress() -> Result<CampaignProgress, GameError> {
+    Ok(vault::load(&profile::path(PROGRESS_FILE))?.unwrap_or_default())
+}
+
+/// The theme picked in settings, the classic one while the progress cannot be read.
+pub fn active_theme() -> Theme {
+    load_progress()
+        .map(|progress| progress.active_theme)
+        .unwrap_or_else(|e| {
+            tracing::warn!(error = %e, "reading campaign progress");
+            Theme::Classic
+        })
+}
+
+pub fn save_progress(progress: &CampaignProgress) -> Result<(), GameError> {
+    let serialized = serde_json::to_string(progress)?;
+    fs::write(profile::path(PROGRESS_FILE), serialized)?;
+    Ok(())
+}
+
+enum Outcome {
+    Completed,
+    /// The locale key shown to the player and sent to broadcast listeners.
+    Failed(&'static str),
+    Quit,
+}
+
+pub fn select_theme(theme: Theme) -> bool {
+    let mut progress = match load_progress() {
+        Ok(progress) => progress,
+        Err(e) => {
+            logging::error(tf("error.load_campaign", &[("error", &e)]));
+            return false;
+        }
+    };
+    if !Theme::available(&progress.unlocked_themes).contains(&theme) {
+        println!(
+            " > {}",
+            tf(
+                "campaign.theme_locked",
+                &[("theme", &format!("{:?}", theme))]
+            )
+        );
+        return false;
+    }
+    progress.active_theme = theme;
+    if let Err(e) = save_progress(&progress) {
+        logging::error(tf("error.save_campaign", &[("error", &e)]));
+    }
+    true
+}
+
+pub fn run(level: Option<usize>) -> crossterm::Result<()> {
+    let mut progress = match load_progress() {
+        Ok(progress) => progress,
+        Err(e) => {
+            logging::error(tf("error.load_campaign", &[("error", &e)]));
+            return Ok(());
+        }
+    };
+
+    let index = match level {
+        Some(number) if number >= 1 && number <= progress.completed + 1 => number - 1,
+        Some(number) => {
+            println!(
+                " > {}",
+                tf(
+                    "campaign.locked",
+                    &[("level", &number), ("previous", &(progress.completed + 1))]
+                )
+            );
+            return Ok(());
+        }
+        None => progress.completed,
+    };
+    let Some(current) = LEVELS.get(index) else {
+        println!(" > {}", t("campaign.complete"));
+        return Ok(());
+    };
+
+    enable_raw_mode()?;
+    let outcome = play_level(index, current, progress.active_theme)?;
+    disable_raw_mode()?;
+
+    match outcome {
+        Outcome::Completed => {
+            println!(
+                " >> {} <<",
+                tf("campaign.level_complete", &[("level", &(index + 1))])
+            );
+            progress.completed = progress.completed.max(index + 1);
+            if let Some(reward) = current.reward {
+                if !progress.unlocked_themes.contains(&reward) {
+                    progress.unlocked_themes.push(reward);
+                    progress.active_theme = reward;
+                    println!(
+                        " > {}",
+                        tf("campaign.unlocked", &[("theme", &format!("{:?}", reward))])
+                    );
+                }
+            }
+            if let Err(e) = save_progress(&progress) {
+                logging::error(tf("error.save_campaign", &[("error", &e)]));
+            }
+        }
+        Outcome::Failed(key) => println!(
+            " >> {} <<",
+            tf("campaign.level_failed", &[("reason", &t(key))])
+        ),
+        Outcome::Quit => {}
+    }
+    Ok(())
+}
+
+fn level_messages(index: usize, level: &Level, moves_made: u32) -> Vec<String> {
+    let mut messages = vec![
+        tf(
+            "campaign.level",
+            &[("level", &(index + 1)), ("name", &t(level.name))],
+        ),
+        format!("{} {}", label("label.goal"), level.goal),
+    ];
+    if let Some(limit) = level.move_limit {
+        messages.push(format!(
+            "{} {}",
+            label("label.moves_left"),
+            limit - moves_made
+        ));
+    }
+    messages
+}
+
+fn play_level(index: usize, level: &Level, theme: Theme) -> crossterm::Result<Outcome> {
+    let mut colors: HashMap<u64, colored::Color> = HashMap::new();
+    initialize_colors(&mut colors, theme);
+
+    let mut game_board = vec![vec![0; level.size]; level.size];
+    for &(i, j) in level.obstacles {
+        game_board[i][j] = OBSTACLE;
+    }
+    spawn_random_tile(&mut game_board);
+    spawn_random_tile(&mut game_board);
+    let mut moves_made: u32 = 0;
+    let mut show_risk = false;
+    let high_score = read_high_score();
+    let mut sound = Sound::new(&load_config().sound);
+
+    loop {
+        render_board(
+            &game_board,
+            &colors,
+            calculate_score(&game_board),
+            high_score,
+            show_risk,
+            &level_messages(index, level, moves_made),
+        )?;
+
+        let max_tile = max_tile(&game_board);
+        let outcome = if max_tile >= level.goal {
+            Some(Outcome::Completed)
+        } else if legal_moves(&game_board).is_empty() {
+            Some(Outcome::Failed("campaign.no_moves"))
+        } else if level.move_limit == Some(moves_made) {
+            Some(Outcome::Failed("campaign.out_of_moves"))
+        } else {
+            None
+        };
+        if let Some(outcome) = outcome {
+            let reason = match outcome {
+                Outcome::Failed(key) => key,
+                _ => "broadcast.level_complete",
+            };
+            broadcast::send_game_over(calculate_score(&game_board), t(reason));
+            return Ok(outcome);
+        }
+
+        if let Event::Key(key_event) = read()? {
+            match key_event.code {
+                KeyCode::Char('e') | KeyCode::Char('E') => return Ok(Outcome::Quit),
+                KeyCode::Char('r') | KeyCode::Char('R') => show_risk = !show_risk,
+                KeyCode::Char('m') | KeyCode::Char('M') => {
+                    sound.toggle_mute();
+                }
+                KeyCode::Char('?') | KeyCode::F(1) => help::show(
+                    &tf(
+                        "campaign.help_title",
+                        &[("level", &(index + 1)), ("name", &t(level.name))],
+                    ),
+                    &match level.move_limit {
+                        Some(limit) => tf(
+                            "campaign.objective_limited",
+                            &[("goal", &level.goal), ("limit", &limit)],
+                        ),
+                        None => tf("campaign.objective", &[("goal", &level.goal)]),
+                    },
+                    &[],
+                )?,
+                code => {
+                    if let Some(direction) = key_direction(code) {
+                        let result = apply_move(&mut game_board, direction);
+                        if result.moved {
+                            sound.play_move(&result);
+                            broadcast::send_move(direction, &result);
+                            accessibility::record_move(direction, &result);
+                            spawn_random_tile(&mut game_board);
+                            moves_made += 1;
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,259 @@
+use crate::accessibility;
+use crate::broadcast;
+use crate::config::load_config;
+use crate::help;
+use crate::i18n::{label, t, tf};
+use crate::logging;
+use crate::sound::Sound;
+use crate::stats::{load_stats, save_stats, today, Stats};
+use crate::theme::initialize_colors;
+use crate::{campaign, key_direction, read_high_score, render_board};
+use crossterm::{
+    cursor,
+    event::{read, Event, KeyCode},
+    terminal::{disable_raw_mode, enable_raw_mode, Clear, ClearType},
+    ExecutableCommand,
+};
+use rust_2048_game::engine::{
+    apply_move, calculate_score, legal_moves, max_tile, spawn_random_tile,
+};
+use std::{collections::HashMap, io::stdout};
+
+pub struct Challenge {
+    pub goal: u64,
+    pub budget: u32,
+}
+
+impl Challenge {
+    /// The key its record is kept under in the stats, the same in every language.
+    pub fn name(&self) -> String {
+        format!("Reach {} in {} moves", self.goal, self.budget)
+    }
+
+    pub fn title(&self) -> String {
+        tf(
+            "challenge.name",
+            &[("goal", &self.goal), ("budget", &self.budget)],
+        )
+    }
+}
+
+pub const CHALLENGES: [Challenge; 5] = [
+    Challenge {
+        goal: 128,
+        budget: 60,
+    },
+    Challenge {
+        goal: 256,
+        budget: 120,
+    },
+    Challenge {
+        goal: 512,
+        budget: 250,
+    },
+    Challenge {
+        goal: 512,
+        budget: 200,
+    },
+    Challenge {
+        goal: 1024,
+        budget: 450,
+    },
+];
+
+fn budget_bar(left: u32, budget: u32) -> String {
+    let width = 20;
+    let filled = (left as usize * width).div_ceil(budget.max(1) as usize);
+    format!(
+        "[{}{}] {}/{}",
+        "#".repeat(filled),
+        "-".repeat(width - filled),
+        left,
+        budget
+    )
+}
+
+fn select_challenge(stats: &Stats) -> crossterm::Result<Option<usize>> {
+    let mut stdout = stdout();
+    stdout.execute(Clear(ClearType::All))?;
+    stdout.execute(cursor::MoveTo(0, 0))?;
+    println!(" > {}", t("challenge.title"));
+    println!();
+    for (i, challenge) in CHALLENGES.iter().enumerate() {
+        let status = match stats.challenges.get(&challenge.name()) {
+            Some(record) if record.completions > 0 => tf(
+                "challenge.done",
+                &[("moves", &record.best_moves.unwrap_or(challenge.budget))],
+            ),
+            Some(record) => tf("challenge.attempts", &[("attempts", &record.attempts)]),
+            None => t("challenge.new").to_string(),
+        };
+        let daily = if i == daily_index(today()) {
+            format!("  ({})", t("challenge.daily"))
+        } else {
+            String::new()
+        };
+        println!(
+            "   {}. {:<24} {}{}",
+            i + 1,
+            challenge.title(),
+            status,
+            daily
+        );
+    }
+    println!();
+    println!(" > {}", t("challenge.hint"));
+
+    enable_raw_mode()?;
+    loop {
+        if let Event::Key(key_event) = read()? {
+            match key_event.code {
+                KeyCode::Char('e') | KeyCode::Char('E') | KeyCode::Esc => return Ok(None),
+                KeyCode::Char(c) => {
+                    if let Some(number) = c.to_digit(10) {
+                        if (1..=CHALLENGES.len()).contains(&(number as usize)) {
+                            return Ok(Some(number as usize - 1));
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+pub fn daily_index(day: u64) -> usize {
+    (day % CHALLENGES.len() as u64) as usize
+}
+
+pub fn run(choice: Option<usize>, daily: bool) -> crossterm::Result<()> {
+    let mut stats = match load_stats() {
+        Ok(stats) => stats,
+        Err(e) => {
+            logging::error(tf("error.load_stats", &[("error", &e)]));
+            return Ok(());
+        }
+    };
+    let choice = if daily {
+        Some(daily_index(today()) + 1)
+    } else {
+        choice
+    };
+    let index = match choice {
+        Some(number) if (1..=CHALLENGES.len()).contains(&number) => number - 1,
+        Some(number) => {
+            println!(
+                " > {}",
+                tf(
+                    "challenge.unknown",
+                    &[("number", &number), ("count", &CHALLENGES.len())]
+                )
+            );
+            return Ok(());
+        }
+        None => {
+            let selected = select_challenge(&stats);
+            disable_raw_mode()?;
+            match selected? {
+                Some(index) => index,
+                None => return Ok(()),
+            }
+        }
+    };
+    let challenge = &CHALLENGES[index];
+
+    let mut colors: HashMap<u64, colored::Color> = HashMap::new();
+    initialize_colors(&mut colors, campaign::active_theme());
+    let mut sound = Sound::new(&load_config().sound);
+    let high_score = read_high_score();
+
+    let mut game_board = vec![vec![0; 4]; 4];
+    spawn_random_tile(&mut game_board);
+    spawn_random_tile(&mut game_board);
+    let mut used: u32 = 0;
+    let mut show_risk = false;
+
+    enable_raw_mode()?;
+    let outcome = loop {
+        let messages = vec![
+            format!("{} {}", label("label.challenge"), challenge.title()),
+            format!(
+                "{} {}",
+                label("label.moves_left"),
+                budget_bar(challenge.budget - used, challenge.budget)
+            ),
+        ];
+        render_board(
+            &game_board,
+            &colors,
+            calculate_score(&game_board),
+            high_score,
+            show_risk,
+            &messages,
+        )?;
+
+        let max_tile = max_tile(&game_board);
+        if max_tile >= challenge.goal {
+            break Some(true);
+        }
+        if legal_moves(&game_board).is_empty() || used >= challenge.budget {
+            break Some(false);
+        }
+
+        if let Event::Key(key_event) = read()? {
+            match key_event.code {
+                KeyCode::Char('e') | KeyCode::Char('E') => break None,
+                KeyCode::Char('r') | KeyCode::Char('R') => show_risk = !show_risk,
+                KeyCode::Char('m') | KeyCode::Char('M') => {
+                    sound.toggle_mute();
+                }
+                KeyCode::Char('?') | KeyCode::F(1) => help::show(
+                    t("mode.challenge"),
+                    &tf("challenge.objective", &[("challenge", &challenge.title())]),
+                    &[],
+                )?,
+                code => {
+                    if let Some(direction) = key_direction(code) {
+                        let result = apply_move(&mut game_board, direction);
+                        if result.moved {
+                            sound.play_move(&result);
+                            broadcast::send_move(direction, &result);
+                            accessibility::record_move(direction, &result);
+                            spawn_random_tile(&mut game_board);
+                            used += 1;
+                        }
+                    }
+                }
+            }
+        }
+    };
+    disable_raw_mode()?;
+
+    let Some(completed) = outcome else {
+        return Ok(());
+    };
+    let record = stats.challenges.entry(challenge.name()).or_default();
+    record.attempts += 1;
+    if completed {
+        record.completions += 1;
+        record.best_moves = Some(record.best_moves.map_or(used, |best| best.min(used)));
+        if daily {
+            stats.daily_completed = Some(today());
+        }
+        println!(" >> {} <<", tf("challenge.complete", &[("moves", &used)]));
+    } else if used >= challenge.budget {
+        println!(" >> {} <<", t("challenge.out_of_moves"));
+    } else {
+        println!(" >> {} <<", t("challenge.no_moves"));
+    }
+    let reason = if completed {
+        t("broadcast.challenge_complete")
+    } else {
+        t("broadcast.challenge_failed")
+    };
+    broadcast::send_game_over(calculate_score(&game_board), reason);
+    if let Err(e) = save_stats(&stats) {
+        logging::error(tf("error.save_stats", &[("error", &e)]));
+    }
+    Ok(())
+}
@@ -0,0 +1,347 @@
+use crate::error::GameError;
+use crate::i18n::tf;
+use crate::logging;
+use crate::profile;
+use rust_2048_game::spawn::{Evil, Gentle, SpawnStrategy, Uniform};
+use serde::{Deserialize, Serialize};
+use std::{fs, io, path::Path, sync::Mutex};
+use toml::{Table, Value};
+
+const CONFIG_FILE: &str = "config.toml";
+
+#[derive(Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct Config {
+    pub sound: SoundConfig,
+    pub reminders: ReminderConfig,
+    pub preview: PreviewConfig,
+    pub game: GameConfig,
+    pub display: DisplayConfig,
+    pub saves: SavesConfig,
+    pub online: OnlineConfig,
+    pub discord: DiscordConfig,
+    pub sync: SyncConfig,
+    pub input: InputConfig,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(default)]
+pub struct InputConfig {
+    pub repeat: bool,
+    pub repeat_delay_ms: u64,
+    pub repeat_rate_ms: u64,
+}
+
+impl Default for InputConfig {
+    fn default() -> Self {
+        InputConfig {
+            repeat: true,
+            repeat_delay_ms: 200,
+            repeat_rate_ms: 120,
+        }
+    }
+}
+
+static INPUT: Mutex<Option<InputConfig>> = Mutex::new(None);
+
+#[derive(Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct SyncConfig {
+    pub folder: String,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct DiscordConfig {
+    pub app_id: String,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct OnlineConfig {
+    pub endpoint: String,
+    pub secret: String,
+    pub player: String,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+pub struct SavesConfig {
+    pub encrypt: bool,
+    pub autosave_secs: u64,
+}
+
+impl Default for SavesConfig {
+    fn default() -> Self {
+        SavesConfig {
+            encrypt: false,
+            autosave_secs: 30,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum TileMarks {
+    #[default]
+    Off,
+    Symbols,
+    Shading,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+pub struct DisplayConfig {
+    pub marks: TileMarks,
+    pub fps: u32,
+    pub language: Language,
+}
+
+impl Default for DisplayConfig {
+    fn default() -> Self {
+        DisplayConfig {
+            marks: TileMarks::Off,
+            fps: 20,
+            language: Language::Auto,
+        }
+    }
+}
+
+static TILE_MARKS: Mutex<Option<TileMarks>> = Mutex::new(None);
+
+/// The language of the interface, `auto` following `LANG`.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum Language {
+    #[default]
+    Auto,
+    #[serde(alias = "en")]
+    English,
+    #[serde(alias = "es")]
+    Spanish,
+}
+
+static LANGUAGE: Mutex<Option<Language>> = Mutex::new(None);
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum KeyBindings {
+    #[default]
+    Arrows,
+    Ijkl,
+    Numpad,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum CornerLock {
+    #[default]
+    Off,
+    Warn,
+    Confirm,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum SpawnBias {
+    #[default]
+    Random,
+    Gentle,
+    Evil,
+}
+
+impl SpawnBias {
+    pub fn name(self) -> &'static str {
+        match self {
+            SpawnBias::Random => "random",
+            SpawnBias::Gentle => "gentle",
+            SpawnBias::Evil => "evil",
+        }
+    }
+
+    pub fn strategy(self) -> Box<dyn SpawnStrategy> {
+        match self {
+            SpawnBias::Random => Box::new(Uniform),
+            SpawnBias::Gentle => Box::new(Gentle),
+            SpawnBias::Evil => Box::new(Evil),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+pub struct GameConfig {
+    pub board_size: usize,
+    pub four_chance: f64,
+    pub animation_ms: u64,
+    pub move_queue: usize,
+    pub keys: KeyBindings,
+    pub corner_lock: CornerLock,
+    pub rewind_moves: usize,
+    pub attract_secs: u64,
+    pub spawn: SpawnBias,
+    pub goals: Vec<u64>,
+    /// Moves between the board snapshots kept for undo and the timeline, 0 for none.
+    pub checkpoint_moves: usize,
+    pub max_checkpoints: usize,
+}
+
+impl Default for GameConfig {
+    fn default() -> Self {
+        GameConfig {
+            board_size: 4,
+            four_chance: 0.1,
+            animation_ms: 90,
+            move_queue: 3,
+            keys: KeyBindings::Arrows,
+            corner_lock: CornerLock::Off,
+            rewind_moves: 5,
+            attract_secs: 30,
+            spawn: SpawnBias::Random,
+            goals: vec![2048],
+            checkpoint_moves: 1000,
+            max_checkpoints: 64,
+        }
+    }
+}
+
+static KEY_BINDINGS: Mutex<Option<KeyBindings>> = Mutex::new(None);
+
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+pub struct PreviewConfig {
+    pub enabled: bool,
+    pub location: bool,
+}
+
+impl Default for PreviewConfig {
+    fn default() -> Self {
+        PreviewConfig {
+            enabled: true,
+            location: false,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+pub struct SoundConfig {
+    pub volume: f32,
+    pub muted: bool,
+    pub music: bool,
+    pub music_volume: f32,
+}
+
+impl Default for SoundConfig {
+    fn default() -> Self {
+        SoundConfig {
+            volume: 0.5,
+            muted: false,
+            music: true,
+            music_volume: 0.3,
+        }
+    }
+}
+
+fn read_table(path: &Path) -> Result<Table, GameError> {
+    match fs::read_to_string(path) {
+        Ok(data) => toml::from_str(&data)
+            .map_err(|e| GameError::Config(format!("{}: {}", path.display(), e))),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Table::new()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+fn merge(base: &mut Table, overrides: Table) {
+    for (key, value) in overrides {
+        match (base.get_mut(&key), value) {
+            (Some(Value::Table(section)), Value::Table(entries)) => merge(section, entries),
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReminderConfig {
+    pub enabled: bool,
+    pub time: String,
+    pub quiet_start: String,
+    pub quiet_end: String,
+}
+
+impl Default for ReminderConfig {
+    fn default() -> Self {
+        ReminderConfig {
+            enabled: false,
+            time: "19:00".to_string(),
+            quiet_start: "22:00".to_string(),
+            quiet_end: "08:00".to_string(),
+        }
+    }
+}
+
+pub fn load_config() -> Config {
+    load_config_with(&mut |e| logging::error(tf("error.bad_config", &[("error", &e)])))
+}
+
+pub fn load_config_with(report: &mut dyn FnMut(GameError)) -> Config {
+    let mut table = read_table(Path::new(CONFIG_FILE)).unwrap_or_else(|e| {
+        report(e);
+        Table::new()
+    });
+    if profile::active().is_some() {
+        match read_table(&profile::path(CONFIG_FILE)) {
+            Ok(overrides) => merge(&mut table, overrides),
+            Err(e) => report(e),
+        }
+    }
+    table.try_into().unwrap_or_else(|e| {
+        report(GameError::Config(e.to_string()));
+        Config::default()
+    })
+}
+
+pub fn save_config(config: &Config) -> Result<(), GameError> {
+    let serialized = toml::to_string(config).map_err(|e| GameError::Config(e.to_string()))?;
+    fs::write(profile::path(CONFIG_FILE), serialized)?;
+    Ok(())
+}
+
+pub fn key_bindings() -> KeyBindings {
+    let mut keys = KEY_BINDINGS.lock().unwrap_or_else(|e| e.into_inner());
+    *keys.get_or_insert_with(|| load_config().game.keys)
+}
+
+pub fn set_key_bindings(keys: KeyBindings) {
+    *KEY_BINDINGS.lock().unwrap_or_else(|e| e.into_inner()) = Some(keys);
+}
+
+pub fn input() -> InputConfig {
+    let mut input = INPUT.lock().unwrap_or_else(|e| e.into_inner());
+    *input.get_or_insert_with(|| load_config().input)
+}
+
+pub fn set_input(settings: InputConfig) {
+    *INPUT.lock().unwrap_or_else(|e| e.into_inner()) = Some(settings);
+}
+
+pub fn tile_marks() -> TileMarks {
+    let mut marks = TILE_MARKS.lock().unwrap_or_else(|e| e.into_inner());
+    *marks.get_or_insert_with(|| load_config().display.marks)
+}
+
+pub fn set_tile_marks(marks: TileMarks) {
+    *TILE_MARKS.lock().unwrap_or_else(|e| e.into_inner()) = Some(marks);
+}
+
+pub fn language() -> Language {
+    let mut language = LANGUAGE.lock().unwrap_or_else(|e| e.into_inner());
+    *language.get_or_insert_with(|| load_config().display.language)
+}
+
+pub fn set_language(language: Language) {
+    *LANGUAGE.lock().unwrap_or_else(|e| e.into_inner()) = Some(language);
+}
@@ -0,0 +1,213 @@
+//! Shared test vectors for engine implementations.
+//!
+//! Each vector is a board, a move and the board and points that move must
+//! produce. Any engine can be checked against them by passing a closure to
+//! [`run`]; the vec-based engine in this crate is available as [`engine`].
+//! [`reference`] is a second, deliberately slow implementation that walks each
+//! line cell by cell, and [`check`] compares the engine with it on any board.
+//! Obstacles are written as their cell value, `18446744073709551615` (`u64::MAX`).
+
+use crate::engine::{apply_move, calculate_score, legal_moves, Direction, MAX_TILE, OBSTACLE};
+use serde::{Deserialize, Serialize};
+use std::{error::Error, fs, path::Path};
+
+const BUILTIN: &str = include_str!("../conformance/moves.json");
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct TestVector {
+    pub name: String,
+    pub board: Vec<Vec<u64>>,
+    #[serde(rename = "move")]
+    pub direction: Direction,
+    pub expected_board: Vec<Vec<u64>>,
+    pub expected_points: u64,
+}
+
+#[derive(Debug)]
+pub struct Failure {
+    pub name: String,
+    pub expected_board: Vec<Vec<u64>>,
+    pub expected_points: u64,
+    pub actual_board: Vec<Vec<u64>>,
+    pub actual_points: u64,
+}
+
+#[derive(Debug, Default)]
+pub struct Report {
+    pub passed: usize,
+    pub failures: Vec<Failure>,
+}
+
+impl Report {
+    pub fn is_ok(&self) -> bool {
+        self.failures.is_empty()
+    }
+}
+
+pub fn vectors() -> Vec<TestVector> {
+    serde_json::from_str(BUILTIN).expect("built-in conformance vectors are valid JSON")
+}
+
+pub fn load(path: &Path) -> Result<Vec<TestVector>, Box<dyn Error>> {
+    Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+}
+
+pub fn engine(board: &[Vec<u64>], direction: Direction) -> (Vec<Vec<u64>>, u64) {
+    let mut board = board.to_vec();
+    let result = apply_move(&mut board, direction);
+    let points = result.merges.iter().map(|merge| merge.value).sum();
+    (board, points)
+}
+
+pub fn run<E>(mut engine: E, vectors: &[TestVector]) -> Report
+where
+    E: FnMut(&[Vec<u64>], Direction) -> (Vec<Vec<u64>>, u64),
+{
+    let mut report = Report::default();
+    for vector in vectors {
+        let (actual_board, actual_points) = engine(&vector.board, vector.direction);
+        if actual_board == vector.expected_board && actual_points == vector.expected_points {
+            report.passed += 1;
+        } else {
+            report.failures.push(Failure {
+                name: vector.name.clone(),
+                expected_board: vector.expected_board.clone(),
+                expected_points: vector.expected_points,
+                actual_board,
+                actual_points,
+            });
+        }
+    }
+    report
+}
+
+/// The cells of each line in the order tiles travel, starting with the cell they move toward.
+fn lines(rows: usize, cols: usize, direction: Direction) -> Vec<Vec<(usize, usize)>> {
+    match direction {
+        Direction::Left => (0..rows)
+            .map(|i| (0..cols).map(|j| (i, j)).collect())
+            .collect(),
+        Direction::Right => (0..rows)
+            .map(|i| (0..cols).rev().map(|j| (i, j)).collect())
+            .collect(),
+        Direction::Up => (0..cols)
+            .map(|j| (0..rows).map(|i| (i, j)).collect())
+            .collect(),
+        Direction::Down => (0..cols)
+            .map(|j| (0..rows).rev().map(|i| (i, j)).collect())
+            .collect(),
+    }
+}
+
+/// Packs one line toward its start: obstacles stay put, and a tile merges at most once.
+fn slide(line: &[u64]) -> (Vec<u64>, u64) {
+    let mut packed = Vec::with_capacity(line.len());
+    let mut points = 0;
+    let mut waiting: Option<u64> = None;
+    for (index, &cell) in line.iter().enumerate() {
+        if cell == OBSTACLE {
+            packed.extend(waiting.take());
+            packed.resize(index, 0);
+            packed.push(OBSTACLE);
+        } else if cell != 0 {
+            match waiting {
+                Some(tile) if tile == cell && tile <= MAX_TILE / 2 => {
+                    packed.push(tile * 2);
+                    points += tile * 2;
+                    waiting = None;
+                }
+                Some(tile) => {
+                    packed.push(tile);
+                    waiting = Some(cell);
+                }
+                None => waiting = Some(cell),
+            }
+        }
+    }
+    packed.extend(waiting);
+    packed.resize(line.len(), 0);
+    (packed, points)
+}
+
+/// A slow engine written for clarity rather than speed, for checking faster ones against.
+pub fn reference(board: &[Vec<u64>], direction: Direction) -> (Vec<Vec<u64>>, u64) {
+    let rows = board.len();
+    let cols = board.first().map_or(0, Vec::len);
+    let mut moved = board.to_vec();
+    let mut points = 0;
+    for line in lines(rows, cols, direction) {
+        let cells: Vec<u64> = line.iter().map(|&(i, j)| board[i][j]).collect();
+        let (packed, line_points) = slide(&cells);
+        for (&(i, j), value) in line.iter().zip(packed) {
+            moved[i][j] = value;
+        }
+        points += line_points;
+    }
+    (moved, points)
+}
+
+pub fn reference_legal_moves(board: &[Vec<u64>]) -> Vec<Direction> {
+    Direction::ALL
+        .into_iter()
+        .filter(|&direction| reference(board, direction).0 != board)
+        .collect()
+}
+
+pub fn reference_score(board: &[Vec<u64>]) -> u64 {
+    let mut score: u64 = 0;
+    for row in board {
+        for &cell in row {
+            if cell != OBSTACLE {
+                score = score.saturating_add(cell);
+            }
+        }
+    }
+    score
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Divergence {
+    Move {
+        direction: Direction,
+        expected_board: Vec<Vec<u64>>,
+        expected_points: u64,
+        actual_board: Vec<Vec<u64>>,
+        actual_points: u64,
+    },
+    Score {
+        expected: u64,
+        actual: u64,
+    },
+    LegalMoves {
+        expected: Vec<Direction>,
+        actual: Vec<Direction>,
+    },
+}
+
+/// Everything the engine gets wrong on `board` compared with [`reference`]: the board and
+/// points after each move, the score and the set of legal moves.
+pub fn check(board: &[Vec<u64>]) -> Vec<Divergence> {
+    let mut divergences = Vec::new();
+    for direction in Direction::ALL {
+        let (expected_board, expected_points) = reference(board, direction);
+        let (actual_board, actual_points) = engine(board, direction);
+        if expected_board != actual_board || expected_points != actual_points {
+            divergences.push(Divergence::Move {
+                direction,
+                expected_board,
+                expected_points,
+                actual_board,
+                actual_points,
+            });
+        }
+    }
+    let (expected, actual) = (reference_score(board), calculate_score(board));
+    if expected != actual {
+        divergences.push(Divergence::Score { expected, actual });
+    }
+    let (expected, actual) = (reference_legal_moves(board), legal_moves(board));
+    if expected != actual {
+        divergences.push(Divergence::LegalMoves { expected, actual });
+    }
+    divergences
+}
@@ -0,0 +1,473 @@
+use crate::broadcast::BroadcastObserver;
+use crate::config::load_config;
+use crate::help;
+use crate::history::{self, GameRecord, Recorder};
+use crate::i18n::{self, t, tf};
+use crate::sound::Sound;
+use crate::theme::initialize_colors;
+use crate::{campaign, key_direction, logging, read_high_score, render_board};
+use crossterm::{
+    event::{poll, read, Event, KeyCode},
+    terminal::{disable_raw_mode, enable_raw_mode},
+};
+use rust_2048_game::engine::{apply_move, calculate_score, Direction, MoveResult};
+use rust_2048_game::game::{Game, GameObserver};
+use rust_2048_game::scoring::Combo;
+use serde::{Deserialize, Serialize};
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    io::{self, BufRead, BufReader, Write},
+    net::{TcpListener, TcpStream},
+    rc::Rc,
+    time::Duration,
+};
+
+const WAIT: Duration = Duration::from_millis(100);
+
+/// What one player did for the team, kept with the shared record of the game.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Contribution {
+    pub name: String,
+    pub moves: u32,
+    pub merges: u32,
+    pub points: u64,
+}
+
+impl Contribution {
+    fn new(name: String) -> Self {
+        Contribution {
+            name,
+            moves: 0,
+            merges: 0,
+            points: 0,
+        }
+    }
+
+    fn stats(&self) -> String {
+        tf(
+            "coop.stats",
+            &[
+                ("points", &self.points),
+                ("merges", &self.merges),
+                ("moves", &self.moves),
+            ],
+        )
+    }
+
+    pub fn summary(&self) -> String {
+        format!("{} {}", self.name, self.stats())
+    }
+}
+
+struct Attribution(Rc<RefCell<Vec<Contribution>>>);
+
+impl GameObserver for Attribution {
+    fn on_player_move(&mut self, player: usize, _direction: Direction, result: &MoveResult) {
+        if let Some(contribution) = self.0.borrow_mut().get_mut(player) {
+            contribution.moves += 1;
+            contribution.merges += result.merges.len() as u32;
+            contribution.points += result.merges.iter().map(|merge| merge.value).sum::<u64>();
+        }
+    }
+}
+
+/// The host's view of the game, sent to the guest after every move.
+#[derive(Serialize, Deserialize)]
+struct Update {
+    board: Vec<Vec<u64>>,
+    turn: usize,
+    players: Vec<Contribution>,
+    record: Option<GameRecord>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum Request {
+    Join { name: String },
+    Move { direction: Direction },
+    Leave,
+}
+
+/// A line-delimited JSON connection that can be checked without blocking the keyboard.
+struct Peer {
+    stream: TcpStream,
+    reader: BufReader<TcpStream>,
+    pending: String,
+}
+
+impl Peer {
+    fn new(stream: TcpStream) -> io::Result<Self> {
+        stream.set_read_timeout(Some(WAIT))?;
+        Ok(Peer {
+            reader: BufReader::new(stream.try_clone()?),
+            stream,
+            pending: String::new(),
+        })
+    }
+
+    fn send<T: Serialize>(&mut self, message: &T) -> io::Result<()> {
+        let mut line = serde_json::to_string(message)?;
+        line.push('\n');
+        self.stream.write_all(line.as_bytes())
+    }
+
+    /// The next whole message, or `None` when nothing arrived within the wait.
+    fn receive<T: for<'de> Deserialize<'de>>(&mut self) -> io::Result<Option<T>> {
+        match self.reader.read_line(&mut self.pending) {
+            Ok(0) => return Err(io::ErrorKind::UnexpectedEof.into()),
+            Ok(_) => {}
+            Err(e)
+                if matches!(
+                    e.kind(),
+                    io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut
+                ) => {}
+            Err(e) => return Err(e),
+        }
+        if !self.pending.ends_with('\n') {
+            return Ok(None);
+        }
+        let message = serde_json::from_str(&std::mem::take(&mut self.pending))?;
+        Ok(Some(message))
+    }
+}
+
+enum Input {
+    Move(Direction),
+    Redraw,
+    Leave,
+}
+
+/// Waits for a move from the keyboard, answering help and mute on the way.
+fn keyboard(sound: &mut Sound, show_risk: &mut bool) -> crossterm::Result<Option<Input>> {
+    if !poll(WAIT)? {
+        return Ok(None);
+    }
+    let Event::Key(key_event) = read()? else {
+        return Ok(None);
+    };
+    Ok(match key_event.code {
+        KeyCode::Char('e') | KeyCode::Char('E') => Some(Input::Leave),
+        KeyCode::Char('m') | KeyCode::Char('M') => {
+            sound.toggle_mute();
+            None
+        }
+        KeyCode::Char('r') | KeyCode::Char('R') => {
+            *show_risk = !*show_risk;
+            Some(Input::Redraw)
+        }
+        KeyCode::Char('?') | KeyCode::F(1) => {
+            help::show(t("mode.coop"), t("coop.objective"), &[])?;
+            Some(Input::Redraw)
+        }
+        code => key_direction(code).map(Input::Move),
+    })
+}
+
+fn messages(players: &[Contribution], turn: usize, you: Option<usize>) -> Vec<String> {
+    let mut messages = vec![match you {
+        Some(you) if you == turn => t("coop.your_move").to_string(),
+        Some(_) => tf("coop.waiting_for", &[("name", &players[turn].name)]),
+        None => tf("coop.to_move", &[("name", &players[turn].name)]),
+    }];
+    for player in players {
+        messages.push(format!("{:<13} : {}", player.name, player.stats()));
+    }
+    messages
+}
+
+fn print_result(record: &GameRecord) {
+    println!(" >> {} <<", tf("coop.ended", &[("score", &record.score)]));
+    for player in &record.players {
+        println!(" > {}", player.summary());
+    }
+}
+
+fn save(record: GameRecord) {
+    if let Err(e) = history::append(record) {
+        logging::error(tf("error.save_coop", &[("error", &e)]));
+    }
+}
+
+/// Plays on this machine, with the guest's moves coming over `peer` when there is one.
+fn host(names: Vec<String>, mut peer: Option<Peer>) -> crossterm::Result<()> {
+    let mut colors: HashMap<u64, colored::Color> = HashMap::new();
+    initialize_colors(&mut colors, campaign::active_theme());
+    let config = load_config();
+    let mut sound = Sound::new(&config.sound);
+    let high_score = read_high_score();
+
+    let contributions = Rc::new(RefCell::new(
+        names.into_iter().map(Contribution::new).collect::<Vec<_>>(),
+    ));
+    let mut game = Game::new(4);
+    game.set_players(contributions.borrow().len());
+    game.subscribe(Box::new(Attribution(Rc::clone(&contributions))));
+    game.subscribe(Box::new(BroadcastObserver));
+    let mut recorder = Recorder::new(game.board(), &config.game, Combo::default());
+    let you = peer.as_ref().map(|_| 0);
+    let mut show_risk = false;
+
+    enable_raw_mode()?;
+    let mut changed = true;
+    let result = loop {
+        if changed {
+            let players = contributions.borrow().clone();
+            render_board(
+                game.board(),
+                &colors,
+                game.score(),
+                high_score,
+                show_risk,
+                &messages(&players, game.player(), you),
+            )?;
+            if let Some(peer) = &mut peer {
+                let update = Update {
+                    board: game.board().to_vec(),
+                    turn: game.player(),
+                    players,
+                    record: None,
+                };
+                if let Err(e) = peer.send(&update) {
+                    break Err(e);
+                }
+            }
+            changed = false;
+        }
+        if game.is_over() {
+            break Ok(());
+        }
+        let input = match &mut peer {
+            Some(peer) if game.player() != 0 => match peer.receive::<Request>() {
+                Ok(Some(Request::Move { direction })) => Some(Input::Move(direction)),
+                Ok(Some(Request::Leave)) => Some(Input::Leave),
+                Ok(_) => match keyboard(&mut sound, &mut show_risk)? {
+                    Some(Input::Move(_)) => None,
+                    input => input,
+                },
+                Err(e) => break Err(e),
+            },
+            _ => keyboard(&mut sound, &mut show_risk)?,
+        };
+        match input {
+            Some(Input::Leave) => break Ok(()),
+            Some(Input::Redraw) => changed = true,
+            Some(Input::Move(direction)) => {
+                let mut moved = game.board().to_vec();
+                apply_move(&mut moved, direction);
+                let result = game.play(direction);
+                if result.moved {
+                    sound.play_move(&result);
+                    recorder.record(direction, &moved, game.board());
+                    changed = true;
+                }
+            }
+            None => {}
+        }
+    };
+    disable_raw_mode()?;
+    if let Err(e) = &result {
+        logging::error(tf("coop.lost_player", &[("error", &e)]));
+    }
+
+    let mode = tf("coop.mode", &[("count", &contributions.borrow().len())]);
+    let mut record = recorder.finish(&mode, game.board());
+    record.players = contributions.borrow().clone();
+    if let Some(peer) = &mut peer {
+        let update = Update {
+            board: game.board().to_vec(),
+            turn: game.player(),
+            players: record.players.clone(),
+            record: Some(record.clone()),
+        };
+        let _ = peer.send(&update);
+    }
+    print_result(&record);
+    save(record);
+    Ok(())
+}
+
+/// Plays the second seat of a game hosted elsewhere, which keeps the board.
+fn guest(name: String, mut peer: Peer) -> crossterm::Result<()> {
+    let mut colors: HashMap<u64, colored::Color> = HashMap::new();
+    initialize_colors(&mut colors, campaign::active_theme());
+    let mut sound = Sound::new(&load_config().sound);
+    let high_score = read_high_score();
+    peer.send(&Request::Join { name })?;
+    let mut show_risk = false;
+
+    enable_raw_mode()?;
+    let mut update: Option<Update> = None;
+    let mut changed = false;
+    let mut sent = false;
+    let result = loop {
+        match peer.receive::<Update>() {
+            Ok(Some(received)) => {
+                update = Some(received);
+                changed = true;
+                sent = false;
+            }
+            Ok(None) => {}
+            Err(e) => break Err(e),
+        }
+        if let Some(update) = update.as_ref().filter(|_| changed) {
+            render_board(
+                &update.board,
+                &colors,
+                calculate_score(&update.board),
+                high_score,
+                show_risk,
+                &messages(&update.players, update.turn, Some(1)),
+            )?;
+            changed = false;
+            if update.record.is_some() {
+                break Ok(());
+            }
+        }
+        let my_turn = update.as_ref().is_some_and(|update| update.turn == 1) && !sent;
+        match keyboard(&mut sound, &mut show_risk)? {
+            Some(Input::Leave) => {
+                let _ = peer.send(&Request::Leave);
+                break Ok(());
+            }
+            Some(Input::Redraw) => changed = true,
+            Some(Input::Move(direction)) if my_turn => {
+                if let Err(e) = peer.send(&Request::Move { direction }) {
+                    break Err(e);
+                }
+                sent = true;
+            }
+            _ => {}
+        }
+    };
+    disable_raw_mode()?;
+    if let Err(e) = &result {
+        logging::error(tf("coop.lost_host", &[("error", &e)]));
+    }
+    if let Some(record) = update.and_then(|update| update.record) {
+        print_result(&record);
+        save(record);
+    }
+    Ok(())
+}
+
+/// Two or more players share one board and take one move each, on one keyboard or, with
+/// `host` and `join`, over the network with the host keeping the board.
+pub fn run(
+    names: Vec<String>,
+    host_at: Option<String>,
+    join: Option<String>,
+) -> crossterm::Result<()> {
+    let mut names = names;
+    if let Some(address) = join {
+        let name = names.into_iter().next().unwrap_or_else(|| i18n::player(2));
+        println!(" > {}", tf("coop.joining", &[("address", &address)]));
+        return match TcpStream::connect(&address).and_then(Peer::new) {
+            Ok(peer) => guest(name, peer),
+            Err(e) => {
+                logging::error(tf(
+                    "coop.join_failed",
+                    &[("address", &address), ("error", &e)],
+                ));
+                Ok(())
+            }
+        };
+    }
+    let Some(address) = host_at else {
+        if names.is_empty() {
+            names = vec![i18n::player(1), i18n::player(2)];
+        }
+        if names.len() < 2 {
+            println!(" > {}", t("coop.too_few"));
+            return Ok(());
+        }
+        return host(names, None);
+    };
+    names.truncate(1);
+    if names.is_empty() {
+        names.push(i18n::player(1));
+    }
+    println!(" > {}", tf("coop.hosting", &[("address", &address)]));
+    let peer = TcpListener::bind(&address)
+        .and_then(|listener| listener.accept())
+        .and_then(|(stream, _)| Peer::new(stream));
+    let mut peer = match peer {
+        Ok(peer) => peer,
+        Err(e) => {
+            logging::error(tf(
+                "coop.host_failed",
+                &[("address", &address), ("error", &e)],
+            ));
+            return Ok(());
+        }
+    };
+    let guest_name = loop {
+        match peer.receive::<Request>() {
+            Ok(Some(Request::Join { name })) => break name.trim().to_string(),
+            Ok(_) => {}
+            Err(e) => {
+                logging::error(tf("coop.never_joined", &[("error", &e)]));
+                return Ok(());
+            }
+        }
+    };
+    // Both seats are told apart by name in the stats and the saved game.
+    if guest_name.is_empty() || names.contains(&guest_name) {
+        names.push(t("coop.guest").to_string());
+    } else {
+        names.push(guest_name);
+    }
+    host(names, Some(peer))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_2048_game::engine::Spawner;
+
+    #[test]
+    fn each_move_is_credited_to_the_player_whose_turn_it_was() {
+        let board = vec![
+            vec![2, 2, 0, 0],
+            vec![4, 4, 0, 0],
+            vec![0, 0, 0, 0],
+            vec![0, 0, 0, 0],
+        ];
+        let mut game = Game::from_board(board, Spawner::seeded(1));
+        game.set_players(2);
+        let contributions = Rc::new(RefCell::new(vec![
+            Contribution::new("Ana".to_string()),
+            Contribution::new("Luis".to_string()),
+        ]));
+        game.subscribe(Box::new(Attribution(Rc::clone(&contributions))));
+        game.play(Direction::Left);
+        assert_eq!(game.player(), 1);
+
+        let players = contributions.borrow();
+        assert_eq!(
+            (players[0].moves, players[0].merges, players[0].points),
+            (1, 2, 12)
+        );
+        assert_eq!(players[1].moves, 0);
+    }
+
+    #[test]
+    fn peer_waits_for_a_whole_line() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let mut writer = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+        let mut peer = Peer::new(listener.accept().unwrap().0).unwrap();
+
+        writer.write_all(br#"{"move": {"dire"#).unwrap();
+        assert!(peer.receive::<Request>().unwrap().is_none());
+        writer.write_all(b"ction\": \"left\"}}\n").unwrap();
+        assert!(matches!(
+            peer.receive::<Request>().unwrap(),
+            Some(Request::Move {
+                direction: Direction::Left
+            })
+        ));
+
+        drop(writer);
+        assert!(peer.receive::<Request>().is_err());
+    }
+}
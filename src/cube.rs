@@ -0,0 +1,112 @@
+use crate::campaign;
+use crate::i18n::{label, t, tf};
+use crate::theme::{cell_width, initialize_colors, tile_cell};
+use colored::*;
+use crossterm::{
+    cursor,
+    event::{read, Event, KeyCode},
+    terminal::{disable_raw_mode, enable_raw_mode, Clear, ClearType},
+    ExecutableCommand,
+};
+use rand::{seq::IteratorRandom, thread_rng, Rng};
+use rust_2048_game::topology::{can_move, shift, GridDirection, GridTopology, Topology};
+use std::{collections::HashMap, io::stdout};
+
+const SIZE: usize = 4;
+
+fn key_cube_direction(code: KeyCode) -> Option<GridDirection> {
+    let (axis, positive) = match code {
+        KeyCode::PageUp => (0, false),
+        KeyCode::PageDown => (0, true),
+        KeyCode::Up => (1, false),
+        KeyCode::Down => (1, true),
+        KeyCode::Left => (2, false),
+        KeyCode::Right => (2, true),
+        _ => return None,
+    };
+    Some(GridDirection { axis, positive })
+}
+
+fn spawn_random_cube_tile(cells: &mut [u64]) {
+    let empty = cells.iter().enumerate().filter(|(_, &val)| val == 0);
+    if let Some((i, _)) = empty.choose(&mut thread_rng()) {
+        cells[i] = if thread_rng().gen_bool(0.9) { 2 } else { 4 };
+    }
+}
+
+fn render_cube_board(
+    cells: &[u64],
+    topology: &GridTopology,
+    colors: &HashMap<u64, Color>,
+) -> crossterm::Result<()> {
+    let mut stdout: std::io::Stdout = stdout();
+    stdout.execute(Clear(ClearType::All))?;
+    stdout.execute(cursor::MoveTo(0, 0))?;
+
+    let (layers, rows, cols) = (topology.dims()[0], topology.dims()[1], topology.dims()[2]);
+    let headers: Vec<String> = (1..=layers)
+        .map(|layer| {
+            format!(
+                "{:<width$}",
+                format!(" {}", tf("cube.layer", &[("layer", &layer)])),
+                width = cols * (cell_width() + 1)
+            )
+        })
+        .collect();
+    println!("{}", headers.join("   ").trim_end());
+    for row in 0..rows {
+        let mut line = String::new();
+        for layer in 0..layers {
+            if layer > 0 {
+                line.push_str(" | ");
+            }
+            for col in 0..cols {
+                let val = cells[topology.cell_at(&[layer, row, col])];
+                line.push_str(&format!("{} ", tile_cell(colors, val)));
+            }
+        }
+        println!("{}", line);
+    }
+    Ok(())
+}
+
+pub fn run() -> crossterm::Result<()> {
+    let topology = GridTopology::new(&[SIZE, SIZE, SIZE]);
+    let mut cells = vec![0; topology.cell_count()];
+    let mut colors: HashMap<u64, Color> = HashMap::new();
+    initialize_colors(&mut colors, campaign::active_theme());
+    spawn_random_cube_tile(&mut cells);
+    spawn_random_cube_tile(&mut cells);
+
+    enable_raw_mode()?;
+    loop {
+        render_cube_board(&cells, &topology, &colors)?;
+        println!();
+        println!(
+            " > {} {}",
+            label("label.current_score"),
+            cells.iter().sum::<u64>()
+        );
+        println!();
+        println!(" > {}", t("cube.keys"));
+        println!(" > {}", t("hint.esc_exit"));
+
+        if !can_move(&cells, &topology) {
+            println!(" >> {} <<", t("game.over"));
+            break;
+        }
+
+        if let Event::Key(key_event) = read()? {
+            if key_event.code == KeyCode::Esc {
+                break;
+            }
+            if let Some(direction) = key_cube_direction(key_event.code) {
+                if shift(&mut cells, &topology, direction) {
+                    spawn_random_cube_tile(&mut cells);
+                }
+            }
+        }
+    }
+    disable_raw_mode()?;
+    Ok(())
+}
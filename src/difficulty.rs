@@ -0,0 +1,170 @@
+use crate::bot;
+use crate::config::Config;
+use crate::frame;
+use crate::i18n::{t, tf};
+use crate::scores::{self, ScoreKey};
+use clap::ValueEnum;
+use crossterm::{
+    cursor,
+    event::{poll, read, Event, KeyCode},
+    terminal::{disable_raw_mode, enable_raw_mode, Clear, ClearType},
+    ExecutableCommand,
+};
+use rand::{seq::IteratorRandom, thread_rng};
+use rust_2048_game::engine::OBSTACLE;
+use rust_2048_game::scoring::Ruleset;
+use serde::{Deserialize, Serialize};
+use std::{io::stdout, time::Duration};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum Difficulty {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+    Insane,
+    Custom,
+}
+
+impl Difficulty {
+    pub const ALL: [Difficulty; 5] = [
+        Difficulty::Easy,
+        Difficulty::Normal,
+        Difficulty::Hard,
+        Difficulty::Insane,
+        Difficulty::Custom,
+    ];
+
+    pub fn name(self) -> String {
+        format!("{:?}", self).to_lowercase()
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Difficulty::Easy => t("difficulty.easy"),
+            Difficulty::Normal => t("difficulty.normal"),
+            Difficulty::Hard => t("difficulty.hard"),
+            Difficulty::Insane => t("difficulty.insane"),
+            Difficulty::Custom => t("difficulty.custom"),
+        }
+    }
+
+    pub fn board_size(self, config: &Config) -> usize {
+        match self {
+            Difficulty::Easy => 5,
+            Difficulty::Custom => config.game.board_size,
+            _ => 4,
+        }
+    }
+
+    pub fn four_chance(self, config: &Config) -> f64 {
+        match self {
+            Difficulty::Easy => 0.05,
+            Difficulty::Normal => 0.1,
+            Difficulty::Hard => 0.2,
+            Difficulty::Insane => 0.3,
+            Difficulty::Custom => config.game.four_chance,
+        }
+    }
+
+    pub fn allows_undo(self) -> bool {
+        matches!(
+            self,
+            Difficulty::Easy | Difficulty::Normal | Difficulty::Custom
+        )
+    }
+
+    pub fn blockers(self) -> usize {
+        match self {
+            Difficulty::Hard => 1,
+            Difficulty::Insane => 2,
+            _ => 0,
+        }
+    }
+
+    pub fn summary(self, config: &Config) -> String {
+        let size = self.board_size(config);
+        let undo = if self.allows_undo() {
+            t("difficulty.timeline")
+        } else {
+            t("difficulty.no_timeline")
+        };
+        tf(
+            "difficulty.summary",
+            &[
+                ("size", &size),
+                ("fours", &format!("{:.0}", self.four_chance(config) * 100.0)),
+                ("blockers", &self.blockers()),
+                ("undo", &undo),
+            ],
+        )
+    }
+}
+
+pub fn place_blockers(game_board: &mut [Vec<u64>], count: usize) {
+    let size = game_board.len();
+    let cells = (0..size).flat_map(|i| (0..size).map(move |j| (i, j)));
+    for (i, j) in cells
+        .filter(|&(i, j)| game_board[i][j] == 0)
+        .choose_multiple(&mut thread_rng(), count)
+    {
+        game_board[i][j] = OBSTACLE;
+    }
+}
+
+fn print_menu(config: &Config) -> crossterm::Result<()> {
+    frame::invalidate();
+    let mut stdout = stdout();
+    stdout.execute(Clear(ClearType::All))?;
+    stdout.execute(cursor::MoveTo(0, 0))?;
+    println!(" > {}", t("difficulty.choose"));
+    println!();
+    for (i, difficulty) in Difficulty::ALL.iter().enumerate() {
+        println!(
+            "   {}. {:<8} {:<42} {} {}",
+            i + 1,
+            difficulty.label(),
+            difficulty.summary(config),
+            t("difficulty.best"),
+            scores::best(ScoreKey::new(
+                difficulty.board_size(config),
+                *difficulty,
+                Ruleset::Standard
+            ))
+        );
+    }
+    println!();
+    println!(" > {}", t("difficulty.hint"));
+    Ok(())
+}
+
+pub fn pick(config: &Config) -> crossterm::Result<Option<Difficulty>> {
+    let idle = Duration::from_secs(config.game.attract_secs);
+    print_menu(config)?;
+    enable_raw_mode()?;
+    let choice = loop {
+        if !idle.is_zero() && !poll(idle)? {
+            bot::demo()?;
+            disable_raw_mode()?;
+            print_menu(config)?;
+            enable_raw_mode()?;
+            continue;
+        }
+        if let Event::Key(key_event) = read()? {
+            match key_event.code {
+                KeyCode::Char('e') | KeyCode::Char('E') | KeyCode::Esc => break None,
+                KeyCode::Enter => break Some(Difficulty::Normal),
+                KeyCode::Char(c) => match c.to_digit(10) {
+                    Some(number) if (1..=Difficulty::ALL.len()).contains(&(number as usize)) => {
+                        break Some(Difficulty::ALL[number as usize - 1]);
+                    }
+                    _ => {}
+                },
+                _ => {}
+            }
+        }
+    };
+    disable_raw_mode()?;
+    Ok(choice)
+}
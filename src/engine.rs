@@ -0,0 +1,432 @@
+use crate::spawn::{SpawnStrategy, Uniform};
+use rand::{rngs::StdRng, seq::IteratorRandom, thread_rng, Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+pub const OBSTACLE: u64 = u64::MAX;
+/// The largest tile a merge makes, far past any real game yet small enough that
+/// no board's score can overflow. Larger tiles from other sources never merge.
+pub const MAX_TILE: u64 = 1 << 48;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Down,
+        Direction::Left,
+        Direction::Right,
+    ];
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Slide {
+    pub from: (usize, usize),
+    pub to: (usize, usize),
+    pub value: u64,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Merge {
+    pub position: (usize, usize),
+    pub value: u64,
+    pub sources: [(usize, usize); 2],
+}
+
+#[derive(Default, Debug)]
+pub struct MoveResult {
+    pub moved: bool,
+    pub merges: Vec<Merge>,
+    pub slides: Vec<Slide>,
+}
+
+impl std::str::FromStr for Direction {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        match text.to_ascii_lowercase().as_str() {
+            "up" => Ok(Direction::Up),
+            "down" => Ok(Direction::Down),
+            "left" => Ok(Direction::Left),
+            "right" => Ok(Direction::Right),
+            _ => Err(format!("unknown direction '{}'", text)),
+        }
+    }
+}
+
+pub fn legal_moves(game_board: &[Vec<u64>]) -> Vec<Direction> {
+    Direction::ALL
+        .into_iter()
+        .filter(|&direction| apply_move(&mut game_board.to_vec(), direction).moved)
+        .collect()
+}
+
+/// The largest tile on the board, leaving out blockers.
+pub fn max_tile(game_board: &[Vec<u64>]) -> u64 {
+    game_board
+        .iter()
+        .flatten()
+        .copied()
+        .filter(|&val| val != OBSTACLE)
+        .max()
+        .unwrap_or(0)
+}
+
+pub fn max_tile_corner(game_board: &[Vec<u64>]) -> Option<(usize, usize)> {
+    let max_tile = Some(max_tile(game_board)).filter(|&val| val > 0)?;
+    let last = game_board.len().checked_sub(1)?;
+    [(0, 0), (0, last), (last, 0), (last, last)]
+        .into_iter()
+        .find(|&(i, j)| game_board[i][j] == max_tile)
+}
+
+/// Looks one move ahead to see whether `direction` pulls the largest tile out of its corner.
+pub fn leaves_corner(game_board: &[Vec<u64>], direction: Direction) -> bool {
+    let Some((i, j)) = max_tile_corner(game_board) else {
+        return false;
+    };
+    let mut next = game_board.to_vec();
+    apply_move(&mut next, direction).moved && next[i][j] < game_board[i][j]
+}
+
+pub fn calculate_score(game_board: &[Vec<u64>]) -> u64 {
+    game_board
+        .iter()
+        .flatten()
+        .filter(|&&val| val != OBSTACLE)
+        .fold(0, |score: u64, &val| score.saturating_add(val))
+}
+
+pub fn spawn_random_tile(game_board: &mut [Vec<u64>]) {
+    let mut empty_cells_array: Vec<(usize, usize)> = Vec::new();
+    for (i, row) in game_board.iter().enumerate() {
+        for (j, &cell) in row.iter().enumerate() {
+            if cell == 0 {
+                empty_cells_array.push((i, j));
+            }
+        }
+    }
+
+    if let Some(&(i, j)) = empty_cells_array.iter().choose(&mut thread_rng()) {
+        let new_value = if thread_rng().gen_bool(0.9) { 2 } else { 4 };
+        game_board[i][j] = new_value;
+    }
+}
+
+pub struct Spawner {
+    rng: StdRng,
+    seed: u64,
+    draws: u64,
+    four_chance: f64,
+    value: u64,
+    slot: f64,
+    strategy: Box<dyn SpawnStrategy>,
+}
+
+impl Spawner {
+    pub fn new() -> Self {
+        Self::seeded(thread_rng().gen())
+    }
+
+    pub fn seeded(seed: u64) -> Self {
+        Self::with_four_chance(seed, 0.1)
+    }
+
+    /// Like `seeded`, drawing even the first tile with the given odds of a 4.
+    pub fn with_four_chance(seed: u64, four_chance: f64) -> Self {
+        let four_chance = four_chance.clamp(0.0, 1.0);
+        let mut rng = StdRng::seed_from_u64(seed);
+        let (value, slot) = Self::draw(&mut rng, four_chance);
+        Spawner {
+            rng,
+            seed,
+            draws: 0,
+            four_chance,
+            value,
+            slot,
+            strategy: Box::new(Uniform),
+        }
+    }
+
+    fn draw(rng: &mut StdRng, four_chance: f64) -> (u64, f64) {
+        let value = if rng.gen_bool(four_chance) { 4 } else { 2 };
+        (value, rng.gen())
+    }
+
+    /// Picks up the sequence of `seed` after `draws` tiles, as `checkpoint` reported it.
+    pub fn resume(seed: u64, draws: u64, four_chance: f64) -> Self {
+        let mut spawner = Self::with_four_chance(seed, four_chance);
+        for _ in 0..draws {
+            (spawner.value, spawner.slot) = Self::draw(&mut spawner.rng, spawner.four_chance);
+        }
+        spawner.draws = draws;
+        spawner
+    }
+
+    /// The seed and how many tiles it has placed, enough to `resume` from this point.
+    pub fn checkpoint(&self) -> (u64, u64) {
+        (self.seed, self.draws)
+    }
+
+    /// Changes the odds from the tile after the one already drawn.
+    pub fn set_four_chance(&mut self, chance: f64) {
+        self.four_chance = chance.clamp(0.0, 1.0);
+    }
+
+    pub fn next_value(&self) -> u64 {
+        self.value
+    }
+
+    pub fn set_strategy(&mut self, strategy: Box<dyn SpawnStrategy>) {
+        self.strategy = strategy;
+    }
+
+    pub fn next_cell(&self, game_board: &[Vec<u64>]) -> Option<(usize, usize)> {
+        self.strategy.place(game_board, self.value, self.slot)
+    }
+
+    pub fn spawn(&mut self, game_board: &mut [Vec<u64>]) -> Option<(usize, usize)> {
+        let (i, j) = self.next_cell(game_board)?;
+        game_board[i][j] = self.value;
+        (self.value, self.slot) = Self::draw(&mut self.rng, self.four_chance);
+        self.draws += 1;
+        Some((i, j))
+    }
+}
+
+impl Default for Spawner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Rotations and reflections of a rectangular board.
+pub trait Board {
+    type Cell;
+
+    fn rotate_cw(&self) -> Vec<Vec<Self::Cell>>;
+    fn rotate_ccw(&self) -> Vec<Vec<Self::Cell>>;
+    fn mirror(&self) -> Vec<Vec<Self::Cell>>;
+}
+
+impl<T: Clone> Board for [Vec<T>] {
+    type Cell = T;
+
+    fn rotate_cw(&self) -> Vec<Vec<T>> {
+        let cols = self.first().map_or(0, Vec::len);
+        (0..cols)
+            .map(|j| self.iter().rev().map(|row| row[j].clone()).collect())
+            .collect()
+    }
+
+    fn rotate_ccw(&self) -> Vec<Vec<T>> {
+        let cols = self.first().map_or(0, Vec::len);
+        (0..cols)
+            .rev()
+            .map(|j| self.iter().map(|row| row[j].clone()).collect())
+            .collect()
+    }
+
+    fn mirror(&self) -> Vec<Vec<T>> {
+        self.iter()
+            .map(|row| row.iter().rev().cloned().collect())
+            .collect()
+    }
+}
+
+/// Turns the board so that `direction` points left.
+pub(crate) fn face_left<T: Clone>(board: &[Vec<T>], direction: Direction) -> Vec<Vec<T>> {
+    match direction {
+        Direction::Left => board.to_vec(),
+        Direction::Right => board.mirror(),
+        Direction::Up => board.rotate_ccw(),
+        Direction::Down => board.rotate_cw(),
+    }
+}
+
+/// Undoes `face_left`.
+pub(crate) fn face_back<T: Clone>(board: &[Vec<T>], direction: Direction) -> Vec<Vec<T>> {
+    match direction {
+        Direction::Left => board.to_vec(),
+        Direction::Right => board.mirror(),
+        Direction::Up => board.rotate_cw(),
+        Direction::Down => board.rotate_ccw(),
+    }
+}
+
+pub fn apply_move(game_board: &mut [Vec<u64>], direction: Direction) -> MoveResult {
+    let initial_board = game_board.to_vec();
+    let mut board = face_left(game_board, direction);
+    let mut origins = face_left(&tile_origins(game_board), direction);
+    shift_left(&mut board, &mut origins);
+    for (row, shifted) in game_board.iter_mut().zip(face_back(&board, direction)) {
+        *row = shifted;
+    }
+    collect_move_result(&initial_board, game_board, face_back(&origins, direction))
+}
+
+fn lines_toward(game_board: &[Vec<u64>], direction: Direction) -> Vec<Vec<(usize, usize)>> {
+    let rows = game_board.len();
+    let cols = game_board.first().map_or(0, |row| row.len());
+    match direction {
+        Direction::Left => (0..rows)
+            .map(|i| (0..cols).map(|j| (i, j)).collect())
+            .collect(),
+        Direction::Right => (0..rows)
+            .map(|i| (0..cols).rev().map(|j| (i, j)).collect())
+            .collect(),
+        Direction::Up => (0..cols)
+            .map(|j| (0..rows).map(|i| (i, j)).collect())
+            .collect(),
+        Direction::Down => (0..cols)
+            .map(|j| (0..rows).rev().map(|i| (i, j)).collect())
+            .collect(),
+    }
+}
+
+pub fn settle(game_board: &mut [Vec<u64>], gravity: Direction) -> Vec<Slide> {
+    let mut slides: Vec<Slide> = Vec::new();
+    for line in lines_toward(game_board, gravity) {
+        let segments: Vec<Vec<(usize, usize)>> = line
+            .split(|&(i, j)| game_board[i][j] == OBSTACLE)
+            .map(|segment| segment.to_vec())
+            .collect();
+        for segment in segments {
+            let mut target = 0;
+            for &(i, j) in &segment {
+                let value = game_board[i][j];
+                if value == 0 {
+                    continue;
+                }
+                let to = segment[target];
+                if to != (i, j) {
+                    game_board[to.0][to.1] = value;
+                    game_board[i][j] = 0;
+                    slides.push(Slide {
+                        from: (i, j),
+                        to,
+                        value,
+                    });
+                }
+                target += 1;
+            }
+        }
+    }
+    slides
+}
+
+pub fn can_make_move_with_gravity(game_board: &[Vec<u64>], gravity: Direction) -> bool {
+    Direction::ALL.into_iter().any(|direction| {
+        let mut next_board = game_board.to_vec();
+        apply_move(&mut next_board, direction);
+        settle(&mut next_board, gravity);
+        next_board != game_board
+    })
+}
+
+#[cfg(feature = "research")]
+pub fn apply_move_traced<T: crate::research::Tracer>(
+    game_board: &mut [Vec<u64>],
+    direction: Direction,
+    tracer: &mut T,
+) -> MoveResult {
+    let result = apply_move(game_board, direction);
+    for slide in &result.slides {
+        tracer.on_slide(slide);
+    }
+    for merge in &result.merges {
+        tracer.on_merge(merge);
+    }
+    result
+}
+
+type Origins = Vec<Vec<Vec<(usize, usize)>>>;
+
+fn tile_origins(game_board: &[Vec<u64>]) -> Origins {
+    game_board
+        .iter()
+        .enumerate()
+        .map(|(i, row)| {
+            row.iter()
+                .enumerate()
+                .map(|(j, &val)| {
+                    if val != 0 && val != OBSTACLE {
+                        vec![(i, j)]
+                    } else {
+                        Vec::new()
+                    }
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn collect_move_result(
+    initial_board: &[Vec<u64>],
+    game_board: &[Vec<u64>],
+    origins: Origins,
+) -> MoveResult {
+    let mut result = MoveResult {
+        moved: initial_board != game_board,
+        ..MoveResult::default()
+    };
+
+    for (i, row) in origins.into_iter().enumerate() {
+        for (j, sources) in row.into_iter().enumerate() {
+            for &from in &sources {
+                if from != (i, j) {
+                    result.slides.push(Slide {
+                        from,
+                        to: (i, j),
+                        value: initial_board[from.0][from.1],
+                    });
+                }
+            }
+            if let [first, second] = sources[..] {
+                result.merges.push(Merge {
+                    position: (i, j),
+                    value: game_board[i][j],
+                    sources: [first, second],
+                });
+            }
+        }
+    }
+
+    result
+}
+
+/// Slides and merges every row toward its start, carrying each tile's origins along.
+fn shift_left(game_board: &mut [Vec<u64>], origins: &mut Origins) {
+    for (row, row_origins) in game_board.iter_mut().zip(origins.iter_mut()) {
+        for i in 1..row.len() {
+            let mut k = i;
+            while k > 0 && row[k] != OBSTACLE && row[k - 1] == 0 {
+                row.swap(k, k - 1);
+                row_origins.swap(k, k - 1);
+                k -= 1;
+            }
+        }
+        for i in 0..row.len().saturating_sub(1) {
+            if row[i] != 0 && row[i] <= MAX_TILE / 2 && row[i] == row[i + 1] {
+                row[i] *= 2;
+                row[i + 1] = 0;
+                let merged = std::mem::take(&mut row_origins[i + 1]);
+                row_origins[i].extend(merged);
+            }
+        }
+        for i in 1..row.len() {
+            let mut k = i;
+            while k > 0 && row[k] != OBSTACLE && row[k - 1] == 0 {
+                row.swap(k, k - 1);
+                row_origins.swap(k, k - 1);
+                k -= 1;
+            }
+        }
+    }
+}
@@ -0,0 +1,24 @@
+use std::{io, path::PathBuf};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum GameError {
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    #[error(transparent)]
+    Serde(#[from] serde_json::Error),
+    #[error("terminal: {0}")]
+    Terminal(io::Error),
+    #[error("{0}")]
+    Config(String),
+    #[error("{} is corrupted: {reason}", path.display())]
+    CorruptedSave { path: PathBuf, reason: String },
+    #[error("{0}")]
+    Locked(String),
+}
+
+impl GameError {
+    pub fn is_missing(&self) -> bool {
+        matches!(self, GameError::Io(e) if e.kind() == io::ErrorKind::NotFound)
+    }
+}
@@ -0,0 +1,214 @@
+use crate::bot::{self, BotStrategy};
+use crate::i18n::{t, tf};
+use crate::layout::{print_panels, Panel};
+use crate::theme::initialize_colors;
+use crate::tick::Ticks;
+use crate::{campaign, frame};
+use colored::Color;
+use crossterm::{
+    cursor,
+    event::{poll, read, Event, KeyCode},
+    terminal::{disable_raw_mode, enable_raw_mode, Clear, ClearType},
+    ExecutableCommand,
+};
+use rand::{rngs::StdRng, thread_rng, Rng, SeedableRng};
+use rust_2048_game::ai::Evaluation;
+use rust_2048_game::engine::max_tile;
+use rust_2048_game::game::Game;
+use rust_2048_game::heuristic;
+use rust_2048_game::mcts::MctsConfig;
+use std::{collections::HashMap, io::stdout, time::Duration};
+
+const STEP: Duration = Duration::from_millis(250);
+const BAR_WIDTH: usize = 20;
+const GOAL: u64 = 2048;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Tick {
+    Step,
+}
+
+struct Side {
+    name: String,
+    strategy: BotStrategy,
+    game: Game,
+    rng: StdRng,
+    evaluations: Vec<Evaluation>,
+    moves: u32,
+    reached_goal: Option<u32>,
+}
+
+impl Side {
+    fn new(name: &str, strategy: BotStrategy, seed: u64, config: &MctsConfig) -> Self {
+        let game = Game::seeded(4, seed);
+        let mut rng = StdRng::seed_from_u64(seed);
+        let evaluations = bot::evaluate(game.board(), strategy, config, &mut rng);
+        Side {
+            name: format!("{}: {:?}", name, strategy),
+            strategy,
+            game,
+            rng,
+            evaluations,
+            moves: 0,
+            reached_goal: None,
+        }
+    }
+
+    fn finished(&self) -> bool {
+        self.evaluations.is_empty()
+    }
+
+    fn outlook(&self) -> f64 {
+        bot::best_move(&self.evaluations).map_or(0.0, |best| best.expected)
+    }
+
+    fn step(&mut self, config: &MctsConfig) {
+        let Some(best) = bot::best_move(&self.evaluations) else {
+            return;
+        };
+        self.game.play(best.direction);
+        self.moves += 1;
+        let max_tile = max_tile(self.game.board());
+        if self.reached_goal.is_none() && max_tile >= GOAL {
+            self.reached_goal = Some(self.moves);
+        }
+        self.evaluations = bot::evaluate(self.game.board(), self.strategy, config, &mut self.rng);
+    }
+}
+
+/// Both bots score moves on their own scale, so the bar shows how safe each board looks to
+/// the shared risk heuristic and the bot's own estimate follows as a number.
+fn evaluation_bar(side: &Side) -> String {
+    let safety = 1.0 - heuristic::risk(side.game.board());
+    let filled = (safety * BAR_WIDTH as f64).round() as usize;
+    format!(
+        "[{}{}] {:.1}",
+        "#".repeat(filled),
+        " ".repeat(BAR_WIDTH - filled),
+        side.outlook()
+    )
+}
+
+fn verdict(sides: &[Side; 2]) -> Option<String> {
+    let [left, right] = sides;
+    match (left.reached_goal, right.reached_goal) {
+        (Some(_), None) => return Some(first(left)),
+        (None, Some(_)) => return Some(first(right)),
+        (Some(_), Some(_)) => {}
+        (None, None) if !left.finished() || !right.finished() => return None,
+        (None, None) => {}
+    }
+    let scores = [left.game.score(), right.game.score()];
+    Some(if scores[0] == scores[1] {
+        tf("versus.draw", &[("score", &scores[0])])
+    } else {
+        let winner = if scores[0] > scores[1] { left } else { right };
+        tf(
+            "versus.wins",
+            &[("name", &winner.name), ("score", &winner.game.score())],
+        )
+    })
+}
+
+fn first(side: &Side) -> String {
+    tf("exhibition.first", &[("name", &side.name), ("goal", &GOAL)])
+}
+
+fn render(
+    sides: &[Side; 2],
+    seed: u64,
+    paused: bool,
+    colors: &HashMap<u64, Color>,
+) -> crossterm::Result<()> {
+    frame::invalidate();
+    let mut stdout = stdout();
+    stdout.execute(Clear(ClearType::All))?;
+    stdout.execute(cursor::MoveTo(0, 0))?;
+    println!(" > {}", tf("exhibition.title", &[("seed", &seed)]));
+    println!();
+    let panels = sides.each_ref().map(|side| Panel {
+        title: side.name.clone(),
+        board: side.game.board(),
+    });
+    print_panels(&panels, colors);
+    println!();
+    for side in sides {
+        let status = if side.finished() {
+            format!("  ({})", t("exhibition.stuck"))
+        } else {
+            String::new()
+        };
+        println!(
+            " > {:<16}: {}{}",
+            side.name,
+            tf(
+                "versus.points",
+                &[("points", &side.game.score()), ("moves", &side.moves)]
+            ),
+            status
+        );
+        println!(" > {:<16}: {}", t("label.evaluation"), evaluation_bar(side));
+    }
+    println!();
+    if paused {
+        println!(" > {}", t("exhibition.paused"));
+    }
+    println!(" > {}", t("exhibition.hint"));
+    Ok(())
+}
+
+/// Plays two bots on the same seed, so both see the same sequence of spawns for as long as
+/// their boards allow it, until one reaches 2048 or both run out of moves.
+pub fn run(
+    left: BotStrategy,
+    right: BotStrategy,
+    seed: Option<u64>,
+    config: MctsConfig,
+) -> crossterm::Result<()> {
+    let mut colors: HashMap<u64, Color> = HashMap::new();
+    initialize_colors(&mut colors, campaign::active_theme());
+    let seed = seed.unwrap_or_else(|| thread_rng().gen());
+    let mut sides = [
+        Side::new(t("exhibition.left"), left, seed, &config),
+        Side::new(t("exhibition.right"), right, seed, &config),
+    ];
+    let mut ticks = Ticks::new();
+    ticks.every(Tick::Step, STEP);
+    let mut paused = false;
+
+    enable_raw_mode()?;
+    let result = loop {
+        if let Err(e) = render(&sides, seed, paused, &colors) {
+            break Err(e);
+        }
+        if let Some(verdict) = verdict(&sides) {
+            break Ok(Some(verdict));
+        }
+        match poll(ticks.timeout()) {
+            Ok(false) => {
+                if !ticks.due().is_empty() && !paused {
+                    for side in &mut sides {
+                        side.step(&config);
+                    }
+                }
+                continue;
+            }
+            Ok(true) => {}
+            Err(e) => break Err(e),
+        }
+        match read() {
+            Ok(Event::Key(key_event)) => match key_event.code {
+                KeyCode::Char('e') | KeyCode::Char('E') | KeyCode::Esc => break Ok(None),
+                KeyCode::Char('p') | KeyCode::Char('P') => paused = !paused,
+                _ => {}
+            },
+            Ok(_) => {}
+            Err(e) => break Err(e),
+        }
+    };
+    disable_raw_mode()?;
+    if let Some(verdict) = result? {
+        println!(" >> {} <<", verdict);
+    }
+    Ok(())
+}
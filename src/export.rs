@@ -0,0 +1,223 @@
+use crate::i18n::tf;
+use crate::theme::{tile_color, tile_label};
+use clap::ValueEnum;
+use colored::Color;
+use rust_2048_game::engine::{calculate_score, OBSTACLE};
+use std::{collections::HashMap, error::Error, fs, path::Path};
+
+const CELL: usize = 64;
+const GAP: usize = 8;
+const BACKGROUND: (u8, u8, u8) = (30, 30, 30);
+const EMPTY: (u8, u8, u8) = (60, 60, 60);
+const BLOCKED: (u8, u8, u8) = (90, 90, 90);
+const INK: (u8, u8, u8) = (20, 20, 20);
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, ValueEnum)]
+pub enum ExportFormat {
+    Text,
+    Ansi,
+    Svg,
+    Png,
+}
+
+impl ExportFormat {
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()? {
+            "txt" => Some(ExportFormat::Text),
+            "ans" => Some(ExportFormat::Ansi),
+            "svg" => Some(ExportFormat::Svg),
+            "png" => Some(ExportFormat::Png),
+            _ => None,
+        }
+    }
+}
+
+fn rgb(color: Color) -> (u8, u8, u8) {
+    match color {
+        Color::Black => (0, 0, 0),
+        Color::Red => (205, 49, 49),
+        Color::Green => (13, 188, 121),
+        Color::Yellow => (229, 229, 16),
+        Color::Blue => (36, 114, 200),
+        Color::Magenta => (188, 63, 188),
+        Color::Cyan => (17, 168, 205),
+        Color::White => (229, 229, 229),
+        Color::BrightBlack => (102, 102, 102),
+        Color::BrightRed => (241, 76, 76),
+        Color::BrightGreen => (35, 209, 139),
+        Color::BrightYellow => (245, 245, 67),
+        Color::BrightBlue => (59, 142, 234),
+        Color::BrightMagenta => (214, 112, 214),
+        Color::BrightCyan => (41, 184, 219),
+        Color::BrightWhite => (255, 255, 255),
+        Color::TrueColor { r, g, b } => (r, g, b),
+    }
+}
+
+fn cell_style(colors: &HashMap<u64, Color>, val: u64) -> ((u8, u8, u8), String) {
+    match val {
+        0 => (EMPTY, String::new()),
+        OBSTACLE => (BLOCKED, "##".to_string()),
+        _ => (rgb(tile_color(colors, val)), tile_label(val)),
+    }
+}
+
+fn text(game_board: &[Vec<u64>], colors: &HashMap<u64, Color>, ansi: bool) -> String {
+    let mut text = String::new();
+    for row in game_board {
+        for &val in row {
+            let label = match val {
+                OBSTACLE => "  ##".to_string(),
+                _ => format!("{:>4}", tile_label(val)),
+            };
+            if ansi && val != 0 && val != OBSTACLE {
+                let code = tile_color(colors, val).to_fg_str();
+                text.push_str(&format!("\x1b[{}m{}\x1b[0m ", code, label));
+            } else {
+                text.push_str(&format!("{} ", label));
+            }
+        }
+        text.push('\n');
+    }
+    text.push_str(&tf(
+        "export.score",
+        &[("score", &calculate_score(game_board))],
+    ));
+    text.push('\n');
+    text
+}
+
+fn svg(game_board: &[Vec<u64>], colors: &HashMap<u64, Color>) -> String {
+    let size = game_board.len();
+    let side = size * (CELL + GAP) + GAP;
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{side}\" height=\"{side}\" viewBox=\"0 0 {side} {side}\">\n"
+    );
+    let (r, g, b) = BACKGROUND;
+    svg.push_str(&format!(
+        "  <rect width=\"{side}\" height=\"{side}\" fill=\"rgb({r},{g},{b})\"/>\n"
+    ));
+    for (i, row) in game_board.iter().enumerate() {
+        for (j, &val) in row.iter().enumerate() {
+            let (x, y) = (GAP + j * (CELL + GAP), GAP + i * (CELL + GAP));
+            let ((r, g, b), label) = cell_style(colors, val);
+            svg.push_str(&format!(
+                "  <rect x=\"{x}\" y=\"{y}\" width=\"{CELL}\" height=\"{CELL}\" rx=\"6\" fill=\"rgb({r},{g},{b})\"/>\n"
+            ));
+            if !label.is_empty() {
+                let (r, g, b) = INK;
+                svg.push_str(&format!(
+                    "  <text x=\"{}\" y=\"{}\" fill=\"rgb({r},{g},{b})\" font-family=\"monospace\" font-size=\"22\" font-weight=\"bold\" text-anchor=\"middle\" dominant-baseline=\"central\">{}</text>\n",
+                    x + CELL / 2,
+                    y + CELL / 2,
+                    label
+                ));
+            }
+        }
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+#[cfg(feature = "png-export")]
+fn glyph(c: char) -> [u8; 5] {
+    match c {
+        '0' => [0b111, 0b101, 0b101, 0b101, 0b111],
+        '1' => [0b010, 0b110, 0b010, 0b010, 0b111],
+        '2' => [0b111, 0b001, 0b111, 0b100, 0b111],
+        '3' => [0b111, 0b001, 0b111, 0b001, 0b111],
+        '4' => [0b101, 0b101, 0b111, 0b001, 0b001],
+        '5' => [0b111, 0b100, 0b111, 0b001, 0b111],
+        '6' => [0b111, 0b100, 0b111, 0b101, 0b111],
+        '7' => [0b111, 0b001, 0b010, 0b010, 0b010],
+        '8' => [0b111, 0b101, 0b111, 0b101, 0b111],
+        '9' => [0b111, 0b101, 0b111, 0b001, 0b111],
+        'k' => [0b100, 0b101, 0b110, 0b101, 0b101],
+        'M' => [0b101, 0b111, 0b111, 0b101, 0b101],
+        'G' => [0b111, 0b100, 0b101, 0b101, 0b111],
+        'T' => [0b111, 0b010, 0b010, 0b010, 0b010],
+        'P' => [0b110, 0b101, 0b110, 0b100, 0b100],
+        'E' => [0b111, 0b100, 0b110, 0b100, 0b111],
+        '#' => [0b101, 0b111, 0b101, 0b111, 0b101],
+        _ => [0; 5],
+    }
+}
+
+#[cfg(feature = "png-export")]
+fn png(game_board: &[Vec<u64>], colors: &HashMap<u64, Color>) -> Result<Vec<u8>, Box<dyn Error>> {
+    let size = game_board.len();
+    let side = size * (CELL + GAP) + GAP;
+    let mut pixels: Vec<u8> = [BACKGROUND.0, BACKGROUND.1, BACKGROUND.2].repeat(side * side);
+    let mut fill = |x: usize, y: usize, w: usize, h: usize, (r, g, b): (u8, u8, u8)| {
+        for py in y..y + h {
+            for px in x..x + w {
+                let at = (py * side + px) * 3;
+                pixels[at..at + 3].copy_from_slice(&[r, g, b]);
+            }
+        }
+    };
+    for (i, row) in game_board.iter().enumerate() {
+        for (j, &val) in row.iter().enumerate() {
+            let (x, y) = (GAP + j * (CELL + GAP), GAP + i * (CELL + GAP));
+            let (background, label) = cell_style(colors, val);
+            fill(x, y, CELL, CELL, background);
+
+            let scale = if label.len() <= 3 { 4 } else { 3 };
+            let width = (label.len() * 4 * scale).saturating_sub(scale);
+            let (left, top) = (x + (CELL - width.min(CELL)) / 2, y + (CELL - 5 * scale) / 2);
+            for (k, c) in label.chars().enumerate() {
+                for (dy, bits) in glyph(c).into_iter().enumerate() {
+                    for dx in 0..3 {
+                        if bits & (0b100 >> dx) != 0 {
+                            fill(
+                                left + (k * 4 + dx) * scale,
+                                top + dy * scale,
+                                scale,
+                                scale,
+                                INK,
+                            );
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    let mut data = Vec::new();
+    let mut encoder = png::Encoder::new(&mut data, side as u32, side as u32);
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder.write_header()?.write_image_data(&pixels)?;
+    Ok(data)
+}
+
+#[cfg(not(feature = "png-export"))]
+fn png(_: &[Vec<u64>], _: &HashMap<u64, Color>) -> Result<Vec<u8>, Box<dyn Error>> {
+    Err("PNG export needs a build with the png-export feature".into())
+}
+
+pub fn export(
+    game_board: &[Vec<u64>],
+    colors: &HashMap<u64, Color>,
+    format: ExportFormat,
+) -> Result<Vec<u8>, Box<dyn Error>> {
+    match format {
+        ExportFormat::Text => Ok(text(game_board, colors, false).into_bytes()),
+        ExportFormat::Ansi => Ok(text(game_board, colors, true).into_bytes()),
+        ExportFormat::Svg => Ok(svg(game_board, colors).into_bytes()),
+        ExportFormat::Png => png(game_board, colors),
+    }
+}
+
+pub fn write(
+    path: &Path,
+    game_board: &[Vec<u64>],
+    colors: &HashMap<u64, Color>,
+    format: Option<ExportFormat>,
+) -> Result<(), Box<dyn Error>> {
+    let format = format
+        .or_else(|| ExportFormat::from_path(path))
+        .unwrap_or(ExportFormat::Text);
+    fs::write(path, export(game_board, colors, format)?)?;
+    Ok(())
+}
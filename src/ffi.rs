@@ -0,0 +1,84 @@
+use crate::engine::Direction;
+use crate::game::Game;
+
+pub struct CGame {
+    game: Game,
+    cells: Vec<u64>,
+}
+
+impl CGame {
+    fn sync(&mut self) {
+        self.cells = self.game.board().concat();
+    }
+}
+
+/// Starts a `size` x `size` game with two tiles; the same seed always plays out the same way.
+/// Release it with `game_free`.
+#[no_mangle]
+pub extern "C" fn game_new(size: usize, seed: u64) -> *mut CGame {
+    let mut game = CGame {
+        game: Game::seeded(size.clamp(2, 16), seed),
+        cells: Vec::new(),
+    };
+    game.sync();
+    Box::into_raw(Box::new(game))
+}
+
+/// Direction is 0 up, 1 down, 2 left, 3 right. Returns 1 if the board changed, 0 if it did
+/// not and -1 for a null game or an unknown direction.
+///
+/// # Safety
+/// `game` must be null or a pointer returned by `game_new` that has not been freed.
+#[no_mangle]
+pub unsafe extern "C" fn game_move(game: *mut CGame, direction: u32) -> i32 {
+    let Some(game) = game.as_mut() else {
+        return -1;
+    };
+    let Some(&direction) = Direction::ALL.get(direction as usize) else {
+        return -1;
+    };
+    let moved = game.game.play(direction).moved;
+    game.sync();
+    i32::from(moved)
+}
+
+/// Row-major cells, `game_size` squared of them; 0 is empty and `UINT64_MAX` a blocker.
+/// The pointer is valid until the next `game_move` or `game_free`.
+///
+/// # Safety
+/// `game` must be null or a pointer returned by `game_new` that has not been freed.
+#[no_mangle]
+pub unsafe extern "C" fn game_board_ptr(game: *const CGame) -> *const u64 {
+    game.as_ref()
+        .map_or(std::ptr::null(), |game| game.cells.as_ptr())
+}
+
+/// # Safety
+/// `game` must be null or a pointer returned by `game_new` that has not been freed.
+#[no_mangle]
+pub unsafe extern "C" fn game_size(game: *const CGame) -> usize {
+    game.as_ref().map_or(0, |game| game.game.board().len())
+}
+
+/// # Safety
+/// `game` must be null or a pointer returned by `game_new` that has not been freed.
+#[no_mangle]
+pub unsafe extern "C" fn game_score(game: *const CGame) -> u64 {
+    game.as_ref().map_or(0, |game| game.game.score())
+}
+
+/// # Safety
+/// `game` must be null or a pointer returned by `game_new` that has not been freed.
+#[no_mangle]
+pub unsafe extern "C" fn game_is_over(game: *const CGame) -> bool {
+    game.as_ref().is_none_or(|game| game.game.is_over())
+}
+
+/// # Safety
+/// `game` must be null or a pointer returned by `game_new`, and is invalid afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn game_free(game: *mut CGame) {
+    if !game.is_null() {
+        drop(Box::from_raw(game));
+    }
+}
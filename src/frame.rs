@@ -0,0 +1,193 @@
+use crate::accessibility;
+use crossterm::{
+    cursor, queue,
+    terminal::{Clear, ClearType},
+};
+use std::{
+    io::{stdout, Write},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+/// Frames slower than this to write out suggest a terminal that would do better with
+/// `--slow-terminal`, once enough of them come in a row.
+const SLOW_FRAME: Duration = Duration::from_millis(50);
+const SLOW_STREAK: u32 = 5;
+
+static NET_FRIENDLY: AtomicBool = AtomicBool::new(false);
+static SLOW_TERMINAL: AtomicBool = AtomicBool::new(false);
+static SLOW_FRAMES: Mutex<SlowFrames> = Mutex::new(SlowFrames {
+    streak: 0,
+    total: Duration::ZERO,
+    suggested: false,
+});
+static PREVIOUS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+static LAST: Mutex<FrameStats> = Mutex::new(FrameStats {
+    bytes: 0,
+    lines_redrawn: 0,
+    lines: 0,
+});
+
+struct SlowFrames {
+    streak: u32,
+    total: Duration,
+    suggested: bool,
+}
+
+#[derive(Clone, Copy)]
+pub struct FrameStats {
+    pub bytes: usize,
+    pub lines_redrawn: usize,
+    pub lines: usize,
+}
+
+pub fn enable_net_friendly() {
+    NET_FRIENDLY.store(true, Ordering::Relaxed);
+    colored::control::set_override(false);
+}
+
+pub fn net_friendly() -> bool {
+    NET_FRIENDLY.load(Ordering::Relaxed)
+}
+
+/// Everything `--net-friendly` does, and redraws only the changed cells of each line
+/// without ever clearing the whole screen.
+pub fn enable_slow_terminal() {
+    SLOW_TERMINAL.store(true, Ordering::Relaxed);
+    enable_net_friendly();
+}
+
+pub fn slow_terminal() -> bool {
+    SLOW_TERMINAL.load(Ordering::Relaxed)
+}
+
+/// The average time the last frames took to draw, once per run, when they were all slow.
+pub fn slow_suggestion() -> Option<Duration> {
+    if slow_terminal() {
+        return None;
+    }
+    let mut slow = SLOW_FRAMES.lock().unwrap_or_else(|e| e.into_inner());
+    if slow.suggested || slow.streak < SLOW_STREAK {
+        return None;
+    }
+    slow.suggested = true;
+    Some(slow.total / slow.streak)
+}
+
+fn note_frame_time(elapsed: Duration) {
+    let mut slow = SLOW_FRAMES.lock().unwrap_or_else(|e| e.into_inner());
+    if elapsed >= SLOW_FRAME {
+        slow.streak += 1;
+        slow.total += elapsed;
+    } else {
+        slow.streak = 0;
+        slow.total = Duration::ZERO;
+    }
+}
+
+/// Queues only the part of `line` that differs from what row `row` shows now.
+fn queue_changed_cells(
+    buffer: &mut Vec<u8>,
+    row: usize,
+    old: &str,
+    line: &str,
+) -> crossterm::Result<()> {
+    let old: Vec<char> = old.chars().collect();
+    let new: Vec<char> = line.chars().collect();
+    let start = old.iter().zip(&new).take_while(|(a, b)| a == b).count();
+    let end = if old.len() == new.len() {
+        new.len()
+            - old[start..]
+                .iter()
+                .rev()
+                .zip(new[start..].iter().rev())
+                .take_while(|(a, b)| a == b)
+                .count()
+    } else {
+        new.len()
+    };
+    queue!(buffer, cursor::MoveTo(start as u16, row as u16))?;
+    buffer.extend_from_slice(new[start..end].iter().collect::<String>().as_bytes());
+    if new.len() < old.len() {
+        queue!(buffer, Clear(ClearType::UntilNewLine))?;
+    }
+    Ok(())
+}
+
+pub fn invalidate() {
+    if let Ok(mut previous) = PREVIOUS.lock() {
+        previous.clear();
+    }
+}
+
+pub fn last_stats() -> FrameStats {
+    LAST.lock().map(|stats| *stats).unwrap_or(FrameStats {
+        bytes: 0,
+        lines_redrawn: 0,
+        lines: 0,
+    })
+}
+
+pub fn present(frame: &str) -> crossterm::Result<()> {
+    let frame = accessibility::to_ascii(frame);
+    let lines: Vec<String> = frame.lines().map(str::to_string).collect();
+    let mut buffer: Vec<u8> = Vec::new();
+    let mut lines_redrawn = 0;
+
+    if net_friendly() {
+        let mut previous = PREVIOUS.lock().unwrap_or_else(|e| e.into_inner());
+        let fresh = previous.is_empty();
+        if fresh && !slow_terminal() {
+            queue!(buffer, Clear(ClearType::All))?;
+        }
+        for (row, line) in lines.iter().enumerate() {
+            match previous.get(row) {
+                Some(old) if old == line => continue,
+                Some(old) if slow_terminal() => queue_changed_cells(&mut buffer, row, old, line)?,
+                _ => {
+                    queue!(buffer, cursor::MoveTo(0, row as u16))?;
+                    buffer.extend_from_slice(line.as_bytes());
+                    queue!(buffer, Clear(ClearType::UntilNewLine))?;
+                }
+            }
+            lines_redrawn += 1;
+        }
+        if fresh && slow_terminal() {
+            queue!(
+                buffer,
+                cursor::MoveTo(0, lines.len() as u16),
+                Clear(ClearType::FromCursorDown)
+            )?;
+        }
+        for row in lines.len()..previous.len() {
+            queue!(
+                buffer,
+                cursor::MoveTo(0, row as u16),
+                Clear(ClearType::CurrentLine)
+            )?;
+        }
+        queue!(buffer, cursor::MoveTo(0, lines.len() as u16))?;
+        *previous = lines.clone();
+    } else {
+        queue!(buffer, Clear(ClearType::All), cursor::MoveTo(0, 0))?;
+        buffer.extend_from_slice(frame.as_bytes());
+        lines_redrawn = lines.len();
+    }
+
+    let started = Instant::now();
+    let mut stdout = stdout();
+    stdout.write_all(&buffer)?;
+    stdout.flush()?;
+    note_frame_time(started.elapsed());
+    if let Ok(mut last) = LAST.lock() {
+        *last = FrameStats {
+            bytes: buffer.len(),
+            lines_redrawn,
+            lines: lines.len(),
+        };
+    }
+    Ok(())
+}
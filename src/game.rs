@@ -0,0 +1,127 @@
+use crate::engine::{
+    apply_move, calculate_score, legal_moves, Direction, Merge, MoveResult, Spawner,
+};
+use crate::policy::{EndGame, GameOverPolicy};
+use crate::spawn::SpawnStrategy;
+
+pub trait GameObserver {
+    fn on_move(&mut self, _direction: Direction, _result: &MoveResult) {}
+    /// Like `on_move`, naming the seat that played it when players take turns.
+    fn on_player_move(&mut self, _player: usize, _direction: Direction, _result: &MoveResult) {}
+    fn on_merge(&mut self, _merge: &Merge) {}
+    fn on_spawn(&mut self, _position: (usize, usize), _value: u64) {}
+    fn on_turn(&mut self, _board: &[Vec<u64>]) {}
+    fn on_game_over(&mut self, _score: u64) {}
+}
+
+pub struct Game {
+    board: Vec<Vec<u64>>,
+    spawner: Spawner,
+    observers: Vec<Box<dyn GameObserver>>,
+    policy: Box<dyn GameOverPolicy>,
+    rescues: u32,
+    players: usize,
+    turn: usize,
+}
+
+impl Game {
+    pub fn new(size: usize) -> Self {
+        Self::start(size, Spawner::new())
+    }
+
+    pub fn seeded(size: usize, seed: u64) -> Self {
+        Self::start(size, Spawner::seeded(seed))
+    }
+
+    fn start(size: usize, spawner: Spawner) -> Self {
+        let mut game = Self::from_board(vec![vec![0; size]; size], spawner);
+        game.spawner.spawn(&mut game.board);
+        game.spawner.spawn(&mut game.board);
+        game
+    }
+
+    pub fn from_board(board: Vec<Vec<u64>>, spawner: Spawner) -> Self {
+        Game {
+            board,
+            spawner,
+            observers: Vec::new(),
+            policy: Box::new(EndGame),
+            rescues: 0,
+            players: 1,
+            turn: 0,
+        }
+    }
+
+    pub fn subscribe(&mut self, observer: Box<dyn GameObserver>) {
+        self.observers.push(observer);
+    }
+
+    pub fn set_policy(&mut self, policy: Box<dyn GameOverPolicy>) {
+        self.policy = policy;
+    }
+
+    pub fn set_spawn_strategy(&mut self, strategy: Box<dyn SpawnStrategy>) {
+        self.spawner.set_strategy(strategy);
+    }
+
+    /// Has `players` seats take turns, one move each, starting from the first.
+    pub fn set_players(&mut self, players: usize) {
+        self.players = players.max(1);
+        self.turn = 0;
+    }
+
+    /// The seat whose move is next.
+    pub fn player(&self) -> usize {
+        self.turn
+    }
+
+    pub fn rescues(&self) -> u32 {
+        self.rescues
+    }
+
+    pub fn board(&self) -> &[Vec<u64>] {
+        &self.board
+    }
+
+    pub fn score(&self) -> u64 {
+        calculate_score(&self.board)
+    }
+
+    pub fn is_over(&self) -> bool {
+        legal_moves(&self.board).is_empty()
+    }
+
+    pub fn play(&mut self, direction: Direction) -> MoveResult {
+        let result = apply_move(&mut self.board, direction);
+        if !result.moved {
+            return result;
+        }
+        let player = self.turn;
+        self.turn = (self.turn + 1) % self.players;
+        for observer in &mut self.observers {
+            observer.on_move(direction, &result);
+            observer.on_player_move(player, direction, &result);
+            for merge in &result.merges {
+                observer.on_merge(merge);
+            }
+        }
+        if let Some((i, j)) = self.spawner.spawn(&mut self.board) {
+            for observer in &mut self.observers {
+                observer.on_spawn((i, j), self.board[i][j]);
+            }
+        }
+        if self.is_over() && self.policy.rescue(&mut self.board) {
+            self.rescues += 1;
+        }
+        for observer in &mut self.observers {
+            observer.on_turn(&self.board);
+        }
+        if self.is_over() {
+            let score = self.score();
+            for observer in &mut self.observers {
+                observer.on_game_over(score);
+            }
+        }
+        result
+    }
+}
@@ -0,0 +1,207 @@
+use crate::config::{load_config, Config};
+use crate::difficulty::Difficulty;
+use crate::error::GameError;
+use crate::help;
+use crate::history::{load_history, GameRecord};
+use crate::i18n::{t, tf};
+use crate::layout::{print_panels, Panel};
+use crate::sound::Sound;
+use crate::theme::initialize_colors;
+use crate::{campaign, key_direction, vault};
+use colored::Color;
+use crossterm::{
+    cursor,
+    event::{poll, read, Event, KeyCode},
+    terminal::{disable_raw_mode, enable_raw_mode, Clear, ClearType},
+    ExecutableCommand,
+};
+use rust_2048_game::engine::{calculate_score, Spawner};
+use rust_2048_game::game::Game;
+use std::{collections::HashMap, io::stdout, path::Path, time::Instant};
+
+const BAR_WIDTH: usize = 30;
+
+pub fn load(rank: usize, file: Option<&Path>) -> Result<GameRecord, GameError> {
+    if let Some(path) = file {
+        return Ok(serde_json::from_str(&vault::read(path)?)?);
+    }
+    let mut history = load_history()?;
+    history.sort_by_key(|record| std::cmp::Reverse(record.score));
+    let count = history.len();
+    history
+        .into_iter()
+        .nth(rank.saturating_sub(1))
+        .ok_or_else(|| {
+            GameError::Config(tf("ghost.no_rank", &[("rank", &rank), ("count", &count)]))
+        })
+}
+
+fn difficulty(record: &GameRecord) -> Option<Difficulty> {
+    let rest = record.mode.strip_prefix("classic-")?;
+    let name = rest.split('-').next()?;
+    Difficulty::ALL
+        .into_iter()
+        .find(|difficulty| difficulty.name() == name)
+}
+
+/// Replays the ghost's seed when it still produces the recorded opening, so both
+/// players draw the same tiles for as long as they make the same moves.
+fn spawner(record: &GameRecord, config: &Config) -> Spawner {
+    if let Some(seed) = record.seed {
+        let mut spawner = match difficulty(record) {
+            Some(difficulty) => Spawner::with_four_chance(seed, difficulty.four_chance(config)),
+            None => Spawner::seeded(seed),
+        };
+        spawner.set_strategy(record.spawn_bias().strategy());
+        let size = record.start.len();
+        let mut opening = vec![vec![0; size]; size];
+        spawner.spawn(&mut opening);
+        spawner.spawn(&mut opening);
+        if opening == record.start {
+            return spawner;
+        }
+    }
+    let mut spawner = Spawner::new();
+    spawner.set_strategy(record.spawn_bias().strategy());
+    spawner
+}
+
+fn bar(label: &str, score: u64, scale: u64) -> String {
+    let filled = (score as usize * BAR_WIDTH)
+        .checked_div(scale as usize)
+        .unwrap_or(0)
+        .min(BAR_WIDTH);
+    format!(
+        " > {:<9}[{}{}] {}",
+        label,
+        "#".repeat(filled),
+        ".".repeat(BAR_WIDTH - filled),
+        score
+    )
+}
+
+fn render(
+    game: &Game,
+    ghost_board: &[Vec<u64>],
+    ghost: &GameRecord,
+    played: usize,
+    colors: &HashMap<u64, Color>,
+) -> crossterm::Result<()> {
+    let mut stdout = stdout();
+    stdout.execute(Clear(ClearType::All))?;
+    stdout.execute(cursor::MoveTo(0, 0))?;
+
+    let ghost_score = calculate_score(ghost_board);
+    let ghost_status = if played == ghost.moves.len() {
+        t("ghost.finished").to_string()
+    } else {
+        tf(
+            "ghost.move",
+            &[("move", &played), ("moves", &ghost.moves.len())],
+        )
+    };
+    print_panels(
+        &[
+            Panel {
+                title: format!("{}: {}", t("ghost.you"), game.score()),
+                board: game.board(),
+            },
+            Panel {
+                title: format!("{}: {}, {}", t("ghost.ghost"), ghost_score, ghost_status),
+                board: ghost_board,
+            },
+        ],
+        colors,
+    );
+    println!();
+    let scale = ghost.score.max(game.score()).max(ghost_score);
+    println!("{}", bar(t("ghost.you"), game.score(), scale));
+    println!("{}", bar(t("ghost.ghost"), ghost_score, scale));
+    let standing = match game.score().cmp(&ghost_score) {
+        std::cmp::Ordering::Greater => {
+            tf("ghost.ahead", &[("points", &(game.score() - ghost_score))])
+        }
+        std::cmp::Ordering::Less => {
+            tf("ghost.behind", &[("points", &(ghost_score - game.score()))])
+        }
+        std::cmp::Ordering::Equal => t("ghost.level").to_string(),
+    };
+    println!(" > {}", standing);
+    println!();
+    println!(
+        " > {}",
+        tf(
+            "ghost.racing",
+            &[("mode", &ghost.mode), ("score", &ghost.score)]
+        )
+    );
+    println!(" > {}", t("ghost.hint"));
+    Ok(())
+}
+
+pub fn run(ghost: GameRecord) -> crossterm::Result<()> {
+    let config = load_config();
+    let mut colors: HashMap<u64, Color> = HashMap::new();
+    initialize_colors(&mut colors, campaign::active_theme());
+    let mut sound = Sound::new(&config.sound);
+    let schedule = ghost.schedule();
+    let mut game = Game::from_board(ghost.start.clone(), spawner(&ghost, &config));
+    let mut ghost_board = ghost.start.clone();
+    let mut played = 0;
+    let mut started: Option<Instant> = None;
+
+    enable_raw_mode()?;
+    loop {
+        if let Some(started) = started {
+            let elapsed = started.elapsed();
+            while played < schedule.len() && schedule[played] <= elapsed {
+                ghost.moves[played].apply(&mut ghost_board);
+                played += 1;
+            }
+        }
+        render(&game, &ghost_board, &ghost, played, &colors)?;
+        if game.is_over() {
+            println!(" >> {} <<", t("game.over"));
+            break;
+        }
+
+        let next_ghost_move = started
+            .filter(|_| played < schedule.len())
+            .map(|started| schedule[played].saturating_sub(started.elapsed()));
+        if let Some(wait) = next_ghost_move {
+            if !poll(wait)? {
+                continue;
+            }
+        }
+        if let Event::Key(key_event) = read()? {
+            match key_event.code {
+                KeyCode::Esc | KeyCode::Char('e') | KeyCode::Char('E') => break,
+                KeyCode::Char('m') | KeyCode::Char('M') => {
+                    sound.toggle_mute();
+                }
+                KeyCode::Char('?') | KeyCode::F(1) => {
+                    help::show(t("mode.ghost"), t("ghost.objective"), &[])?;
+                }
+                code => {
+                    let Some(direction) = key_direction(code) else {
+                        continue;
+                    };
+                    let result = game.play(direction);
+                    if result.moved {
+                        sound.play_move(&result);
+                        started.get_or_insert_with(Instant::now);
+                    }
+                }
+            }
+        }
+    }
+    disable_raw_mode()?;
+    println!(
+        " > {}",
+        tf(
+            "ghost.result",
+            &[("score", &game.score()), ("ghost", &ghost.score)]
+        )
+    );
+    Ok(())
+}
@@ -0,0 +1,95 @@
+use crate::i18n::{label, t, tf};
+use rust_2048_game::engine::max_tile;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Reached {
+    pub tile: u64,
+    pub step: usize,
+    pub elapsed_secs: u64,
+}
+
+/// The tiles a game is played towards, in order, and when each one was first made.
+#[derive(Serialize, Deserialize, Clone, Default, Debug)]
+pub struct Goals {
+    pub tiles: Vec<u64>,
+    pub reached: Vec<Reached>,
+}
+
+impl Goals {
+    pub fn new(tiles: &[u64]) -> Self {
+        let mut tiles: Vec<u64> = tiles
+            .iter()
+            .copied()
+            .filter(|tile| *tile >= 4 && tile.is_power_of_two())
+            .collect();
+        tiles.sort_unstable();
+        tiles.dedup();
+        Goals {
+            tiles,
+            reached: Vec::new(),
+        }
+    }
+
+    pub fn next(&self) -> Option<u64> {
+        self.tiles.get(self.reached.len()).copied()
+    }
+
+    /// Marks every goal the board has now made, returning the ones reached by this move.
+    pub fn check(&mut self, game_board: &[Vec<u64>], step: usize, elapsed_secs: u64) -> Vec<u64> {
+        let max_tile = max_tile(game_board);
+        let mut reached = Vec::new();
+        while let Some(tile) = self.next().filter(|&tile| max_tile >= tile) {
+            self.reached.push(Reached {
+                tile,
+                step,
+                elapsed_secs,
+            });
+            reached.push(tile);
+        }
+        reached
+    }
+
+    pub fn message(&self) -> String {
+        let progress = match self.next() {
+            Some(tile) => format!("{} {}", label("label.goal"), tile),
+            None => format!("{} {}", label("label.goal"), t("goal.all_reached")),
+        };
+        if self.reached.is_empty() {
+            return progress;
+        }
+        format!(
+            "{}, {}",
+            progress,
+            tf("goal.made", &[("timeline", &timeline(&self.reached))])
+        )
+    }
+
+    pub fn banner(&self, tile: u64) -> String {
+        let text = match self.next() {
+            Some(next) => tf("goal.next", &[("tile", &tile), ("next", &next)]),
+            None => tf("goal.last", &[("tile", &tile)]),
+        };
+        format!("{} {}", label("label.goal_reached"), text)
+    }
+}
+
+pub fn timeline(reached: &[Reached]) -> String {
+    reached
+        .iter()
+        .map(|goal| {
+            tf(
+                "goal.at",
+                &[
+                    ("tile", &goal.tile),
+                    ("move", &goal.step),
+                    (
+                        "time",
+                        &format!("{}m{:02}s", goal.elapsed_secs / 60, goal.elapsed_secs % 60),
+                    ),
+                ],
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
@@ -0,0 +1,157 @@
+use crate::accessibility;
+use crate::error::GameError;
+use crate::frame;
+use crate::history::ScorePoint;
+use crate::i18n::{t, tf};
+use crossterm::{
+    cursor,
+    event::{read, Event},
+    terminal::{Clear, ClearType},
+    ExecutableCommand,
+};
+use std::{fmt::Write as _, fs, io::stdout, path::Path};
+
+const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+const ASCII_BARS: [char; 8] = ['_', '.', '-', ':', '=', '+', '*', '#'];
+const SPARKLINE_WIDTH: usize = 30;
+const GRAPH_WIDTH: usize = 60;
+const GRAPH_HEIGHT: usize = 12;
+/// Merges below this are too common to be worth pointing out.
+const BIG_MERGE: u64 = 64;
+
+/// Splits the series into at most `width` runs of moves, keeping the last point of each.
+fn columns<T>(series: &[T], width: usize) -> Vec<&[T]> {
+    let per_column = series.len().div_ceil(width.max(1)).max(1);
+    series.chunks(per_column).collect()
+}
+
+/// The moves that made a new largest tile, from 64 up.
+fn big_merges(series: &[ScorePoint]) -> Vec<&ScorePoint> {
+    let mut largest = BIG_MERGE - 1;
+    series
+        .iter()
+        .filter(|point| {
+            let big = point.merged > largest;
+            largest = largest.max(point.merged);
+            big
+        })
+        .collect()
+}
+
+/// A one-line graph of `scores`, which may be a sample of the game taken at even steps.
+pub fn sparkline(scores: &[u64]) -> String {
+    let bars = if accessibility::ascii() {
+        ASCII_BARS
+    } else {
+        BARS
+    };
+    let top = scores.iter().copied().max().unwrap_or(0);
+    columns(scores, SPARKLINE_WIDTH)
+        .iter()
+        .filter_map(|column| column.last())
+        .map(|&score| {
+            let level = (score * (bars.len() as u64 - 1))
+                .checked_div(top)
+                .unwrap_or(0);
+            bars[level as usize]
+        })
+        .collect()
+}
+
+fn lines(series: &[ScorePoint]) -> Vec<String> {
+    let top = series.iter().map(|point| point.score).max().unwrap_or(0);
+    let columns = columns(series, GRAPH_WIDTH);
+    let heights: Vec<usize> = columns
+        .iter()
+        .filter_map(|column| column.last())
+        .map(|point| {
+            (point.score as usize * GRAPH_HEIGHT)
+                .checked_div(top as usize)
+                .unwrap_or(0)
+        })
+        .collect();
+    let big = big_merges(series);
+
+    let mut lines = Vec::new();
+    for level in (1..=GRAPH_HEIGHT).rev() {
+        let label = match level {
+            GRAPH_HEIGHT => top.to_string(),
+            1 => "0".to_string(),
+            _ => String::new(),
+        };
+        let bars: String = heights
+            .iter()
+            .map(|&height| if height >= level { '#' } else { ' ' })
+            .collect();
+        lines.push(format!("{:>7} |{}", label, bars));
+    }
+    lines.push(format!("{:>7} +{}", "", "-".repeat(heights.len())));
+    let markers: String = columns
+        .iter()
+        .map(|column| {
+            let marked = column
+                .iter()
+                .any(|point| big.iter().any(|merge| merge.step == point.step));
+            if marked {
+                '^'
+            } else {
+                ' '
+            }
+        })
+        .collect();
+    lines.push(format!("{:>7}  {}", "", markers));
+    let last = series.last().map_or(0, |point| point.step);
+    let first = tf("graph.first_move", &[("move", &0)]);
+    lines.push(format!(
+        "{:>7}  {}{:>width$}",
+        "",
+        first,
+        last,
+        width = heights.len().saturating_sub(first.chars().count())
+    ));
+    lines.push(String::new());
+    if big.is_empty() {
+        lines.push(tf("graph.no_big_merges", &[("value", &BIG_MERGE)]));
+    } else {
+        let mut text = t("graph.big_merges").to_string();
+        for merge in big {
+            let at = tf(
+                "graph.merge_at",
+                &[("value", &merge.merged), ("move", &merge.step)],
+            );
+            let _ = write!(text, " {},", at);
+        }
+        text.pop();
+        lines.push(text);
+    }
+    lines
+}
+
+/// Draws the score over the whole game, marking the moves that made a new largest tile.
+pub fn show(title: &str, series: &[ScorePoint]) -> crossterm::Result<()> {
+    frame::invalidate();
+    let mut stdout = stdout();
+    stdout.execute(Clear(ClearType::All))?;
+    stdout.execute(cursor::MoveTo(0, 0))?;
+    println!(" > {}", title);
+    println!();
+    for line in lines(series) {
+        println!("   {}", line);
+    }
+    println!();
+    println!(" > {}", t("hint.back"));
+    loop {
+        if let Event::Key(_) = read()? {
+            return Ok(());
+        }
+    }
+}
+
+pub fn write_csv(path: &Path, series: &[ScorePoint]) -> Result<(), GameError> {
+    let mut csv = String::from("move,score,merged\n");
+    for point in series {
+        let _ = writeln!(csv, "{},{},{}", point.step, point.score, point.merged);
+    }
+    fs::write(path, csv)?;
+    Ok(())
+}
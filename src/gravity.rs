@@ -0,0 +1,172 @@
+use crate::accessibility;
+use crate::broadcast;
+use crate::config::load_config;
+use crate::frame;
+use crate::help;
+use crate::i18n::{self, label, t, tf};
+use crate::sound::Sound;
+use crate::theme::initialize_colors;
+use crate::{campaign, key_direction, read_high_score, render_board};
+use crossterm::{
+    event::{poll, read, Event, KeyCode},
+    terminal::{disable_raw_mode, enable_raw_mode},
+};
+use rust_2048_game::engine::{
+    apply_move, calculate_score, can_make_move_with_gravity, settle, spawn_random_tile, Direction,
+};
+use rust_2048_game::link::BoardLink;
+use std::{
+    collections::{HashMap, VecDeque},
+    time::{Duration, Instant},
+};
+
+fn with_queue(messages: &[String], queue: &VecDeque<Direction>) -> Vec<String> {
+    let mut shown = messages.to_vec();
+    if !queue.is_empty() {
+        let moves: Vec<String> = queue
+            .iter()
+            .map(|direction| format!("{:?}", direction))
+            .collect();
+        shown.push(format!("{} {}", label("label.queued"), moves.join(" ")));
+    }
+    shown
+}
+
+pub fn run(gravity: Direction, opened: Option<Vec<Vec<u64>>>) -> crossterm::Result<()> {
+    let mut colors: HashMap<u64, colored::Color> = HashMap::new();
+    initialize_colors(&mut colors, campaign::active_theme());
+    let config = load_config();
+    let fall_frame = Duration::from_millis(config.game.animation_ms);
+    let mut sound = Sound::new(&config.sound);
+    let high_score = read_high_score();
+    let mut messages = vec![
+        tf(
+            "gravity.pulls",
+            &[("direction", &i18n::direction(gravity).to_lowercase())],
+        ),
+        t("hint.share").to_string(),
+    ];
+    let mut show_risk = false;
+
+    let mut game_board = match opened {
+        Some(game_board) => game_board,
+        None => {
+            let mut game_board = vec![vec![0; config.game.board_size]; config.game.board_size];
+            spawn_random_tile(&mut game_board);
+            spawn_random_tile(&mut game_board);
+            game_board
+        }
+    };
+    settle(&mut game_board, gravity);
+
+    let mut queue: VecDeque<Direction> = VecDeque::new();
+
+    enable_raw_mode()?;
+    loop {
+        render_board(
+            &game_board,
+            &colors,
+            calculate_score(&game_board),
+            high_score,
+            show_risk,
+            &with_queue(&messages, &queue),
+        )?;
+        if !can_make_move_with_gravity(&game_board, gravity) {
+            println!(" >> {} <<", t("game.over"));
+            broadcast::send_game_over(calculate_score(&game_board), t("broadcast.no_moves"));
+            break;
+        }
+
+        let direction = match queue.pop_front() {
+            Some(direction) => direction,
+            None => {
+                let Event::Key(key_event) = read()? else {
+                    continue;
+                };
+                match key_event.code {
+                    KeyCode::Char('e') | KeyCode::Char('E') => break,
+                    KeyCode::Char('r') | KeyCode::Char('R') => {
+                        show_risk = !show_risk;
+                        continue;
+                    }
+                    KeyCode::Char('m') | KeyCode::Char('M') => {
+                        sound.toggle_mute();
+                        continue;
+                    }
+                    KeyCode::Char('?') | KeyCode::F(1) => {
+                        help::show(
+                            t("mode.gravity"),
+                            t("gravity.objective"),
+                            &[("S", t("help.classic.share"))],
+                        )?;
+                        continue;
+                    }
+                    KeyCode::Char('s') | KeyCode::Char('S') => {
+                        let link = BoardLink::new(
+                            format!("gravity-{:?}", gravity).to_lowercase(),
+                            game_board.clone(),
+                            None,
+                            0,
+                        );
+                        messages.truncate(2);
+                        messages.push(match link {
+                            Ok(link) => format!("{} {}", label("label.share_link"), link),
+                            Err(tile) => format!(
+                                "{} {}",
+                                label("label.share_link"),
+                                tf("link.unshareable", &[("tile", &tile.to_string())])
+                            ),
+                        });
+                        continue;
+                    }
+                    code => match key_direction(code) {
+                        Some(direction) => direction,
+                        None => continue,
+                    },
+                }
+            }
+        };
+
+        let before = game_board.clone();
+        let result = apply_move(&mut game_board, direction);
+        let mut settled = game_board.clone();
+        let falls = settle(&mut settled, gravity);
+        if settled == before {
+            game_board = before;
+            continue;
+        }
+
+        sound.play_move(&result);
+        broadcast::send_move(direction, &result);
+        accessibility::record_move(direction, &result);
+        if !falls.is_empty() && !fall_frame.is_zero() && !frame::net_friendly() {
+            let until = Instant::now() + fall_frame;
+            loop {
+                render_board(
+                    &game_board,
+                    &colors,
+                    calculate_score(&game_board),
+                    high_score,
+                    show_risk,
+                    &with_queue(&messages, &queue),
+                )?;
+                let now = Instant::now();
+                if now >= until || !poll(until - now)? {
+                    break;
+                }
+                if let Event::Key(key_event) = read()? {
+                    if let Some(queued) = key_direction(key_event.code) {
+                        if queue.len() < config.game.move_queue {
+                            queue.push_back(queued);
+                        }
+                    }
+                }
+            }
+        }
+        game_board = settled;
+        spawn_random_tile(&mut game_board);
+        settle(&mut game_board, gravity);
+    }
+    disable_raw_mode()?;
+    Ok(())
+}
@@ -0,0 +1,57 @@
+use crate::frame;
+use crate::i18n::{t, tf};
+use crossterm::{
+    cursor,
+    event::{read, Event},
+    terminal::{Clear, ClearType},
+    ExecutableCommand,
+};
+use std::io::stdout;
+
+const RULES: [&str; 4] = [
+    "help.rule.slide",
+    "help.rule.merge",
+    "help.rule.spawn",
+    "help.rule.end",
+];
+
+pub fn show(mode: &str, objective: &str, extra_keys: &[(&str, &str)]) -> crossterm::Result<()> {
+    frame::invalidate();
+    let mut stdout = stdout();
+    stdout.execute(Clear(ClearType::All))?;
+    stdout.execute(cursor::MoveTo(0, 0))?;
+
+    let mut keys: Vec<(&str, &str)> = vec![
+        (t("help.key.arrows"), t("help.action.move")),
+        ("R", t("help.action.risk")),
+    ];
+    if cfg!(feature = "sound") {
+        keys.push(("M", t("help.action.sound")));
+    }
+    keys.extend_from_slice(extra_keys);
+    keys.push(("? / F1", t("help.action.help")));
+    keys.push(("E", t("help.action.exit")));
+
+    println!(" > {}", tf("help.title", &[("mode", &mode)]));
+    println!();
+    println!(" > {}", t("help.objective"));
+    println!("   {}", objective);
+    println!();
+    println!(" > {}", t("help.keys"));
+    for (key, action) in keys {
+        println!("   {:<12}{}", key, action);
+    }
+    println!();
+    println!(" > {}", t("help.rules"));
+    for rule in RULES {
+        println!("   {}", t(rule));
+    }
+    println!();
+    println!(" > {}", t("help.back"));
+
+    loop {
+        if let Event::Key(_) = read()? {
+            return Ok(());
+        }
+    }
+}
@@ -0,0 +1,180 @@
+use crate::engine::{legal_moves, max_tile, OBSTACLE};
+use std::collections::HashMap;
+
+pub const COMPROMISED_RISK: f64 = 0.6;
+
+pub fn empty_cells(game_board: &[Vec<u64>]) -> usize {
+    game_board
+        .iter()
+        .flatten()
+        .filter(|&&cell| cell == 0)
+        .count()
+}
+
+fn tile_rank(value: u64) -> f64 {
+    if value == 0 || value == OBSTACLE {
+        0.0
+    } else {
+        (value as f64).log2()
+    }
+}
+
+fn line_monotonicity(line: &[u64]) -> f64 {
+    if line.len() < 2 {
+        return 1.0;
+    }
+    let mut increasing = 0.0;
+    let mut decreasing = 0.0;
+    for pair in line.windows(2) {
+        let (a, b) = (tile_rank(pair[0]), tile_rank(pair[1]));
+        if a <= b {
+            increasing += 1.0;
+        }
+        if a >= b {
+            decreasing += 1.0;
+        }
+    }
+    f64::max(increasing, decreasing) / (line.len() - 1) as f64
+}
+
+pub fn monotonicity(game_board: &[Vec<u64>]) -> f64 {
+    if game_board.is_empty() || game_board[0].is_empty() {
+        return 1.0;
+    }
+    let mut lines: Vec<f64> = game_board
+        .iter()
+        .map(|row| line_monotonicity(row))
+        .collect();
+    for col in 0..game_board[0].len() {
+        let column: Vec<u64> = game_board.iter().map(|row| row[col]).collect();
+        lines.push(line_monotonicity(&column));
+    }
+    lines.iter().sum::<f64>() / lines.len() as f64
+}
+
+fn is_tile(value: u64) -> bool {
+    value != 0 && value != OBSTACLE
+}
+
+pub fn smoothness(game_board: &[Vec<u64>]) -> f64 {
+    let mut total_difference = 0.0;
+    let mut pairs = 0;
+    for (i, row) in game_board.iter().enumerate() {
+        for (j, &cell) in row.iter().enumerate() {
+            if !is_tile(cell) {
+                continue;
+            }
+            let right = row.get(j + 1).copied();
+            let below = game_board.get(i + 1).map(|next| next[j]);
+            for neighbor in [right, below].into_iter().flatten() {
+                if is_tile(neighbor) {
+                    total_difference += (tile_rank(cell) - tile_rank(neighbor)).abs();
+                    pairs += 1;
+                }
+            }
+        }
+    }
+    if pairs == 0 {
+        return 1.0;
+    }
+    1.0 / (1.0 + total_difference / pairs as f64)
+}
+
+pub fn entropy(game_board: &[Vec<u64>]) -> f64 {
+    let tiles: Vec<u64> = game_board
+        .iter()
+        .flatten()
+        .copied()
+        .filter(|&val| is_tile(val))
+        .collect();
+    if tiles.is_empty() {
+        return 0.0;
+    }
+    let mut counts: HashMap<u64, usize> = HashMap::new();
+    for &tile in &tiles {
+        *counts.entry(tile).or_insert(0) += 1;
+    }
+    counts
+        .values()
+        .map(|&count| {
+            let p = count as f64 / tiles.len() as f64;
+            p * (1.0 / p).log2()
+        })
+        .sum()
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct BoardMetrics {
+    pub empty_cells: usize,
+    pub monotonicity: f64,
+    pub smoothness: f64,
+    pub entropy: f64,
+    pub risk: f64,
+}
+
+pub fn metrics(game_board: &[Vec<u64>]) -> BoardMetrics {
+    BoardMetrics {
+        empty_cells: empty_cells(game_board),
+        monotonicity: monotonicity(game_board),
+        smoothness: smoothness(game_board),
+        entropy: entropy(game_board),
+        risk: risk(game_board),
+    }
+}
+
+pub fn risk(game_board: &[Vec<u64>]) -> f64 {
+    let total = game_board.iter().map(|row| row.len()).sum::<usize>();
+    if total == 0 {
+        return 0.0;
+    }
+    if legal_moves(game_board).is_empty() {
+        return 1.0;
+    }
+    let crowding = 1.0 - empty_cells(game_board) as f64 / total as f64;
+    let disorder = 1.0 - monotonicity(game_board);
+    let roughness = 1.0 - smoothness(game_board);
+    (0.6 * crowding * crowding + 0.25 * disorder + 0.15 * roughness).clamp(0.0, 1.0)
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Weights {
+    pub empty_cells: f64,
+    pub monotonicity: f64,
+    pub corner: f64,
+}
+
+impl Default for Weights {
+    fn default() -> Self {
+        Weights {
+            empty_cells: 2.7,
+            monotonicity: 4.0,
+            corner: 2.0,
+        }
+    }
+}
+
+pub fn evaluate(game_board: &[Vec<u64>]) -> f64 {
+    evaluate_with(game_board, &Weights::default())
+}
+
+pub fn evaluate_with(game_board: &[Vec<u64>], weights: &Weights) -> f64 {
+    let max_tile = max_tile(game_board);
+    let rows = game_board.len();
+    let cols = game_board.first().map_or(0, |row| row.len());
+    let corners = [
+        (0, 0),
+        (0, cols.saturating_sub(1)),
+        (rows.saturating_sub(1), 0),
+        (rows.saturating_sub(1), cols.saturating_sub(1)),
+    ];
+    let max_in_corner =
+        rows > 0 && cols > 0 && corners.iter().any(|&(i, j)| game_board[i][j] == max_tile);
+
+    let mut value = empty_cells(game_board) as f64 * weights.empty_cells;
+    value += monotonicity(game_board) * weights.monotonicity;
+    value += tile_rank(max_tile);
+    if max_in_corner {
+        value += weights.corner;
+    }
+    value
+}
@@ -0,0 +1,109 @@
+use crate::campaign;
+use crate::i18n::{label, t};
+use crate::theme::{initialize_colors, marked_label, paint};
+use colored::*;
+use crossterm::{
+    cursor,
+    event::{read, Event, KeyCode},
+    terminal::{disable_raw_mode, enable_raw_mode, Clear, ClearType},
+    ExecutableCommand,
+};
+use rand::{seq::IteratorRandom, thread_rng, Rng};
+use rust_2048_game::topology::{can_move, shift, HexDirection, HexTopology, Topology};
+use std::{collections::HashMap, io::stdout};
+
+fn key_hex_direction(code: KeyCode) -> Option<HexDirection> {
+    match code {
+        KeyCode::Char('q') => Some(HexDirection::UpLeft),
+        KeyCode::Char('w') => Some(HexDirection::Up),
+        KeyCode::Char('e') => Some(HexDirection::UpRight),
+        KeyCode::Char('a') => Some(HexDirection::DownLeft),
+        KeyCode::Char('s') => Some(HexDirection::Down),
+        KeyCode::Char('d') => Some(HexDirection::DownRight),
+        _ => None,
+    }
+}
+
+fn spawn_random_hex_tile(cells: &mut [u64]) {
+    let empty = cells.iter().enumerate().filter(|(_, &val)| val == 0);
+    if let Some((i, _)) = empty.choose(&mut thread_rng()) {
+        cells[i] = if thread_rng().gen_bool(0.9) { 2 } else { 4 };
+    }
+}
+
+fn render_hex_board(
+    cells: &[u64],
+    topology: &HexTopology,
+    colors: &HashMap<u64, Color>,
+) -> crossterm::Result<()> {
+    let mut stdout: std::io::Stdout = stdout();
+    stdout.execute(Clear(ClearType::All))?;
+    stdout.execute(cursor::MoveTo(0, 0))?;
+
+    let min_q = topology.coords().iter().map(|c| c.0).min().unwrap_or(0);
+    let max_q = topology.coords().iter().map(|c| c.0).max().unwrap_or(0);
+    let lines = topology.coords().iter().map(|&(q, r)| 2 * r + q);
+    let (top, bottom) = (lines.clone().min().unwrap_or(0), lines.max().unwrap_or(0));
+
+    for y in top..=bottom {
+        let mut line = String::new();
+        for q in min_q..=max_q {
+            let cell = if (y - q) % 2 == 0 {
+                topology.cell_at(q, (y - q) / 2)
+            } else {
+                None
+            };
+            match cell {
+                Some(i) if cells[i] == 0 => line.push_str(&format!("{:^6}", ".")),
+                Some(i) => {
+                    let label = format!("{:^6}", marked_label(cells[i]));
+                    line.push_str(&paint(&label, colors, cells[i]).to_string());
+                }
+                None => line.push_str("      "),
+            }
+        }
+        println!("{}", line.trim_end());
+    }
+    Ok(())
+}
+
+pub fn run(radius: i32) -> crossterm::Result<()> {
+    let topology = HexTopology::new(radius.clamp(1, 4));
+    let mut cells = vec![0; topology.cell_count()];
+    let mut colors: HashMap<u64, Color> = HashMap::new();
+    initialize_colors(&mut colors, campaign::active_theme());
+    spawn_random_hex_tile(&mut cells);
+    spawn_random_hex_tile(&mut cells);
+
+    enable_raw_mode()?;
+    loop {
+        render_hex_board(&cells, &topology, &colors)?;
+        println!();
+        println!(
+            " > {} {}",
+            label("label.current_score"),
+            cells.iter().sum::<u64>()
+        );
+        println!();
+        println!(" > {}", t("hex.keys"));
+        println!(" > {}", t("hint.esc_exit"));
+
+        if !can_move(&cells, &topology) {
+            println!(" >> {} <<", t("game.over"));
+            break;
+        }
+
+        if let Event::Key(key_event) = read()? {
+            if key_event.code == KeyCode::Esc {
+                break;
+            }
+            if let Some(direction) = key_hex_direction(key_event.code) {
+                if shift(&mut cells, &topology, direction) {
+                    spawn_random_hex_tile(&mut cells);
+                }
+            }
+        }
+    }
+    disable_raw_mode()?;
+    Ok(())
+}
@@ -0,0 +1,662 @@
+use crate::config::{GameConfig, SpawnBias};
+use crate::coop::Contribution;
+use crate::error::GameError;
+use crate::goals::{self, Reached};
+use crate::i18n::{label, t, tf};
+use crate::theme::initialize_colors;
+use crate::{campaign, frame, graph, logging, profile, read_high_score, render_board, vault};
+use crossterm::{
+    cursor,
+    event::{poll, read, Event, KeyCode},
+    terminal::{disable_raw_mode, enable_raw_mode, Clear, ClearType},
+    ExecutableCommand,
+};
+use rust_2048_game::engine::{self, apply_move, calculate_score, Direction};
+use rust_2048_game::journal::{Journal, Spawn};
+use rust_2048_game::scoring::Combo;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    io::stdout,
+    path::PathBuf,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+const HISTORY_FILE: &str = "history.json";
+const LEADERBOARD_SIZE: usize = 100;
+const REPLAY_STEP: Duration = Duration::from_millis(300);
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ReplayStep {
+    pub direction: Direction,
+    pub spawn: Option<(usize, usize, u64)>,
+    #[serde(default)]
+    pub elapsed_ms: u64,
+}
+
+impl ReplayStep {
+    pub fn apply(&self, game_board: &mut [Vec<u64>]) {
+        apply_move(game_board, self.direction);
+        if let Some((i, j, value)) = self.spawn {
+            game_board[i][j] = value;
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct GameRecord {
+    pub mode: String,
+    pub finished: u64,
+    pub duration_secs: u64,
+    pub score: u64,
+    pub board: Vec<Vec<u64>>,
+    pub start: Vec<Vec<u64>>,
+    pub moves: Vec<ReplayStep>,
+    #[serde(default)]
+    pub seed: Option<u64>,
+    #[serde(default)]
+    pub goals: Vec<Reached>,
+    #[serde(default)]
+    pub players: Vec<Contribution>,
+}
+
+impl GameRecord {
+    pub fn schedule(&self) -> Vec<Duration> {
+        let timed = timed(self);
+        let mut at = Duration::ZERO;
+        self.moves
+            .iter()
+            .map(|replay_step| {
+                at += if timed {
+                    Duration::from_millis(replay_step.elapsed_ms)
+                } else {
+                    REPLAY_STEP
+                };
+                at
+            })
+            .collect()
+    }
+
+    /// The spawn bias the game was played with, from its mode.
+    pub fn spawn_bias(&self) -> SpawnBias {
+        [SpawnBias::Gentle, SpawnBias::Evil]
+            .into_iter()
+            .find(|bias| self.mode.split('-').any(|part| part == bias.name()))
+            .unwrap_or_default()
+    }
+
+    pub fn score_series(&self) -> Vec<ScorePoint> {
+        score_series(
+            &self.start,
+            self.moves
+                .iter()
+                .map(|replay_step| (replay_step.direction, replay_step.spawn)),
+        )
+    }
+}
+
+/// The score after a move and the largest tile that move merged, 0 if it merged none.
+pub struct ScorePoint {
+    pub step: usize,
+    pub score: u64,
+    pub merged: u64,
+}
+
+fn score_series(
+    start: &[Vec<u64>],
+    moves: impl Iterator<Item = (Direction, Option<Spawn>)>,
+) -> Vec<ScorePoint> {
+    let mut game_board = start.to_vec();
+    let mut series = vec![ScorePoint {
+        step: 0,
+        score: calculate_score(&game_board),
+        merged: 0,
+    }];
+    for (step, (direction, spawn)) in moves.enumerate() {
+        let result = apply_move(&mut game_board, direction);
+        if let Some((i, j, value)) = spawn {
+            game_board[i][j] = value;
+        }
+        series.push(ScorePoint {
+            step: step + 1,
+            score: calculate_score(&game_board),
+            merged: result
+                .merges
+                .iter()
+                .map(|merge| merge.value)
+                .max()
+                .unwrap_or(0),
+        });
+    }
+    series
+}
+
+/// Appends `value` as a LEB128 varint, seven bits to a byte.
+fn push_varint(bytes: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        bytes.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    bytes.push(value as u8);
+}
+
+/// Reads back the varints `push_varint` wrote, in order.
+fn varints(bytes: &[u8]) -> impl Iterator<Item = u64> + '_ {
+    let mut rest = bytes;
+    std::iter::from_fn(move || {
+        let end = rest.iter().position(|&byte| byte < 0x80)?;
+        let value = rest[..=end]
+            .iter()
+            .rev()
+            .fold(0u64, |value, &byte| value << 7 | u64::from(byte & 0x7f));
+        rest = &rest[end + 1..];
+        Some(value)
+    })
+}
+
+/// Scores kept for the live sparkline, about this many at most.
+const SPARKLINE_SAMPLES: usize = 64;
+
+/// Keeps the game being played as a compact journal, so even an endless game can be
+/// undone, scrubbed and replayed without holding every board. A move costs about five
+/// bytes: three in the journal and usually two of thinking time.
+pub struct Recorder {
+    journal: Journal,
+    /// Milliseconds of thinking before each move, as varints so a typical pause of under
+    /// 16 seconds takes two bytes.
+    thinking: Vec<u8>,
+    /// The score and combo every `sample_every` moves from the start, thinned out to every
+    /// other one as the game grows, so neither the sparkline nor a rewind replays the
+    /// whole journal.
+    samples: Vec<(u64, Combo)>,
+    sample_every: usize,
+    combo: Combo,
+    started: Instant,
+    last_move: Instant,
+}
+
+impl Recorder {
+    /// Starts recording from `game_board`, with `combo` the streak and bonus it already had.
+    pub fn new(game_board: &[Vec<u64>], config: &GameConfig, combo: Combo) -> Self {
+        Recorder {
+            journal: Journal::new(game_board, config.checkpoint_moves, config.max_checkpoints),
+            thinking: Vec::new(),
+            samples: vec![(calculate_score(game_board), combo)],
+            sample_every: 1,
+            combo,
+            started: Instant::now(),
+            last_move: Instant::now(),
+        }
+    }
+
+    pub fn record(&mut self, direction: Direction, moved: &[Vec<u64>], spawned: &[Vec<u64>]) {
+        let spawn = moved.iter().enumerate().find_map(|(i, row)| {
+            row.iter()
+                .enumerate()
+                .find(|&(j, &cell)| cell != spawned[i][j])
+                .map(|(j, _)| (i, j, spawned[i][j]))
+        });
+        let millis = self.last_move.elapsed().as_millis();
+        self.last_move = Instant::now();
+        self.combo.record(&self.journal.push(direction, spawn));
+        push_varint(
+            &mut self.thinking,
+            u64::try_from(millis).unwrap_or(u64::MAX),
+        );
+        if self.journal.len().is_multiple_of(self.sample_every) {
+            self.samples.push((calculate_score(spawned), self.combo));
+            if self.samples.len() > 2 * SPARKLINE_SAMPLES {
+                self.sample_every *= 2;
+                self.samples = self.samples.iter().step_by(2).copied().collect();
+            }
+        }
+    }
+
+    pub fn moves_per_minute(&self) -> f64 {
+        per_minute(self.journal.len(), self.started.elapsed())
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.started.elapsed()
+    }
+
+    pub fn thinking(&self) -> Duration {
+        self.last_move.elapsed()
+    }
+
+    pub fn len(&self) -> usize {
+        self.journal.len()
+    }
+
+    pub fn position(&self, step: usize) -> Vec<Vec<u64>> {
+        self.journal.position(step)
+    }
+
+    /// Every move's score and merge, replayed from the start for the full graph.
+    pub fn score_series(&self) -> Vec<ScorePoint> {
+        score_series(self.journal.start(), self.journal.events())
+    }
+
+    /// The score at evenly spaced moves and now, cheap enough to redraw on every move.
+    pub fn score_samples(&self) -> Vec<u64> {
+        let mut scores: Vec<u64> = self.samples.iter().map(|&(score, _)| score).collect();
+        if !self.len().is_multiple_of(self.sample_every) {
+            scores.push(calculate_score(self.journal.current()));
+        }
+        scores
+    }
+
+    /// The combo streak and bonus after the last move.
+    pub fn combo(&self) -> Combo {
+        self.combo
+    }
+
+    pub fn branch(&mut self, step: usize) -> Vec<Vec<u64>> {
+        self.journal.truncate(step);
+        let kept: usize = self
+            .thinking
+            .iter()
+            .scan(0, |moves, &byte| {
+                let before = *moves;
+                *moves += usize::from(byte < 0x80);
+                Some(before)
+            })
+            .take_while(|&moves| moves < step)
+            .count();
+        self.thinking.truncate(kept);
+        let sampled = step.min(self.len()) / self.sample_every;
+        self.samples.truncate(sampled + 1);
+        let from = sampled * self.sample_every;
+        let mut game_board = self.journal.position(from);
+        self.combo = self.samples[sampled].1;
+        for at in from..self.len() {
+            let (direction, spawn) = self.journal.event(at);
+            self.combo.record(&apply_move(&mut game_board, direction));
+            if let Some((i, j, value)) = spawn {
+                game_board[i][j] = value;
+            }
+        }
+        self.last_move = Instant::now();
+        self.journal.current().to_vec()
+    }
+
+    /// Expands the journal into a `GameRecord`; the compact form only covers the game in
+    /// play, saved games keep the readable one-step-per-move format.
+    pub fn finish(self, mode: &str, game_board: &[Vec<u64>]) -> GameRecord {
+        let moves = self
+            .journal
+            .events()
+            .zip(varints(&self.thinking))
+            .map(|((direction, spawn), elapsed_ms)| ReplayStep {
+                direction,
+                spawn,
+                elapsed_ms,
+            })
+            .collect();
+        GameRecord {
+            mode: mode.to_string(),
+            finished: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|elapsed| elapsed.as_secs())
+                .unwrap_or(0),
+            duration_secs: self.started.elapsed().as_secs(),
+            score: calculate_score(game_board),
+            board: game_board.to_vec(),
+            start: self.journal.start().to_vec(),
+            moves,
+            seed: None,
+            goals: Vec::new(),
+            players: Vec::new(),
+        }
+    }
+}
+
+pub fn load_history() -> Result<Vec<GameRecord>, GameError> {
+    Ok(vault::load(&profile::path(HISTORY_FILE))?.unwrap_or_default())
+}
+
+pub fn append(record: GameRecord) -> Result<(), GameError> {
+    let mut history = load_history()?;
+    history.push(record);
+    vault::write(
+        &profile::path(HISTORY_FILE),
+        &serde_json::to_string(&history)?,
+    )
+}
+
+fn per_minute(moves: usize, elapsed: Duration) -> f64 {
+    if elapsed < Duration::from_secs(1) {
+        return 0.0;
+    }
+    moves as f64 * 60.0 / elapsed.as_secs_f64()
+}
+
+fn timed(record: &GameRecord) -> bool {
+    record
+        .moves
+        .iter()
+        .any(|replay_step| replay_step.elapsed_ms > 0)
+}
+
+fn summary(record: &GameRecord) -> String {
+    let max_tile = engine::max_tile(&record.board);
+    let mut text = tf(
+        "history.summary",
+        &[
+            ("day", &format!("{:<6}", record.finished / 86_400)),
+            ("mode", &format!("{:<14}", record.mode)),
+            ("score", &format!("{:<6}", record.score)),
+            ("max", &format!("{:<5}", max_tile)),
+            ("moves", &record.moves.len()),
+            (
+                "time",
+                &format!(
+                    "{}m{:02}s",
+                    record.duration_secs / 60,
+                    record.duration_secs % 60
+                ),
+            ),
+        ],
+    );
+    if timed(record) {
+        let thinking: u64 = record.moves.iter().map(|step| step.elapsed_ms).sum();
+        let rate = per_minute(record.moves.len(), Duration::from_millis(thinking));
+        text.push_str(", ");
+        text.push_str(&tf("history.rate", &[("rate", &format!("{:.0}", rate))]));
+    }
+    if !record.goals.is_empty() {
+        text.push_str(", ");
+        text.push_str(&tf(
+            "goal.made",
+            &[("timeline", &goals::timeline(&record.goals))],
+        ));
+    }
+    for player in &record.players {
+        text.push_str(&format!(", {}", player.summary()));
+    }
+    text
+}
+
+pub fn print_leaderboard() {
+    let mut history = match load_history() {
+        Ok(history) => history,
+        Err(e) => {
+            logging::error(tf("error.load_history", &[("error", &e)]));
+            return;
+        }
+    };
+    history.sort_by_key(|record| std::cmp::Reverse(record.score));
+    println!(" > {}", t("history.leaderboard"));
+    println!();
+    if history.is_empty() {
+        println!("   {}", t("history.empty"));
+    }
+    for (rank, record) in history.iter().take(LEADERBOARD_SIZE).enumerate() {
+        println!("   {:>3}. {}", rank + 1, summary(record));
+    }
+}
+
+pub fn run() -> crossterm::Result<()> {
+    enable_raw_mode()?;
+    let result = browse();
+    disable_raw_mode()?;
+    result
+}
+
+pub fn browse() -> crossterm::Result<()> {
+    let (history, mut status) = match load_history() {
+        Ok(history) => (history, None),
+        Err(e) => (Vec::new(), Some(tf("error.load_history", &[("error", &e)]))),
+    };
+    let mut colors: HashMap<u64, colored::Color> = HashMap::new();
+    initialize_colors(&mut colors, campaign::active_theme());
+    let mut selected = history.len().saturating_sub(1);
+
+    loop {
+        frame::invalidate();
+        let mut stdout = stdout();
+        stdout.execute(Clear(ClearType::All))?;
+        stdout.execute(cursor::MoveTo(0, 0))?;
+        println!(" > {}", t("history.title"));
+        println!();
+        if history.is_empty() {
+            println!("   {}", t("history.empty"));
+        }
+        for (i, record) in history.iter().enumerate().rev() {
+            let marker = if i == selected { ">" } else { " " };
+            println!(" {} {}", marker, summary(record));
+        }
+        println!();
+        if let Some(status) = &status {
+            println!(" > {}", status);
+            println!();
+        }
+        println!(" > {}", t("history.hint"));
+
+        if let Event::Key(key_event) = read()? {
+            match key_event.code {
+                KeyCode::Char('e') | KeyCode::Char('E') | KeyCode::Esc => return Ok(()),
+                KeyCode::Up if selected + 1 < history.len() => selected += 1,
+                KeyCode::Down => selected = selected.saturating_sub(1),
+                KeyCode::Enter if !history.is_empty() => {
+                    let record = &history[selected];
+                    render_board(
+                        &record.board,
+                        &colors,
+                        record.score,
+                        read_high_score(),
+                        false,
+                        &[summary(record), t("hint.back").to_string()],
+                    )?;
+                    while !matches!(read()?, Event::Key(_)) {}
+                }
+                KeyCode::Char('p') | KeyCode::Char('P') if !history.is_empty() => {
+                    replay(&history[selected], &colors)?;
+                }
+                KeyCode::Char('g') | KeyCode::Char('G') if !history.is_empty() => {
+                    let record = &history[selected];
+                    graph::show(&summary(record), &record.score_series())?;
+                }
+                KeyCode::Char('c') | KeyCode::Char('C') if !history.is_empty() => {
+                    let record = &history[selected];
+                    let path = PathBuf::from(format!("game-{}-scores.csv", record.finished));
+                    status = Some(match graph::write_csv(&path, &record.score_series()) {
+                        Ok(()) => format!(
+                            "{} {}",
+                            label("label.exported"),
+                            tf("export.done", &[("path", &path.display())])
+                        ),
+                        Err(e) => format!(
+                            "{} {}",
+                            label("label.exported"),
+                            tf("export.failed", &[("error", &e)])
+                        ),
+                    });
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+fn replay(record: &GameRecord, colors: &HashMap<u64, colored::Color>) -> crossterm::Result<()> {
+    let high_score = read_high_score();
+    let mut game_board = record.start.clone();
+    let total = record.moves.len();
+    let speed = if timed(record) {
+        t("history.original_speed")
+    } else {
+        t("history.steady_pace")
+    };
+    for step in 0..=total {
+        if step > 0 {
+            record.moves[step - 1].apply(&mut game_board);
+        }
+        render_board(
+            &game_board,
+            colors,
+            calculate_score(&game_board),
+            high_score,
+            false,
+            &[
+                format!(
+                    "{} {}",
+                    label("label.replay"),
+                    tf(
+                        "history.replay",
+                        &[("move", &step), ("moves", &total), ("speed", &speed)]
+                    )
+                ),
+                t("history.stop").to_string(),
+            ],
+        )?;
+        let delay = match record.moves.get(step) {
+            Some(next) if timed(record) => Duration::from_millis(next.elapsed_ms),
+            _ => REPLAY_STEP,
+        };
+        if poll(delay)? {
+            read()?;
+            return Ok(());
+        }
+    }
+    while !matches!(read()?, Event::Key(_)) {}
+    Ok(())
+}
+
+pub fn timeline(
+    recorder: &mut Recorder,
+    colors: &HashMap<u64, colored::Color>,
+) -> crossterm::Result<Option<Vec<Vec<u64>>>> {
+    let high_score = read_high_score();
+    let mut step = recorder.len();
+    loop {
+        let game_board = recorder.position(step);
+        render_board(
+            &game_board,
+            colors,
+            calculate_score(&game_board),
+            high_score,
+            false,
+            &[
+                format!(
+                    "{} {}",
+                    label("label.timeline"),
+                    tf(
+                        "history.move",
+                        &[("move", &step), ("moves", &recorder.len())]
+                    )
+                ),
+                t("timeline.hint").to_string(),
+                t("timeline.back").to_string(),
+            ],
+        )?;
+        if let Event::Key(key_event) = read()? {
+            match key_event.code {
+                KeyCode::Char('t') | KeyCode::Char('T') | KeyCode::Esc => return Ok(None),
+                KeyCode::Left => step = step.saturating_sub(1),
+                KeyCode::Right => step = (step + 1).min(recorder.len()),
+                KeyCode::Home => step = 0,
+                KeyCode::End => step = recorder.len(),
+                KeyCode::Enter if step == recorder.len() => return Ok(None),
+                KeyCode::Enter => return Ok(Some(recorder.branch(step))),
+                _ => {}
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_2048_game::engine::Spawner;
+
+    type Turn = (Direction, Vec<Vec<u64>>, Vec<Vec<u64>>);
+
+    /// A seeded game on a board roomy enough to outlast the sparkline, always taking the
+    /// first direction that moves.
+    fn game(moves: usize) -> (Vec<Vec<u64>>, Vec<Turn>) {
+        let mut spawner = Spawner::seeded(11);
+        let mut game_board = vec![vec![0; 8]; 8];
+        spawner.spawn(&mut game_board);
+        spawner.spawn(&mut game_board);
+        let start = game_board.clone();
+        let mut turns = Vec::new();
+        while turns.len() < moves {
+            let Some((direction, moved)) = Direction::ALL.into_iter().find_map(|direction| {
+                let mut moved = game_board.clone();
+                apply_move(&mut moved, direction)
+                    .moved
+                    .then_some((direction, moved))
+            }) else {
+                break;
+            };
+            game_board = moved.clone();
+            spawner.spawn(&mut game_board);
+            turns.push((direction, moved, game_board.clone()));
+        }
+        (start, turns)
+    }
+
+    fn recorded(start: &[Vec<u64>], turns: &[Turn]) -> Recorder {
+        let mut recorder = Recorder::new(start, &GameConfig::default(), Combo::default());
+        for (direction, moved, spawned) in turns {
+            recorder.record(*direction, moved, spawned);
+        }
+        recorder
+    }
+
+    #[test]
+    fn varints_round_trip() {
+        let values = [
+            0,
+            1,
+            127,
+            128,
+            16_383,
+            16_384,
+            u64::from(u32::MAX),
+            u64::MAX,
+        ];
+        let mut bytes = Vec::new();
+        for value in values {
+            push_varint(&mut bytes, value);
+        }
+        assert_eq!(bytes[..4], [0, 1, 127, 0x80]);
+        assert_eq!(varints(&bytes).collect::<Vec<_>>(), values);
+    }
+
+    #[test]
+    fn score_samples_stay_short_and_end_on_the_current_score() {
+        let (start, turns) = game(300);
+        assert_eq!(turns.len(), 300);
+        let recorder = recorded(&start, &turns);
+        let samples = recorder.score_samples();
+        assert!(samples.len() <= 2 * SPARKLINE_SAMPLES + 1);
+        assert_eq!(samples.first(), Some(&calculate_score(&start)));
+        assert_eq!(samples.last(), Some(&calculate_score(&turns[299].2)));
+        assert!(samples.windows(2).all(|pair| pair[0] <= pair[1]));
+    }
+
+    #[test]
+    fn branch_matches_a_game_that_stopped_there() {
+        let (start, turns) = game(300);
+        for step in [0, 1, 77, 128, 200, 299, 300] {
+            let mut branched = recorded(&start, &turns);
+            let game_board = branched.branch(step);
+            let stopped = recorded(&start, &turns[..step]);
+            let expected = if step == 0 {
+                &start
+            } else {
+                &turns[step - 1].2
+            };
+            assert_eq!(&game_board, expected, "step {}", step);
+            assert_eq!(branched.len(), step);
+            assert_eq!(branched.combo(), stopped.combo(), "step {}", step);
+            assert_eq!(varints(&branched.thinking).count(), step);
+            assert_eq!(
+                branched.score_samples().last(),
+                stopped.score_samples().last()
+            );
+        }
+    }
+}
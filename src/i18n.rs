@@ -0,0 +1,87 @@
+use crate::config::{self, Language};
+use rust_2048_game::engine::Direction;
+use std::{collections::HashMap, fmt::Display, sync::OnceLock};
+
+const ENGLISH: &str = include_str!("../locales/en.txt");
+const SPANISH: &str = include_str!("../locales/es.txt");
+
+type Catalog = HashMap<&'static str, &'static str>;
+
+static CATALOGS: OnceLock<(Catalog, Catalog)> = OnceLock::new();
+
+/// Reads `key = value` lines, skipping blanks and `#` comments.
+fn parse(text: &'static str) -> Catalog {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| line.split_once('='))
+        .map(|(key, value)| (key.trim(), value.trim()))
+        .collect()
+}
+
+fn catalogs() -> &'static (Catalog, Catalog) {
+    CATALOGS.get_or_init(|| (parse(ENGLISH), parse(SPANISH)))
+}
+
+/// Settles `auto` from the usual locale variables, English when none of them match.
+pub fn resolve(language: Language) -> Language {
+    if language != Language::Auto {
+        return language;
+    }
+    let locale = ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .into_iter()
+        .filter_map(|name| std::env::var(name).ok())
+        .find(|value| !value.is_empty())
+        .unwrap_or_default();
+    if locale.starts_with("es") {
+        Language::Spanish
+    } else {
+        Language::English
+    }
+}
+
+/// The text for `key` in the active language, falling back to English and then to the key.
+pub fn t(key: &'static str) -> &'static str {
+    let (english, spanish) = catalogs();
+    let text = match resolve(config::language()) {
+        Language::Spanish => spanish.get(key),
+        _ => None,
+    };
+    text.or_else(|| english.get(key)).copied().unwrap_or(key)
+}
+
+/// Like `t`, filling each `{name}` in the text with its argument.
+pub fn tf(key: &'static str, args: &[(&str, &dyn Display)]) -> String {
+    let mut text = t(key).to_string();
+    for (name, value) in args {
+        text = text.replace(&format!("{{{}}}", name), &value.to_string());
+    }
+    text
+}
+
+/// A status label padded so the values line up, as in `Current score : 128`.
+pub fn label(key: &'static str) -> String {
+    format!("{:<13} :", t(key))
+}
+
+pub fn direction(direction: Direction) -> &'static str {
+    match direction {
+        Direction::Up => t("direction.up"),
+        Direction::Down => t("direction.down"),
+        Direction::Left => t("direction.left"),
+        Direction::Right => t("direction.right"),
+    }
+}
+
+/// The name a player goes by when none was given, counting from 1.
+pub fn player(number: usize) -> String {
+    tf("player.default", &[("number", &number)])
+}
+
+pub fn language_name(language: Language) -> &'static str {
+    match language {
+        Language::Auto => t("language.auto"),
+        Language::English => "English",
+        Language::Spanish => "Español",
+    }
+}
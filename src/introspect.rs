@@ -0,0 +1,85 @@
+use serde::Serialize;
+
+#[derive(Serialize)]
+pub struct CommandInfo {
+    name: String,
+    about: Option<String>,
+    flags: Vec<FlagInfo>,
+    subcommands: Vec<CommandInfo>,
+}
+
+#[derive(Serialize)]
+struct FlagInfo {
+    name: String,
+    long: Option<String>,
+    short: Option<char>,
+    help: Option<String>,
+    takes_value: bool,
+    required: bool,
+    default: Vec<String>,
+    possible_values: Vec<String>,
+}
+
+pub fn describe(command: &clap::Command) -> CommandInfo {
+    let flags = command
+        .get_arguments()
+        .filter(|arg| !matches!(arg.get_id().as_str(), "help" | "version"))
+        .map(|arg| FlagInfo {
+            name: arg.get_id().to_string(),
+            long: arg.get_long().map(str::to_string),
+            short: arg.get_short(),
+            help: arg.get_help().map(|help| help.to_string()),
+            takes_value: arg.get_action().takes_values(),
+            required: arg.is_required_set(),
+            default: arg
+                .get_default_values()
+                .iter()
+                .map(|value| value.to_string_lossy().into_owned())
+                .collect(),
+            possible_values: if arg.get_action().takes_values() {
+                arg.get_possible_values()
+                    .iter()
+                    .map(|value| value.get_name().to_string())
+                    .collect()
+            } else {
+                Vec::new()
+            },
+        })
+        .collect();
+
+    CommandInfo {
+        name: command.get_name().to_string(),
+        about: command.get_about().map(|about| about.to_string()),
+        flags,
+        subcommands: command
+            .get_subcommands()
+            .filter(|sub| sub.get_name() != "help")
+            .map(describe)
+            .collect(),
+    }
+}
+
+pub fn print_text(info: &CommandInfo, indent: usize) {
+    println!(
+        "{}{}{}",
+        "  ".repeat(indent),
+        info.name,
+        info.about
+            .as_ref()
+            .map(|about| format!(" - {}", about))
+            .unwrap_or_default()
+    );
+    for flag in &info.flags {
+        if let Some(long) = &flag.long {
+            println!(
+                "{}  --{}{}",
+                "  ".repeat(indent),
+                long,
+                if flag.takes_value { " <value>" } else { "" }
+            );
+        }
+    }
+    for sub in &info.subcommands {
+        print_text(sub, indent + 1);
+    }
+}
@@ -0,0 +1,196 @@
+use crate::engine::{apply_move, Direction, MoveResult};
+
+/// A spawn after a move: row, column and the value placed there.
+pub type Spawn = (usize, usize, u64);
+
+/// Packed size of one move: direction and spawn value, then the spawn's row and column.
+const EVENT_BYTES: usize = 3;
+const NO_SPAWN: u8 = 0;
+const ZERO: u8 = 1;
+/// First code for a power of two, `1 << (code - POWER)`.
+const POWER: u8 = 2;
+/// A spawn value that has no code of its own, kept aside in `wide`.
+const WIDE: u8 = 63;
+
+fn direction_bits(direction: Direction) -> u8 {
+    match direction {
+        Direction::Up => 0,
+        Direction::Down => 1,
+        Direction::Left => 2,
+        Direction::Right => 3,
+    }
+}
+
+fn value_code(value: u64) -> u8 {
+    match value {
+        0 => ZERO,
+        value if value.is_power_of_two() && value.trailing_zeros() < u32::from(WIDE - POWER) => {
+            POWER + value.trailing_zeros() as u8
+        }
+        _ => WIDE,
+    }
+}
+
+/// Every move of a game in about three bytes each, with boards rebuilt on demand by
+/// replaying from the nearest checkpoint. Checkpoints are taken every `interval` moves;
+/// once there are more than `max_checkpoints`, every other one is dropped and the
+/// interval doubles, so memory stays flat however long the game runs. Rows and columns
+/// take a byte each, which covers any board up to 256 on a side.
+pub struct Journal {
+    start: Vec<Vec<u64>>,
+    events: Vec<u8>,
+    /// Steps whose spawn value did not fit in the packed code, in step order.
+    wide: Vec<(usize, u64)>,
+    checkpoints: Vec<(usize, Vec<Vec<u64>>)>,
+    interval: usize,
+    max_checkpoints: usize,
+    current: Vec<Vec<u64>>,
+}
+
+impl Journal {
+    /// An `interval` of 0 takes no checkpoints, so every position replays from the start.
+    pub fn new(start: &[Vec<u64>], interval: usize, max_checkpoints: usize) -> Self {
+        Journal {
+            start: start.to_vec(),
+            events: Vec::new(),
+            wide: Vec::new(),
+            checkpoints: Vec::new(),
+            interval,
+            max_checkpoints,
+            current: start.to_vec(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.events.len() / EVENT_BYTES
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    pub fn start(&self) -> &[Vec<u64>] {
+        &self.start
+    }
+
+    /// The board after every move so far.
+    pub fn current(&self) -> &[Vec<u64>] {
+        &self.current
+    }
+
+    pub fn checkpoints(&self) -> usize {
+        self.checkpoints.len()
+    }
+
+    /// Bytes held for moves and checkpoints, leaving out the start and current boards.
+    pub fn memory(&self) -> usize {
+        let cells: usize = self
+            .checkpoints
+            .iter()
+            .map(|(_, board)| board.iter().map(Vec::len).sum::<usize>())
+            .sum();
+        self.events.len()
+            + self.wide.len() * std::mem::size_of::<(usize, u64)>()
+            + cells * std::mem::size_of::<u64>()
+    }
+
+    /// Plays a move on the current board and returns what it merged.
+    pub fn push(&mut self, direction: Direction, spawn: Option<Spawn>) -> MoveResult {
+        let step = self.len();
+        let (code, row, col) = match spawn {
+            None => (NO_SPAWN, 0, 0),
+            Some((row, col, value)) => {
+                let code = value_code(value);
+                if code == WIDE {
+                    self.wide.push((step, value));
+                }
+                (code, row as u8, col as u8)
+            }
+        };
+        self.events
+            .extend_from_slice(&[code << 2 | direction_bits(direction), row, col]);
+        let result = Self::apply(&mut self.current, direction, spawn);
+
+        let taken = step + 1;
+        if self.interval > 0 && taken.is_multiple_of(self.interval) {
+            self.checkpoints.push((taken, self.current.clone()));
+            if self.checkpoints.len() > self.max_checkpoints {
+                self.interval *= 2;
+                let interval = self.interval;
+                self.checkpoints
+                    .retain(|(at, _)| at.is_multiple_of(interval));
+            }
+        }
+        result
+    }
+
+    /// The move played at `step`, counting from 0.
+    pub fn event(&self, step: usize) -> (Direction, Option<Spawn>) {
+        let bytes = &self.events[step * EVENT_BYTES..(step + 1) * EVENT_BYTES];
+        let direction = Direction::ALL
+            .into_iter()
+            .find(|&direction| direction_bits(direction) == bytes[0] & 0b11)
+            .unwrap_or(Direction::Up);
+        let value = match bytes[0] >> 2 {
+            NO_SPAWN => return (direction, None),
+            ZERO => 0,
+            WIDE => self
+                .wide
+                .binary_search_by_key(&step, |&(at, _)| at)
+                .map_or(0, |index| self.wide[index].1),
+            code => 1 << (code - POWER),
+        };
+        let spawn = (usize::from(bytes[1]), usize::from(bytes[2]), value);
+        (direction, Some(spawn))
+    }
+
+    pub fn events(&self) -> impl Iterator<Item = (Direction, Option<Spawn>)> + '_ {
+        (0..self.len()).map(|step| self.event(step))
+    }
+
+    fn apply(board: &mut [Vec<u64>], direction: Direction, spawn: Option<Spawn>) -> MoveResult {
+        let result = apply_move(board, direction);
+        if let Some((i, j, value)) = spawn {
+            board[i][j] = value;
+        }
+        result
+    }
+
+    /// The board after `step` moves, replayed from the closest checkpoint before it.
+    pub fn position(&self, step: usize) -> Vec<Vec<u64>> {
+        let step = step.min(self.len());
+        if step == self.len() {
+            return self.current.clone();
+        }
+        let index = self.checkpoints.partition_point(|(at, _)| *at <= step);
+        let (from, mut board) = match index.checked_sub(1) {
+            Some(index) => self.checkpoints[index].clone(),
+            None => (0, self.start.clone()),
+        };
+        for at in from..step {
+            let (direction, spawn) = self.event(at);
+            Self::apply(&mut board, direction, spawn);
+        }
+        board
+    }
+
+    /// Every position from the start to now, rebuilt one at a time rather than all held.
+    pub fn positions(&self) -> impl Iterator<Item = Vec<Vec<u64>>> + '_ {
+        let mut board = self.start.clone();
+        std::iter::once(board.clone()).chain(self.events().map(move |(direction, spawn)| {
+            Self::apply(&mut board, direction, spawn);
+            board.clone()
+        }))
+    }
+
+    /// Forgets every move after `step`, for undo and for playing on from an older position.
+    pub fn truncate(&mut self, step: usize) {
+        if step >= self.len() {
+            return;
+        }
+        self.current = self.position(step);
+        self.events.truncate(step * EVENT_BYTES);
+        self.wide.retain(|&(at, _)| at < step);
+        self.checkpoints.retain(|&(at, _)| at <= step);
+    }
+}
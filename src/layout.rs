@@ -0,0 +1,58 @@
+use crate::theme::{cell_width, tile_cell};
+use colored::Color;
+use crossterm::terminal;
+use std::collections::HashMap;
+
+const GAP: usize = 8;
+const FALLBACK_WIDTH: usize = 80;
+
+pub struct Panel<'a> {
+    pub title: String,
+    pub board: &'a [Vec<u64>],
+}
+
+fn board_width(board: &[Vec<u64>]) -> usize {
+    board.len() * (cell_width() + 1)
+}
+
+/// Prints boards side by side, wrapping onto more rows when the terminal is too narrow.
+pub fn print_panels(panels: &[Panel], colors: &HashMap<u64, Color>) {
+    let Some(widest) = panels.iter().map(|panel| board_width(panel.board)).max() else {
+        return;
+    };
+    let column = widest + GAP;
+    let terminal_width = terminal::size()
+        .map(|(columns, _)| columns as usize)
+        .unwrap_or(FALLBACK_WIDTH);
+    let fitting = (terminal_width / column).clamp(1, panels.len());
+    let per_row = panels.len().div_ceil(panels.len().div_ceil(fitting));
+
+    for (i, chunk) in panels.chunks(per_row).enumerate() {
+        if i > 0 {
+            println!();
+        }
+        for panel in chunk {
+            print!("{:<column$}", panel.title);
+        }
+        println!();
+        let rows = chunk
+            .iter()
+            .map(|panel| panel.board.len())
+            .max()
+            .unwrap_or(0);
+        for row in 0..rows {
+            for panel in chunk {
+                let cells = panel.board.get(row).map(Vec::as_slice).unwrap_or(&[]);
+                for &val in cells {
+                    print!("{} ", tile_cell(colors, val));
+                }
+                print!(
+                    "{:pad$}",
+                    "",
+                    pad = column - cells.len() * (cell_width() + 1)
+                );
+            }
+            println!();
+        }
+    }
+}
@@ -0,0 +1,18 @@
+pub mod ai;
+pub mod conformance;
+pub mod engine;
+#[cfg(feature = "cdylib")]
+pub mod ffi;
+pub mod game;
+pub mod heuristic;
+pub mod journal;
+pub mod link;
+pub mod mask;
+pub mod mcts;
+pub mod policy;
+pub mod powerup;
+pub mod research;
+pub mod scoring;
+pub mod spawn;
+pub mod tablebase;
+pub mod topology;
@@ -0,0 +1,144 @@
+use crate::engine::OBSTACLE;
+use std::{fmt, str::FromStr};
+
+pub const SCHEME: &str = "2048://";
+const MAX_SIZE: usize = 16;
+/// Tiles are written as one base-36 digit of their exponent, so 2^35 is the largest.
+pub const MAX_TILE: u64 = 1 << 35;
+/// Opening a link replays this many draws at most, far beyond any real game.
+const MAX_DRAWS: u64 = 10_000_000;
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct BoardLink {
+    pub mode: String,
+    pub board: Vec<Vec<u64>>,
+    pub seed: Option<u64>,
+    /// Tiles the seed had already placed, so spawns carry on from the shared position.
+    pub draws: u64,
+}
+
+fn encode_cell(val: u64) -> Option<char> {
+    match val {
+        0 => Some('0'),
+        OBSTACLE => Some('_'),
+        _ if val.is_power_of_two() && (2..=MAX_TILE).contains(&val) => {
+            char::from_digit(val.trailing_zeros(), 36)
+        }
+        _ => None,
+    }
+}
+
+fn decode_cell(c: char) -> Result<u64, String> {
+    match c {
+        '0' => Ok(0),
+        '_' => Ok(OBSTACLE),
+        _ => match c.to_digit(36) {
+            Some(exponent) => Ok(1 << exponent),
+            None => Err(format!("invalid tile '{}'", c)),
+        },
+    }
+}
+
+impl BoardLink {
+    /// A link to `board`, or the first tile a link cannot hold.
+    pub fn new(
+        mode: String,
+        board: Vec<Vec<u64>>,
+        seed: Option<u64>,
+        draws: u64,
+    ) -> Result<Self, u64> {
+        if let Some(&tile) = board
+            .iter()
+            .flatten()
+            .find(|&&val| encode_cell(val).is_none())
+        {
+            return Err(tile);
+        }
+        Ok(BoardLink {
+            mode,
+            board,
+            seed,
+            draws,
+        })
+    }
+}
+
+impl fmt::Display for BoardLink {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let rows: Vec<String> = self
+            .board
+            .iter()
+            // `new` rejects tiles without a digit; '!' keeps a hand-built link from opening.
+            .map(|row| {
+                row.iter()
+                    .map(|&val| encode_cell(val).unwrap_or('!'))
+                    .collect()
+            })
+            .collect();
+        write!(f, "{}{}/{}", SCHEME, self.mode, rows.join("."))?;
+        if let Some(seed) = self.seed {
+            write!(f, "?seed={}", seed)?;
+            if self.draws > 0 {
+                write!(f, "&draws={}", self.draws)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for BoardLink {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let rest = text
+            .strip_prefix(SCHEME)
+            .ok_or_else(|| format!("link must start with {}", SCHEME))?;
+        let (path, query) = rest.split_once('?').unwrap_or((rest, ""));
+        let (mode, cells) = path.split_once('/').ok_or("link is missing the board")?;
+        if mode.is_empty() {
+            return Err("link is missing the mode".to_string());
+        }
+
+        let board = cells
+            .split('.')
+            .map(|row| row.chars().map(decode_cell).collect())
+            .collect::<Result<Vec<Vec<u64>>, String>>()?;
+        let size = board.len();
+        if !(2..=MAX_SIZE).contains(&size) || board.iter().any(|row| row.len() != size) {
+            return Err("board must be square, between 2 and 16 cells wide".to_string());
+        }
+
+        let mut seed = None;
+        let mut draws = 0;
+        for pair in query.split('&').filter(|pair| !pair.is_empty()) {
+            match pair.split_once('=') {
+                Some(("seed", value)) => {
+                    seed = Some(
+                        value
+                            .parse()
+                            .map_err(|_| format!("invalid seed '{}'", value))?,
+                    )
+                }
+                Some(("draws", value)) => {
+                    draws = value
+                        .parse()
+                        .map_err(|_| format!("invalid draws '{}'", value))?
+                }
+                _ => return Err(format!("unknown link parameter '{}'", pair)),
+            }
+        }
+
+        if seed.is_none() && draws > 0 {
+            return Err("draws needs a seed".to_string());
+        }
+        if draws > MAX_DRAWS {
+            return Err(format!("draws may be at most {}", MAX_DRAWS));
+        }
+        Ok(BoardLink {
+            mode: mode.to_string(),
+            board,
+            seed,
+            draws,
+        })
+    }
+}
@@ -0,0 +1,135 @@
+use crate::i18n::{self, label, tf};
+use rust_2048_game::engine::{legal_moves, Direction, MoveResult};
+use rust_2048_game::game::GameObserver;
+use std::{
+    collections::VecDeque,
+    fmt,
+    fs::OpenOptions,
+    path::Path,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+const DRAWS_SHOWN: usize = 3;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static DEBUG: AtomicBool = AtomicBool::new(false);
+
+pub fn init(path: &Path) -> std::io::Result<()> {
+    let file = OpenOptions::new().create(true).append(true).open(path)?;
+    tracing_subscriber::fmt()
+        .with_writer(std::sync::Mutex::new(file))
+        .with_ansi(false)
+        .with_max_level(tracing::Level::DEBUG)
+        .init();
+    ENABLED.store(true, Ordering::Relaxed);
+    tracing::info!(version = env!("CARGO_PKG_VERSION"), "log opened");
+    Ok(())
+}
+
+pub fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+pub fn enable_debug() {
+    DEBUG.store(true, Ordering::Relaxed);
+}
+
+pub fn debug() -> bool {
+    DEBUG.load(Ordering::Relaxed)
+}
+
+pub fn error(message: impl fmt::Display) {
+    tracing::error!("{}", message);
+    eprintln!(" > {}", message);
+}
+
+pub fn report(messages: &mut Vec<String>, message: impl fmt::Display) {
+    tracing::error!("{}", message);
+    let line = format!("{} {}", label("label.error"), message);
+    if !messages.contains(&line) {
+        messages.push(line);
+    }
+}
+
+pub struct TraceObserver {
+    mode: String,
+}
+
+impl TraceObserver {
+    pub fn new(mode: &str) -> Self {
+        tracing::info!(mode, "game started");
+        TraceObserver {
+            mode: mode.to_string(),
+        }
+    }
+}
+
+impl GameObserver for TraceObserver {
+    fn on_move(&mut self, direction: Direction, result: &MoveResult) {
+        let gained: u64 = result.merges.iter().map(|merge| merge.value).sum();
+        tracing::debug!(?direction, merges = result.merges.len(), gained, "move");
+    }
+
+    fn on_spawn(&mut self, position: (usize, usize), value: u64) {
+        tracing::debug!(?position, value, "spawn");
+    }
+
+    fn on_game_over(&mut self, score: u64) {
+        tracing::info!(mode = %self.mode, score, "game over");
+    }
+}
+
+pub struct Draws(VecDeque<(u64, (usize, usize))>);
+
+impl Draws {
+    pub fn new() -> Self {
+        Draws(VecDeque::with_capacity(DRAWS_SHOWN))
+    }
+
+    pub fn record(&mut self, value: u64, position: (usize, usize)) {
+        if self.0.len() == DRAWS_SHOWN {
+            self.0.pop_front();
+        }
+        self.0.push_back((value, position));
+    }
+}
+
+pub fn overlay(messages: &mut Vec<String>, game_board: &[Vec<u64>], draws: &Draws) {
+    if !debug() {
+        return;
+    }
+    let empty = game_board
+        .iter()
+        .flatten()
+        .filter(|&&cell| cell == 0)
+        .count();
+    let legal: Vec<String> = legal_moves(game_board)
+        .iter()
+        .map(|&direction| i18n::direction(direction).to_string())
+        .collect();
+    let draws: Vec<String> = draws
+        .0
+        .iter()
+        .rev()
+        .map(|(value, (i, j))| {
+            tf(
+                "debug.spawn",
+                &[("value", value), ("row", &(i + 1)), ("col", &(j + 1))],
+            )
+        })
+        .collect();
+    let prefix = label("label.debug");
+    messages.retain(|message| !message.starts_with(&prefix));
+    messages.push(format!(
+        "{} {}",
+        prefix,
+        tf(
+            "debug.overlay",
+            &[
+                ("empty", &empty),
+                ("legal", &legal.join(" ")),
+                ("spawns", &draws.join(", "))
+            ]
+        )
+    ));
+}
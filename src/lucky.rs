@@ -0,0 +1,224 @@
+use crate::accessibility;
+use crate::difficulty::Difficulty;
+use crate::error::GameError;
+use crate::frame;
+use crate::help;
+use crate::i18n::{label, t, tf};
+use crate::scores::{self, ScoreKey};
+use crate::theme::{self, initialize_colors};
+use crate::{campaign, key_direction, logging, profile, vault};
+use colored::{Color, Colorize};
+use crossterm::{
+    event::{read, Event, KeyCode},
+    terminal::{disable_raw_mode, enable_raw_mode},
+};
+use rand::thread_rng;
+use rust_2048_game::engine::OBSTACLE;
+use rust_2048_game::powerup::{self, Activation, Special, Tile, EMPTY};
+use rust_2048_game::scoring::Ruleset;
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, fs};
+
+const SAVE_FILE: &str = "lucky_state.json";
+const SIZE: usize = 4;
+
+#[derive(Serialize, Deserialize)]
+struct LuckyState {
+    board: Vec<Vec<Tile>>,
+    /// Points on tiles cleared by bombs, kept on the score.
+    bonus: u64,
+}
+
+impl LuckyState {
+    fn score(&self) -> u64 {
+        powerup::score(&self.board) + self.bonus
+    }
+}
+
+/// The saved game when it can still be played on.
+fn load() -> Result<Option<LuckyState>, GameError> {
+    let state: Option<LuckyState> = vault::load(&profile::path(SAVE_FILE))?;
+    Ok(state.filter(|state| powerup::can_move(&state.board)))
+}
+
+fn save(state: &LuckyState) -> Result<(), GameError> {
+    vault::write(&profile::path(SAVE_FILE), &serde_json::to_string(state)?)
+}
+
+/// Draws `special` right-aligned in `width` columns, no padding when `width` is 0.
+fn special_cell(special: Special, width: usize) -> String {
+    let (glyph, color) = match special {
+        Special::Wildcard => ("*", Color::Magenta),
+        Special::Bomb => ("B", Color::Red),
+        Special::Doubler => ("x2", Color::Yellow),
+    };
+    let text = format!("{:>width$}", glyph, width = width);
+    if accessibility::colorless() {
+        text
+    } else {
+        text.color(color).bold().to_string()
+    }
+}
+
+fn appeared(special: Special) -> &'static str {
+    match special {
+        Special::Wildcard => t("lucky.appeared.wildcard"),
+        Special::Bomb => t("lucky.appeared.bomb"),
+        Special::Doubler => t("lucky.appeared.doubler"),
+    }
+}
+
+fn describe(activation: &Activation) -> String {
+    let (row, col) = activation.position;
+    let at = tf("lucky.at", &[("row", &(row + 1)), ("col", &(col + 1))]);
+    let key = match activation.special {
+        Special::Bomb => "lucky.bomb",
+        Special::Wildcard => "lucky.wildcard",
+        Special::Doubler => "lucky.doubler",
+    };
+    tf(
+        key,
+        &[
+            ("at", &at),
+            ("value", &activation.value),
+            ("points", &activation.cleared),
+        ],
+    )
+}
+
+fn render(
+    state: &LuckyState,
+    best: u64,
+    colors: &HashMap<u64, Color>,
+    messages: &[String],
+) -> crossterm::Result<()> {
+    let mut lines = Vec::new();
+    for row in &state.board {
+        let mut line = String::new();
+        for &tile in row {
+            let cell = match tile {
+                Tile::Number(OBSTACLE) => format!("{:>width$}", "##", width = theme::cell_width())
+                    .dimmed()
+                    .to_string(),
+                Tile::Number(value) => theme::tile_cell(colors, value).to_string(),
+                Tile::Special(special) => special_cell(special, theme::cell_width()),
+            };
+            line.push_str(&cell);
+            line.push(' ');
+        }
+        lines.push(line);
+    }
+    lines.push(format!(
+        " > {} {}",
+        label("label.current_score"),
+        state.score()
+    ));
+    lines.push(format!(" > {} {}", label("label.high_score"), best));
+    lines.push(format!(
+        " > {} {}",
+        label("label.specials"),
+        tf(
+            "lucky.specials",
+            &[
+                ("wildcard", &special_cell(Special::Wildcard, 0)),
+                ("bomb", &special_cell(Special::Bomb, 0)),
+                ("doubler", &special_cell(Special::Doubler, 0)),
+            ]
+        )
+    ));
+    lines.push(String::new());
+    for message in messages {
+        lines.push(format!(" > {}", message));
+    }
+    if !messages.is_empty() {
+        lines.push(String::new());
+    }
+    lines.push(format!(" > {}", t("hint.help")));
+    lines.push(format!(" > {}", t("hint.save_exit")));
+    let mut text = lines.join("\n");
+    text.push('\n');
+    frame::present(&text)
+}
+
+/// Classic 2048 where a few spawns are special tiles that go off when they merge.
+pub fn run(lucky_chance: f64) -> crossterm::Result<()> {
+    let mut colors: HashMap<u64, Color> = HashMap::new();
+    initialize_colors(&mut colors, campaign::active_theme());
+    let score_key = ScoreKey::new(SIZE, Difficulty::Normal, Ruleset::Lucky);
+    let mut best = scores::best(score_key);
+    let mut rng = thread_rng();
+    let mut messages = Vec::new();
+
+    let saved = match load() {
+        Ok(saved) => saved,
+        Err(e) => {
+            logging::error(tf("error.load_lucky", &[("error", &e)]));
+            return Ok(());
+        }
+    };
+    let mut state = saved.unwrap_or_else(|| {
+        let mut board = vec![vec![EMPTY; SIZE]; SIZE];
+        for _ in 0..2 {
+            powerup::spawn(&mut board, &mut rng, 0.0);
+        }
+        LuckyState { board, bonus: 0 }
+    });
+
+    enable_raw_mode()?;
+    let over = loop {
+        render(&state, best.max(state.score()), &colors, &messages)?;
+        if !powerup::can_move(&state.board) {
+            break true;
+        }
+        let Event::Key(key_event) = read()? else {
+            continue;
+        };
+        let direction = match key_event.code {
+            KeyCode::Char('e') | KeyCode::Char('E') => break false,
+            KeyCode::Char('?') | KeyCode::F(1) => {
+                let keys = [
+                    ("*", t("lucky.key.wildcard")),
+                    ("B", t("lucky.key.bomb")),
+                    ("x2", t("lucky.key.doubler")),
+                ];
+                help::show(t("mode.lucky"), t("lucky.objective"), &keys)?;
+                continue;
+            }
+            code => match key_direction(code) {
+                Some(direction) => direction,
+                None => continue,
+            },
+        };
+        let result = powerup::apply_move(&mut state.board, direction);
+        if !result.moved {
+            continue;
+        }
+        state.bonus += result.cleared;
+        messages = result
+            .activations
+            .iter()
+            .map(|activation| format!("{} {}", label("label.lucky"), describe(activation)))
+            .collect();
+        if let Some(Tile::Special(special)) =
+            powerup::spawn(&mut state.board, &mut rng, lucky_chance)
+        {
+            messages.push(format!("{} {}", label("label.lucky"), appeared(special)));
+        }
+    };
+    disable_raw_mode()?;
+
+    let score = state.score();
+    if score > best {
+        best = score;
+        if let Err(e) = scores::record(score_key, best) {
+            logging::error(tf("error.save_high_score", &[("error", &e)]));
+        }
+    }
+    if over {
+        println!(" >> {} <<", t("game.over"));
+        let _ = fs::remove_file(profile::path(SAVE_FILE));
+    } else if let Err(e) = save(&state) {
+        logging::error(tf("error.save_lucky", &[("error", &e)]));
+    }
+    Ok(())
+}
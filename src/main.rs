@@ -1,354 +1,2123 @@
+use clap::{CommandFactory, Parser, Subcommand};
 use colored::*;
+use config::{CornerLock, SpawnBias};
 use crossterm::{
-    cursor,
-    event::{read, Event, KeyCode},
-    terminal::{disable_raw_mode, enable_raw_mode, Clear, ClearType},
-    ExecutableCommand,
+    event::{poll, read, Event, KeyCode},
+    terminal::{disable_raw_mode, enable_raw_mode},
 };
-use rand::{seq::IteratorRandom, thread_rng, Rng};
+use difficulty::Difficulty;
+use error::GameError;
+use goals::Goals;
+use rand::{thread_rng, Rng};
+use rust_2048_game::ai::canonical_hash;
+use rust_2048_game::engine::{
+    apply_move, calculate_score, leaves_corner, legal_moves, max_tile, spawn_random_tile,
+    Direction, MoveResult, Spawner, OBSTACLE,
+};
+use rust_2048_game::game::GameObserver;
+use rust_2048_game::scoring::{Combo, Ruleset};
+use rust_2048_game::{ai, heuristic, link::BoardLink, mask::Mask, mcts::MctsConfig, powerup};
+use scores::ScoreKey;
 use serde::{Deserialize, Serialize};
+use status::StatusLine;
 use std::{
-    collections::HashMap,
+    collections::{hash_map::Entry, HashMap},
     fs::{self, File},
-    io::{self, stdout, Write},
+    io::{self, stdout, IsTerminal, Write},
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime, UNIX_EPOCH},
 };
+use tick::Ticks;
+
+mod accessibility;
+mod adversary;
+mod analysis;
+mod bench;
+mod bot;
+mod broadcast;
+mod bundle;
+mod campaign;
+mod challenge;
+mod config;
+mod coop;
+mod cube;
+mod difficulty;
+mod error;
+mod exhibition;
+mod export;
+mod frame;
+mod ghost;
+mod goals;
+mod graph;
+mod gravity;
+mod help;
+mod hex;
+mod history;
+mod i18n;
+mod introspect;
+mod layout;
+mod logging;
+mod lucky;
+mod multi;
+#[cfg(feature = "sound")]
+mod music;
+#[cfg(feature = "online")]
+mod online;
+mod output;
+mod popup;
+mod practice;
+#[cfg(feature = "discord")]
+mod presence;
+mod profile;
+mod puzzle;
+mod reconcile;
+mod relay;
+mod reminder;
+mod repeat;
+mod resume;
+mod scores;
+#[cfg(test)]
+mod scratch;
+mod script;
+mod settings;
+mod signing;
+mod sound;
+mod splitscreen;
+mod stats;
+mod status;
+mod sync;
+mod theme;
+mod tick;
+mod title;
+mod tune;
+mod tutorial;
+mod update;
+mod vault;
+mod verify;
+#[cfg(feature = "web")]
+mod web;
+mod zen;
+
+const NET_FRIENDLY_FPS: u32 = 1;
+const PACE_INTERVAL: Duration = Duration::from_secs(1);
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Tick {
+    Frame,
+    Pace,
+    Autosave,
+}
+
+#[derive(Parser)]
+#[command(about = "2048 in the terminal")]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+    #[arg(long, global = true)]
+    profile: Option<String>,
+    /// Stream board states and events as JSON lines to a Unix socket path or tcp://host:port
+    #[arg(long, global = true)]
+    broadcast: Option<String>,
+    /// Serve a live view of the board to browsers at this address, e.g. 127.0.0.1:8080
+    #[cfg(feature = "web")]
+    #[arg(long, global = true)]
+    serve: Option<String>,
+    /// Check GitHub for a newer release on startup (cached for a day)
+    #[arg(long)]
+    check_updates: bool,
+    /// Start new classic games at this difficulty instead of asking
+    #[arg(long, value_enum)]
+    difficulty: Option<difficulty::Difficulty>,
+    /// Start new classic games with combo scoring: consecutive merging moves multiply merge points
+    #[arg(long)]
+    combo: bool,
+    /// Start new classic games on the shape in this mask file: '.' cells are played on and '#'
+    /// cells are walls, or a JSON grid of 1s and 0s
+    #[arg(long)]
+    mask: Option<PathBuf>,
+    /// Play new classic games towards these tiles in turn, e.g. 512,1024,2048,4096
+    #[arg(long, value_delimiter = ',')]
+    goal: Vec<u64>,
+    /// Post the final score of classic games to the global leaderboard (online feature)
+    #[arg(long)]
+    submit: bool,
+    /// Watch the bot play a fresh game
+    #[arg(long)]
+    bot: bool,
+    /// Learn the rules and basic strategy in five short guided lessons
+    #[arg(long)]
+    tutorial: bool,
+    /// Search the bot uses to pick its moves
+    #[arg(long, value_enum, default_value_t = bot::BotStrategy::Expectimax)]
+    bot_strategy: bot::BotStrategy,
+    /// Random playouts the MCTS bot runs per move at most
+    #[arg(long, global = true, default_value_t = 200)]
+    rollouts: u32,
+    /// Worker threads for bench, tune and verify, all cores by default
+    #[arg(long, global = true)]
+    jobs: Option<usize>,
+    /// Time the MCTS bot may think per move in milliseconds, 0 for no limit
+    #[arg(long, global = true, default_value_t = 200)]
+    move_budget_ms: u64,
+    /// Play moves from a file of L/U/R/D letters ("-" for stdin) and print each state as JSON
+    #[arg(long)]
+    input_script: Option<PathBuf>,
+    /// Print each turn as colored text or as one JSON object per line
+    #[arg(long, global = true, value_enum, default_value_t = output::OutputFormat::Text)]
+    output: output::OutputFormat,
+    /// Print without colors (NO_COLOR is honored too)
+    #[arg(long, global = true)]
+    no_color: bool,
+    /// Replace any non-ASCII text with '?'
+    #[arg(long, global = true)]
+    ascii: bool,
+    /// Describe the board and each move as sentences for screen readers
+    #[arg(long, global = true)]
+    accessible: bool,
+    /// Switch to a color theme, including the colorblind-safe ones (kept for later games)
+    #[arg(long, global = true, value_enum)]
+    theme: Option<theme::Theme>,
+    /// Render for slow remote terminals: no colors, changed lines only, fewer redraws
+    #[arg(long, global = true)]
+    net_friendly: bool,
+    /// Like --net-friendly, but redraw only the changed cells and never clear the screen, for
+    /// high-latency SSH sessions and serial consoles
+    #[arg(long, global = true)]
+    slow_terminal: bool,
+    /// Append a trace of moves, spawns, saves and errors to this file
+    #[arg(long, global = true)]
+    log_file: Option<PathBuf>,
+    /// Show empty cells, legal moves and the last spawns under the board
+    #[arg(long, global = true)]
+    debug: bool,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Play out a saved position with the expectimax engine
+    Solve {
+        #[arg(long)]
+        input: Option<PathBuf>,
+        #[arg(long, default_value_t = 3)]
+        depth: u32,
+        #[arg(long, default_value_t = 20)]
+        moves: usize,
+        #[arg(long)]
+        log: Option<PathBuf>,
+    },
+    /// Print board metrics and move evaluations for a saved position
+    Analyze {
+        #[arg(long)]
+        input: Option<PathBuf>,
+        #[arg(long, default_value_t = 3)]
+        depth: u32,
+        #[arg(long)]
+        tablebase: Option<PathBuf>,
+    },
+    /// Solve every position of a small board exactly and save the table
+    Tablebase {
+        #[arg(long, default_value_t = 3, value_parser = clap::value_parser!(u8).range(2..=3))]
+        size: u8,
+        #[arg(long)]
+        file: Option<PathBuf>,
+    },
+    /// Play seeded games with each bot strategy and compare the results
+    Bench {
+        #[arg(long, default_value_t = 10)]
+        games: u32,
+        #[arg(long, default_value_t = 1)]
+        seed: u64,
+    },
+    /// Check the move engine against a slow reference on random seeded positions
+    Verify {
+        #[arg(long, default_value_t = 1_000_000)]
+        positions: u64,
+        #[arg(long, default_value_t = 1)]
+        seed: u64,
+        /// Only square boards of this size, any shape up to 6x6 by default
+        #[arg(long)]
+        size: Option<usize>,
+    },
+    /// Sweep the expectimax heuristic weights over seeded games and write the results as CSV
+    Tune {
+        /// Empty cell bonus as VALUE or START:END:STEP
+        #[arg(long, default_value = "2.0:3.5:0.5")]
+        empty: tune::Sweep,
+        /// Monotonicity weight as VALUE or START:END:STEP
+        #[arg(long, default_value = "3:5:1")]
+        monotonicity: tune::Sweep,
+        /// Bonus for the largest tile sitting in a corner as VALUE or START:END:STEP
+        #[arg(long, default_value = "0:4:2")]
+        corner: tune::Sweep,
+        #[arg(long, default_value_t = 10)]
+        games: u32,
+        #[arg(long, default_value_t = 1)]
+        seed: u64,
+        #[arg(long, default_value_t = 2)]
+        depth: u32,
+        #[arg(long, default_value = "tune.csv")]
+        csv: PathBuf,
+    },
+    /// Play the next level of the campaign
+    Campaign {
+        #[arg(long)]
+        level: Option<usize>,
+    },
+    /// Reach a target tile within a fixed move budget
+    Challenge {
+        #[arg(long)]
+        number: Option<usize>,
+        #[arg(long, conflicts_with = "number")]
+        daily: bool,
+    },
+    /// Solve the dirty dozen: fixed start positions with a target tile and a move limit
+    Puzzle {
+        #[arg(long)]
+        number: Option<usize>,
+    },
+    /// Send a desktop reminder when today's daily challenge is still open
+    Reminders {
+        #[arg(long)]
+        watch: bool,
+    },
+    /// Practice moving to a steady metronome beat
+    Practice {
+        #[arg(long, default_value_t = 90)]
+        bpm: u32,
+    },
+    /// Play without a game over, full boards are relieved instead
+    Zen {
+        #[arg(long, value_enum, default_value_t = zen::Relief::Clear)]
+        relief: zen::Relief,
+    },
+    /// Mirror saves, stats and config with the folder set under [sync] in config.toml
+    Sync,
+    /// Pack saves, stats, history and config into one tar.gz to move to another machine
+    ExportData {
+        #[arg(default_value = "2048-data.tar.gz")]
+        file: PathBuf,
+    },
+    /// Unpack a bundle from export-data, keeping backups of the files it replaces
+    ImportData { file: PathBuf },
+    /// Survive an opponent that places every tile where it hurts most
+    Adversary {
+        #[arg(long, default_value_t = 2, value_parser = clap::value_parser!(u32).range(1..=adversary::MAX_DEPTH as i64))]
+        depth: u32,
+    },
+    /// Play with rare lucky tiles: wildcards, bombs and doublers that go off when they merge
+    Lucky {
+        #[arg(long, default_value_t = powerup::LUCKY_CHANCE)]
+        chance: f64,
+    },
+    /// Watch two bots play side by side on the same seeded spawns
+    Exhibition {
+        #[arg(long, value_enum, default_value_t = bot::BotStrategy::Expectimax)]
+        left: bot::BotStrategy,
+        #[arg(long, value_enum, default_value_t = bot::BotStrategy::Mcts)]
+        right: bot::BotStrategy,
+        #[arg(long)]
+        seed: Option<u64>,
+    },
+    /// Take turns with other players on one shared puzzle
+    Relay {
+        /// Player names, two numbered players when left out
+        #[arg(long, value_delimiter = ',')]
+        players: Vec<String>,
+        #[arg(long, default_value_t = 10)]
+        turn_moves: u32,
+        #[arg(long, default_value_t = 300)]
+        budget: u32,
+        #[arg(long, default_value_t = 512)]
+        goal: u64,
+    },
+    /// Share one board with other players, taking one move each, here or over the network
+    Coop {
+        /// Player names, two numbered players when left out; with --host or --join only
+        /// the first is used, for this seat
+        #[arg(long, value_delimiter = ',')]
+        players: Vec<String>,
+        /// Wait for one other player to join at this address, e.g. 0.0.0.0:7048
+        #[arg(long, conflicts_with = "join")]
+        host: Option<String>,
+        /// Join a game hosted at this address
+        #[arg(long)]
+        join: Option<String>,
+    },
+    /// Play head-to-head on two boards with one keyboard
+    Splitscreen,
+    /// Race a recorded game that replays beside you at its original speed
+    Ghost {
+        /// Race this game from the local leaderboard, 1 being the best
+        #[arg(long, default_value_t = 1)]
+        rank: usize,
+        /// Race a game record saved as JSON, such as one shared by another player
+        #[arg(long, conflicts_with = "rank")]
+        file: Option<PathBuf>,
+    },
+    /// Play 2 or 4 boards at once, every key moves all of them
+    Multi {
+        #[arg(long, default_value_t = 2, value_parser = parse_board_count)]
+        boards: usize,
+    },
+    /// Play on a hexagonal board with six directions
+    Hex {
+        #[arg(long, default_value_t = 2)]
+        radius: i32,
+    },
+    /// Play on a 4x4x4 cube shown as four layers side by side
+    Cube,
+    /// Play with tiles falling toward one side after every move
+    Gravity {
+        #[arg(long, default_value = "down")]
+        direction: Direction,
+    },
+    /// Browse finished games, view their final boards and replay them
+    History,
+    /// Print the best finished games, or the global top 100 with --global (online feature)
+    Leaderboard {
+        #[arg(long)]
+        global: bool,
+    },
+    /// Save a saved position as text, ANSI text, SVG or PNG
+    Export {
+        file: PathBuf,
+        #[arg(long)]
+        input: Option<PathBuf>,
+        #[arg(long, value_enum)]
+        format: Option<export::ExportFormat>,
+    },
+    /// Open a shared 2048:// link and play from that position
+    Open { uri: String },
+    /// Print a shell completion script
+    Completions {
+        #[arg(value_enum)]
+        shell: clap_complete::Shell,
+    },
+    /// List subcommands and flags
+    Commands {
+        #[arg(long)]
+        json: bool,
+    },
+}
 
-#[derive(Serialize, Deserialize)]
+fn parse_board_count(value: &str) -> Result<usize, String> {
+    match value.parse() {
+        Ok(count @ (2 | 4)) => Ok(count),
+        _ => Err("play either 2 or 4 boards".to_string()),
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
 struct GameState {
-    game_board: Vec<Vec<u32>>,
-    current_score: u32,
-    high_score: u32,
+    game_board: Vec<Vec<u64>>,
+    current_score: u64,
+    #[serde(default)]
+    difficulty: Difficulty,
+    #[serde(default)]
+    ruleset: Ruleset,
+    #[serde(default)]
+    combo: Combo,
+    #[serde(default)]
+    assisted: bool,
+    #[serde(default)]
+    continued: bool,
+    #[serde(default)]
+    goals: Goals,
+    #[serde(default)]
+    size: usize,
+    #[serde(default)]
+    saved_at: u64,
 }
 
-fn save_game_state(state: &GameState) -> Result<(), Box<dyn std::error::Error>> {
-    let serialized = serde_json::to_string(state)?;
-    fs::write("game_state.json", serialized)?;
-    Ok(())
+fn save_game_state(state: &GameState) -> Result<(), GameError> {
+    let stamped = GameState {
+        size: state.game_board.len(),
+        saved_at: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs())
+            .unwrap_or(0),
+        ..state.clone()
+    };
+    let serialized = serde_json::to_string(&stamped)?;
+    vault::write(&profile::path("game_state.json"), &serialized)
 }
 
-fn load_game_state() -> Option<GameState> {
-    let data = fs::read_to_string("game_state.json").ok()?;
-    serde_json::from_str(&data).ok()
+fn load_mask(path: &Path) -> Result<Mask, GameError> {
+    fs::read_to_string(path)?
+        .parse()
+        .map_err(|e| GameError::Config(format!("{}: {}", path.display(), e)))
+}
+
+fn load_game_state() -> Result<Option<GameState>, GameError> {
+    vault::load(&profile::path("game_state.json"))
 }
 
 fn main() -> crossterm::Result<()> {
-    enable_raw_mode()?;
-    let mut colors: HashMap<u32, Color> = HashMap::new();
-    let mut state = load_game_state().unwrap_or_else(|| GameState {
-        game_board: vec![vec![0; 4]; 4],
-        current_score: 0,
-        high_score: read_high_score(),
+    let cli = Cli::parse();
+    output::set_format(cli.output);
+    if cli.slow_terminal {
+        frame::enable_slow_terminal();
+    } else if cli.net_friendly {
+        frame::enable_net_friendly();
+    }
+    if let Some(path) = &cli.log_file {
+        if let Err(e) = logging::init(path) {
+            logging::error(i18n::tf(
+                "error.log_file",
+                &[("path", &path.display()), ("error", &e)],
+            ));
+        }
+    }
+    if cli.debug {
+        logging::enable_debug();
+    }
+    accessibility::configure(cli.no_color, cli.ascii, cli.accessible);
+    let picks_profile = cli.input_script.is_none()
+        && io::stdin().is_terminal()
+        && !matches!(
+            cli.command,
+            Some(Command::Completions { .. }) | Some(Command::Commands { .. })
+        );
+    let mut chosen = cli.profile;
+    if chosen.is_none() && picks_profile {
+        let names = profile::list();
+        if !names.is_empty() {
+            match profile::pick(&names)? {
+                Some(name) => chosen = name,
+                None => return Ok(()),
+            }
+        }
+    }
+    if let Err(e) = profile::activate(chosen) {
+        logging::error(i18n::tf("error.load_profile", &[("error", &e)]));
+        std::process::exit(1);
+    }
+    if let Err(e) = vault::unlock(&config::load_config().saves) {
+        logging::error(i18n::tf("error.unlock", &[("error", &e)]));
+        std::process::exit(1);
+    }
+    let syncs = !matches!(
+        cli.command,
+        Some(Command::Completions { .. }) | Some(Command::Commands { .. }) | Some(Command::Sync)
+    );
+    if syncs {
+        sync::automatic();
+    }
+    if let Some(theme) = cli.theme {
+        if !campaign::select_theme(theme) {
+            std::process::exit(1);
+        }
+    }
+    if let Some(target) = &cli.broadcast {
+        if let Err(e) = broadcast::start(target) {
+            logging::error(i18n::tf(
+                "error.broadcast",
+                &[("target", &target), ("error", &e)],
+            ));
+            std::process::exit(1);
+        }
+    }
+    #[cfg(feature = "web")]
+    if let Some(address) = &cli.serve {
+        if let Err(e) = web::start(address) {
+            logging::error(i18n::tf(
+                "error.web",
+                &[("address", &address), ("error", &e)],
+            ));
+            std::process::exit(1);
+        }
+    }
+    if let Some(jobs) = cli.jobs {
+        if let Err(e) = rayon::ThreadPoolBuilder::new()
+            .num_threads(jobs)
+            .build_global()
+        {
+            logging::error(i18n::tf(
+                "error.threads",
+                &[("count", &jobs), ("error", &e)],
+            ));
+        }
+    }
+    let mcts_config = MctsConfig {
+        rollouts: cli.rollouts.max(1),
+        budget: (cli.move_budget_ms > 0).then(|| Duration::from_millis(cli.move_budget_ms)),
+    };
+    let result = match cli.command {
+        Some(Command::Solve {
+            input,
+            depth,
+            moves,
+            log,
+        }) => {
+            let input = input.unwrap_or_else(|| profile::path("game_state.json"));
+            if let Err(e) = solve(&input, depth, moves, log.as_deref()) {
+                logging::error(i18n::tf("error.solve", &[("error", &e)]));
+                std::process::exit(1);
+            }
+            Ok(())
+        }
+        Some(Command::Analyze {
+            input,
+            depth,
+            tablebase,
+        }) => {
+            let input = input.unwrap_or_else(|| profile::path("game_state.json"));
+            if let Err(e) = analysis::run(&input, depth, tablebase.as_deref()) {
+                logging::error(i18n::tf("error.analyze", &[("error", &e)]));
+                std::process::exit(1);
+            }
+            Ok(())
+        }
+        Some(Command::Tablebase { size, file }) => {
+            let file = file.unwrap_or_else(|| analysis::default_tablebase(size as usize));
+            if let Err(e) = analysis::generate_tablebase(size as usize, &file) {
+                logging::error(i18n::tf("error.tablebase", &[("error", &e)]));
+                std::process::exit(1);
+            }
+            Ok(())
+        }
+        Some(Command::Bench { games, seed }) => {
+            bench::run(games, seed, mcts_config);
+            Ok(())
+        }
+        Some(Command::Verify {
+            positions,
+            seed,
+            size,
+        }) => {
+            if !verify::run(positions, seed, size) {
+                std::process::exit(1);
+            }
+            Ok(())
+        }
+        Some(Command::Tune {
+            empty,
+            monotonicity,
+            corner,
+            games,
+            seed,
+            depth,
+            csv,
+        }) => {
+            if let Err(e) = tune::run(empty, monotonicity, corner, games, seed, depth, &csv) {
+                logging::error(i18n::tf("error.tune", &[("error", &e)]));
+                std::process::exit(1);
+            }
+            Ok(())
+        }
+        Some(Command::Campaign { level }) => campaign::run(level),
+        Some(Command::Challenge { number, daily }) => challenge::run(number, daily),
+        Some(Command::Puzzle { number }) => puzzle::run(number),
+        Some(Command::Reminders { watch }) => {
+            reminder::run(watch);
+            Ok(())
+        }
+        Some(Command::Practice { bpm }) => practice::run(bpm),
+        Some(Command::Zen { relief }) => zen::run(relief),
+        Some(Command::Adversary { depth }) => adversary::run(depth),
+        Some(Command::Lucky { chance }) => lucky::run(chance),
+        Some(Command::Exhibition { left, right, seed }) => {
+            exhibition::run(left, right, seed, mcts_config)
+        }
+        Some(Command::Sync) => {
+            sync::show();
+            Ok(())
+        }
+        Some(Command::ExportData { file }) => {
+            bundle::show_export(&file);
+            Ok(())
+        }
+        Some(Command::ImportData { file }) => {
+            bundle::show_import(&file);
+            Ok(())
+        }
+        Some(Command::Relay {
+            players,
+            turn_moves,
+            budget,
+            goal,
+        }) => relay::run(players, turn_moves, budget, goal),
+        Some(Command::Coop {
+            players,
+            host,
+            join,
+        }) => coop::run(players, host, join),
+        Some(Command::Splitscreen) => splitscreen::run(),
+        Some(Command::Ghost { rank, file }) => match ghost::load(rank, file.as_deref()) {
+            Ok(record) => ghost::run(record),
+            Err(e) => {
+                logging::error(i18n::tf("error.ghost", &[("error", &e)]));
+                Ok(())
+            }
+        },
+        Some(Command::Multi { boards }) => multi::run(boards),
+        Some(Command::Hex { radius }) => hex::run(radius),
+        Some(Command::Cube) => cube::run(),
+        Some(Command::Gravity { direction }) => gravity::run(direction, None),
+        Some(Command::History) => history::run(),
+        Some(Command::Leaderboard { global: false }) => {
+            history::print_leaderboard();
+            Ok(())
+        }
+        #[cfg(feature = "online")]
+        Some(Command::Leaderboard { global: true }) => {
+            if let Err(e) = online::show_global(&config::load_config().online) {
+                logging::error(i18n::tf("error.leaderboard", &[("error", &e)]));
+                std::process::exit(1);
+            }
+            Ok(())
+        }
+        #[cfg(not(feature = "online"))]
+        Some(Command::Leaderboard { global: true }) => {
+            eprintln!(" > {}", i18n::t("online.unavailable"));
+            std::process::exit(1);
+        }
+        Some(Command::Export {
+            file,
+            input,
+            format,
+        }) => {
+            let input = input.unwrap_or_else(|| profile::path("game_state.json"));
+            if let Err(e) = export_position(&input, &file, format) {
+                logging::error(i18n::tf("error.export_position", &[("error", &e)]));
+                std::process::exit(1);
+            }
+            println!(
+                " > {}",
+                i18n::tf("export.written", &[("path", &file.display())])
+            );
+            Ok(())
+        }
+        Some(Command::Open { uri }) => open_link(&uri),
+        Some(Command::Completions { shell }) => {
+            let mut command = Cli::command();
+            let name = command.get_name().to_string();
+            clap_complete::generate(shell, &mut command, name, &mut stdout());
+            Ok(())
+        }
+        Some(Command::Commands { json }) => {
+            let info = introspect::describe(&Cli::command());
+            if json {
+                match serde_json::to_string_pretty(&info) {
+                    Ok(serialized) => println!("{}", serialized),
+                    Err(e) => {
+                        logging::error(i18n::tf("error.serialize_commands", &[("error", &e)]))
+                    }
+                }
+            } else {
+                introspect::print_text(&info, 0);
+            }
+            Ok(())
+        }
+        None if cli.input_script.is_some() || !io::stdin().is_terminal() => {
+            if let Err(e) = script::run(cli.input_script.as_deref()) {
+                logging::error(i18n::tf("error.script", &[("error", &e)]));
+                std::process::exit(1);
+            }
+            Ok(())
+        }
+        None if cli.tutorial => tutorial::run(),
+        None if cli.bot => bot::run(cli.bot_strategy, mcts_config),
+        None => {
+            if cli.submit && !cfg!(feature = "online") {
+                eprintln!(" > {}", i18n::t("online.submit_unavailable"));
+                std::process::exit(1);
+            }
+            let mask = match cli.mask.as_deref().map(load_mask).transpose() {
+                Ok(mask) => mask,
+                Err(e) => {
+                    logging::error(i18n::tf("error.mask", &[("error", &e)]));
+                    std::process::exit(1);
+                }
+            };
+            let options = PlayOptions {
+                check_updates: cli.check_updates,
+                difficulty: cli.difficulty,
+                combo: cli.combo,
+                submit: cli.submit,
+                mask,
+                goals: (!cli.goal.is_empty()).then_some(cli.goal),
+                ..PlayOptions::default()
+            };
+            if options.difficulty.is_none() && !options.combo && options.mask.is_none() {
+                title::run(options)
+            } else {
+                play(options)
+            }
+        }
+    };
+    if syncs {
+        sync::automatic();
+    }
+    broadcast::stop();
+    result
+}
+
+fn solve(
+    input: &Path,
+    depth: u32,
+    moves: usize,
+    log: Option<&Path>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let data = vault::read(input)?;
+    let state: GameState = serde_json::from_str(&data)?;
+    let mut game_board = state.game_board;
+    let mut annotations = String::new();
+
+    println!(
+        " > {}",
+        i18n::tf(
+            "solve.title",
+            &[("path", &input.display()), ("depth", &depth)]
+        )
+    );
+    annotations.push_str(&format!("start\n{}\n", board_to_text(&game_board)));
+
+    let mut played = 0;
+    while played < moves {
+        let evaluations = ai::evaluate_moves(&game_board, depth);
+        let Some(best) = evaluations
+            .iter()
+            .max_by(|a, b| a.expected.total_cmp(&b.expected))
+        else {
+            break;
+        };
+
+        played += 1;
+        println!(
+            " > {:3}. {:<9} ({})",
+            played,
+            i18n::direction(best.direction),
+            i18n::tf(
+                "solve.expected",
+                &[("expected", &format!("{:.2}", best.expected))]
+            )
+        );
+        annotations.push_str(&format!("move {} {:?}\n", played, best.direction));
+        for evaluation in &evaluations {
+            annotations.push_str(&format!(
+                "  {:<5} {:.3}\n",
+                format!("{:?}", evaluation.direction),
+                evaluation.expected
+            ));
+        }
+
+        let result = apply_move(&mut game_board, best.direction);
+        for merge in &result.merges {
+            annotations.push_str(&format!(
+                "  merged {} at ({}, {})\n",
+                merge.value, merge.position.0, merge.position.1
+            ));
+        }
+        spawn_random_tile(&mut game_board);
+        annotations.push_str(&format!("{}\n", board_to_text(&game_board)));
+    }
+
+    let max_tile = max_tile(&game_board);
+    println!();
+    print!("{}", board_to_text(&game_board));
+    let key = if legal_moves(&game_board).is_empty() {
+        "solve.over"
+    } else {
+        "solve.after"
+    };
+    println!(
+        " > {}",
+        i18n::tf(
+            key,
+            &[
+                ("moves", &played),
+                ("score", &calculate_score(&game_board)),
+                ("max", &max_tile)
+            ]
+        )
+    );
+
+    if let Some(path) = log {
+        fs::write(path, annotations)?;
+        println!(" > {}", i18n::tf("solve.log", &[("path", &path.display())]));
+    }
+    Ok(())
+}
+
+fn export_position(
+    input: &Path,
+    file: &Path,
+    format: Option<export::ExportFormat>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let state: GameState = serde_json::from_str(&vault::read(input)?)?;
+    let mut colors: HashMap<u64, Color> = HashMap::new();
+    theme::initialize_colors(&mut colors, campaign::active_theme());
+    export::write(file, &state.game_board, &colors, format)
+}
+
+fn board_to_text(game_board: &[Vec<u64>]) -> String {
+    let mut text = String::new();
+    for row in game_board {
+        for &val in row {
+            if val == OBSTACLE {
+                text.push_str("  ## ");
+            } else {
+                text.push_str(&format!("{:4} ", val));
+            }
+        }
+        text.push('\n');
+    }
+    text
+}
+
+fn open_link(uri: &str) -> crossterm::Result<()> {
+    let link: BoardLink = match uri.parse() {
+        Ok(link) => link,
+        Err(e) => {
+            logging::error(i18n::tf("error.link", &[("error", &e)]));
+            std::process::exit(1);
+        }
+    };
+    let seed = link.seed.map(|seed| (seed, link.draws));
+    match link.mode.split_once('-').unwrap_or((&link.mode, "")) {
+        // classic[-difficulty][-combo][-mask], plain classic being a normal game.
+        ("classic", options) => {
+            let mut difficulty = Difficulty::Normal;
+            let mut combo = false;
+            let mut masked = false;
+            for option in options.split('-').filter(|option| !option.is_empty()) {
+                match option {
+                    "combo" => combo = true,
+                    "mask" => masked = true,
+                    name => match Difficulty::ALL
+                        .into_iter()
+                        .find(|difficulty| difficulty.name() == name)
+                    {
+                        Some(chosen) => difficulty = chosen,
+                        None => {
+                            logging::error(i18n::tf(
+                                "error.link_difficulty",
+                                &[("difficulty", &name)],
+                            ));
+                            std::process::exit(1);
+                        }
+                    },
+                }
+            }
+            play(PlayOptions {
+                mask: masked.then(|| Mask::walls(&link.board)),
+                opened: Some(link.board),
+                difficulty: Some(difficulty),
+                combo,
+                seed,
+                ..PlayOptions::default()
+            })
+        }
+        ("gravity", direction) => match direction.parse() {
+            Ok(direction) => gravity::run(direction, Some(link.board)),
+            Err(e) => {
+                logging::error(i18n::tf("error.link", &[("error", &e)]));
+                std::process::exit(1);
+            }
+        },
+        _ => {
+            logging::error(i18n::tf("error.link_mode", &[("mode", &link.mode)]));
+            std::process::exit(1);
+        }
+    }
+}
+
+#[derive(Default)]
+struct PlayOptions {
+    check_updates: bool,
+    opened: Option<Vec<Vec<u64>>>,
+    difficulty: Option<Difficulty>,
+    combo: bool,
+    submit: bool,
+    mask: Option<Mask>,
+    size: Option<usize>,
+    fresh: bool,
+    resume: bool,
+    goals: Option<Vec<u64>>,
+    /// A spawn seed and the tiles it already placed, to carry on a shared position.
+    seed: Option<(u64, u64)>,
+}
+
+fn play(options: PlayOptions) -> crossterm::Result<()> {
+    let PlayOptions {
+        check_updates,
+        opened,
+        difficulty,
+        combo,
+        submit,
+        mask,
+        size,
+        fresh,
+        resume,
+        goals,
+        seed,
+    } = options;
+    let update = if check_updates {
+        update::check_for_update()
+    } else {
+        None
+    };
+    let mut messages: Vec<String> = vec![i18n::t("hint.share").to_string()];
+    if let Some(release) = &update {
+        messages.push(i18n::tf(
+            "update.available",
+            &[("version", &release.tag_name)],
+        ));
+    }
+
+    messages.extend(reconcile::run());
+
+    let mut colors: HashMap<u64, Color> = HashMap::new();
+    let mut config = config::load_config_with(&mut |e| {
+        logging::report(
+            &mut messages,
+            i18n::tf("error.bad_config", &[("error", &e)]),
+        )
     });
-    state.current_score = calculate_score(&state.game_board);
+    let opened_link = opened.is_some();
+    let mut state = match opened {
+        Some(game_board) => GameState {
+            game_board,
+            current_score: 0,
+            difficulty: difficulty.unwrap_or_default(),
+            ruleset: if combo {
+                Ruleset::Combo
+            } else {
+                Ruleset::Standard
+            },
+            combo: Combo::default(),
+            assisted: false,
+            continued: false,
+            goals: Goals::default(),
+            size: 0,
+            saved_at: 0,
+        },
+        None => match load_game_state() {
+            _ if fresh => None,
+            Ok(Some(state))
+                if calculate_score(&state.game_board) > 0 && io::stdin().is_terminal() =>
+            {
+                let chosen = difficulty.unwrap_or(state.difficulty);
+                let wanted = (!resume).then(|| {
+                    let size = match &mask {
+                        Some(mask) => mask.board().len(),
+                        None => size.unwrap_or_else(|| chosen.board_size(&config)),
+                    };
+                    let ruleset = if combo {
+                        Ruleset::Combo
+                    } else {
+                        Ruleset::Standard
+                    };
+                    ScoreKey::new(size, chosen, ruleset)
+                });
+                match resume::ask(&state, wanted)? {
+                    resume::Choice::Resume => Some(state),
+                    resume::Choice::NewGame => None,
+                    resume::Choice::Archived(path) => {
+                        messages.push(i18n::tf("resume.archived", &[("path", &path.display())]));
+                        None
+                    }
+                    resume::Choice::Quit => return Ok(()),
+                }
+            }
+            Ok(Some(state)) => match resume::unplayable(&state) {
+                None => Some(state),
+                Some(reason) => {
+                    match resume::archive(state.saved_at) {
+                        Ok(path) => logging::report(
+                            &mut messages,
+                            i18n::tf(
+                                "resume.set_aside",
+                                &[("reason", &reason), ("path", &path.display())],
+                            ),
+                        ),
+                        Err(e) => {
+                            logging::error(i18n::tf("error.archive", &[("error", &e)]));
+                            return Ok(());
+                        }
+                    }
+                    None
+                }
+            },
+            Ok(None) => None,
+            Err(e @ GameError::CorruptedSave { .. }) => {
+                match resume::archive(0) {
+                    Ok(path) => logging::report(
+                        &mut messages,
+                        i18n::tf(
+                            "resume.unreadable",
+                            &[("path", &path.display()), ("error", &e)],
+                        ),
+                    ),
+                    Err(archive_error) => {
+                        logging::error(format!(
+                            "Could not resume the saved game, {}: {}",
+                            e, archive_error
+                        ));
+                        return Ok(());
+                    }
+                }
+                None
+            }
+            Err(e) => {
+                logging::error(i18n::tf("error.resume", &[("reason", &e)]));
+                return Ok(());
+            }
+        }
+        .unwrap_or_else(|| GameState {
+            game_board: Vec::new(),
+            current_score: 0,
+            difficulty: Difficulty::Normal,
+            ruleset: Ruleset::Standard,
+            combo: Combo::default(),
+            assisted: false,
+            continued: false,
+            goals: Goals::default(),
+            size: 0,
+            saved_at: 0,
+        }),
+    };
+    state.current_score = calculate_score(&state.game_board) + state.combo.bonus;
+    if state.current_score == 0 && !opened_link {
+        let chosen = match difficulty {
+            Some(difficulty) => difficulty,
+            None if io::stdin().is_terminal() => match difficulty::pick(&config)? {
+                Some(difficulty) => difficulty,
+                None => return Ok(()),
+            },
+            None => Difficulty::Normal,
+        };
+        let size = size.unwrap_or_else(|| chosen.board_size(&config));
+        state.difficulty = chosen;
+        state.ruleset = if combo {
+            Ruleset::Combo
+        } else {
+            Ruleset::Standard
+        };
+        state.combo = Combo::default();
+        state.assisted = false;
+        state.continued = false;
+        state.game_board = match &mask {
+            Some(mask) => mask.board(),
+            None => vec![vec![0; size]; size],
+        };
+        difficulty::place_blockers(&mut state.game_board, chosen.blockers());
+        state.goals = Goals::new(goals.as_deref().unwrap_or(&config.game.goals));
+    }
+    if state.goals.tiles.is_empty() {
+        state.goals = Goals::new(goals.as_deref().unwrap_or(&config.game.goals));
+    }
+    messages.push(state.goals.message());
+    messages.push(format!(
+        "{} {}",
+        i18n::label("label.difficulty"),
+        state.difficulty.label()
+    ));
+    if state.ruleset == Ruleset::Combo {
+        messages.push(format!(
+            "{} {}",
+            i18n::label("label.rules"),
+            i18n::t("rules.combo")
+        ));
+        messages.push(combo_meter(&state.combo));
+    }
+    state.assisted |= config.game.corner_lock != CornerLock::Off;
+    corner_assist(&mut messages, &state.game_board, config.game.corner_lock);
+    let mut confirming: Option<Direction> = None;
 
-    let mut high_score = read_high_score();
-    initialize_colors(&mut colors);
+    enable_raw_mode()?;
+    let score_key = ScoreKey::new(state.game_board.len(), state.difficulty, state.ruleset);
+    let mut high_score = scores::best(score_key);
+    let best_before = high_score;
+    messages.push(format!(
+        "{} {}",
+        i18n::label("label.best_on"),
+        score_key.describe()
+    ));
+    let mut show_risk = false;
+    let mut status = StatusLine::default();
+    let mut sound = sound::Sound::new(&config.sound);
+    sound.note_max_tile(max_tile(&state.game_board));
+    theme::initialize_colors(&mut colors, campaign::active_theme());
+    let four_chance = state.difficulty.four_chance(&config);
+    let mut spawner = match seed {
+        Some((seed, draws)) => Spawner::resume(seed, draws, four_chance),
+        None => Spawner::with_four_chance(thread_rng().gen(), four_chance),
+    };
+    let (seed, first_draw) = spawner.checkpoint();
+    // Only a game dealt from the seed's first draw, never rewound and never dealt under
+    // changed odds, can be replayed from the seed alone.
+    let mut reproducible = first_draw == 0 && state.current_score == 0;
+    spawner.set_strategy(config.game.spawn.strategy());
+    let mut draws = logging::Draws::new();
     if state.current_score == 0 {
-        spawn_random_tile(&mut state.game_board);
-        spawn_random_tile(&mut state.game_board);
+        for _ in 0..2 {
+            let value = spawner.next_value();
+            if let Some(position) = spawner.spawn(&mut state.game_board) {
+                draws.record(value, position);
+            }
+        }
+    }
+    logging::overlay(&mut messages, &state.game_board, &draws);
+    if config.preview.enabled {
+        messages.push(spawn_preview(
+            &spawner,
+            &state.game_board,
+            config.preview.location,
+        ));
     }
-    render_board(&state.game_board, &colors, state.current_score, high_score)?;
+    let mut recorder = history::Recorder::new(&state.game_board, &config.game, state.combo);
+    let mut seen: HashMap<u64, usize> = HashMap::from([(canonical_hash(&state.game_board), 0)]);
+    let mut finished: Option<history::GameRecord> = None;
+    let mut observers = integrations(&i18n::tf(
+        "presence.classic",
+        &[("difficulty", &state.difficulty.label())],
+    ));
+    render_board(
+        &state.game_board,
+        &colors,
+        state.current_score,
+        high_score,
+        show_risk,
+        &status.with_messages(&messages),
+    )?;
 
+    let fps = if frame::net_friendly() {
+        NET_FRIENDLY_FPS
+    } else {
+        config.display.fps
+    };
+    let mut ticks = Ticks::new();
+    ticks.every(Tick::Frame, tick::frame_interval(fps));
+    ticks.every(Tick::Pace, PACE_INTERVAL);
+    if config.saves.autosave_secs > 0 {
+        ticks.every(
+            Tick::Autosave,
+            Duration::from_secs(config.saves.autosave_secs),
+        );
+    }
+    let mut saved_board = state.game_board.clone();
     loop {
+        if !poll(ticks.timeout())? {
+            let mut redraw = false;
+            for due in ticks.due() {
+                match due {
+                    Tick::Frame => redraw |= status.expire() | popup::tick(),
+                    Tick::Pace => {
+                        redraw |= update_pace(&mut messages, &recorder)
+                            | suggest_slow_terminal(&mut messages)
+                    }
+                    Tick::Autosave if state.game_board != saved_board => {
+                        match save_game_state(&state) {
+                            Ok(()) => saved_board = state.game_board.clone(),
+                            Err(e) => logging::report(
+                                &mut messages,
+                                i18n::tf("error.autosave", &[("error", &e)]),
+                            ),
+                        }
+                    }
+                    Tick::Autosave => {}
+                }
+            }
+            if redraw {
+                render_board(
+                    &state.game_board,
+                    &colors,
+                    state.current_score,
+                    high_score,
+                    show_risk,
+                    &status.with_messages(&messages),
+                )?;
+            }
+            continue;
+        }
         if let Event::Key(key_event) = read()? {
             match key_event.code {
                 KeyCode::Char('e') | KeyCode::Char('E') => {
                     if let Err(e) = save_game_state(&state) {
-                        eprintln!(" > Failed to save game state: {}", e);
+                        logging::error(i18n::tf("error.save_game", &[("error", &e)]));
                     }
                     break;
                 }
-                _ => {
-                    let moved: bool = match key_event.code {
-                        KeyCode::Up => move_up(&mut state.game_board),
-                        KeyCode::Down => move_down(&mut state.game_board),
-                        KeyCode::Left => move_left(&mut state.game_board),
-                        KeyCode::Right => move_right(&mut state.game_board),
-                        _ => false,
+                KeyCode::Char('u') | KeyCode::Char('U') if update.is_some() => {
+                    if let Some(release) = &update {
+                        update::show_changelog(release)?;
+                    }
+                    render_board(
+                        &state.game_board,
+                        &colors,
+                        state.current_score,
+                        high_score,
+                        show_risk,
+                        &status.with_messages(&messages),
+                    )?;
+                }
+                KeyCode::Char('m') | KeyCode::Char('M') => {
+                    sound.toggle_mute();
+                }
+                KeyCode::Char('?') | KeyCode::F(1) => {
+                    let mut extra_keys = vec![
+                        ("S", i18n::t("help.classic.share")),
+                        ("H", i18n::t("help.classic.history")),
+                        ("T", i18n::t("help.classic.timeline")),
+                        ("X", i18n::t("help.classic.export")),
+                        ("G", i18n::t("help.classic.graph")),
+                        ("O", i18n::t("help.classic.settings")),
+                    ];
+                    if update.is_some() {
+                        extra_keys.push(("U", i18n::t("help.classic.changelog")));
+                    }
+                    let objective = match state.goals.next() {
+                        Some(tile) => i18n::tf("help.classic.objective", &[("tile", &tile)]),
+                        None => i18n::t("help.classic.objective_done").to_string(),
+                    };
+                    help::show(i18n::t("mode.classic"), &objective, &extra_keys)?;
+                    render_board(
+                        &state.game_board,
+                        &colors,
+                        state.current_score,
+                        high_score,
+                        show_risk,
+                        &status.with_messages(&messages),
+                    )?;
+                }
+                KeyCode::Char('h') | KeyCode::Char('H') => {
+                    history::browse()?;
+                    render_board(
+                        &state.game_board,
+                        &colors,
+                        state.current_score,
+                        high_score,
+                        show_risk,
+                        &status.with_messages(&messages),
+                    )?;
+                }
+                KeyCode::Char('g') | KeyCode::Char('G') => {
+                    graph::show(i18n::t("graph.title"), &recorder.score_series())?;
+                    render_board(
+                        &state.game_board,
+                        &colors,
+                        state.current_score,
+                        high_score,
+                        show_risk,
+                        &status.with_messages(&messages),
+                    )?;
+                }
+                KeyCode::Char('x') | KeyCode::Char('X') => {
+                    let secs = SystemTime::now()
+                        .duration_since(UNIX_EPOCH)
+                        .map(|elapsed| elapsed.as_secs())
+                        .unwrap_or(0);
+                    let path = PathBuf::from(format!("board-{}.svg", secs));
+                    messages.retain(|message| !message.starts_with(&i18n::label("label.exported")));
+                    match export::write(&path, &state.game_board, &colors, None) {
+                        Ok(()) => messages.push(format!(
+                            "{} {}",
+                            i18n::label("label.exported"),
+                            i18n::tf("export.done", &[("path", &path.display())])
+                        )),
+                        Err(e) => messages.push(format!(
+                            "{} {}",
+                            i18n::label("label.exported"),
+                            i18n::tf("export.failed", &[("error", &e)])
+                        )),
+                    }
+                    render_board(
+                        &state.game_board,
+                        &colors,
+                        state.current_score,
+                        high_score,
+                        show_risk,
+                        &status.with_messages(&messages),
+                    )?;
+                }
+                KeyCode::Char('o') | KeyCode::Char('O') => {
+                    let dealing = (config.game.spawn, state.difficulty.four_chance(&config));
+                    settings::show(&mut config, &mut colors)?;
+                    reproducible &=
+                        dealing == (config.game.spawn, state.difficulty.four_chance(&config));
+                    spawner.set_four_chance(state.difficulty.four_chance(&config));
+                    spawner.set_strategy(config.game.spawn.strategy());
+                    state.assisted |= config.game.corner_lock != CornerLock::Off;
+                    corner_assist(&mut messages, &state.game_board, config.game.corner_lock);
+                    messages
+                        .retain(|message| !message.starts_with(&i18n::label("label.next_tile")));
+                    if config.preview.enabled {
+                        messages.push(spawn_preview(
+                            &spawner,
+                            &state.game_board,
+                            config.preview.location,
+                        ));
+                    }
+                    render_board(
+                        &state.game_board,
+                        &colors,
+                        state.current_score,
+                        high_score,
+                        show_risk,
+                        &status.with_messages(&messages),
+                    )?;
+                }
+                KeyCode::Char('t') | KeyCode::Char('T') if !state.difficulty.allows_undo() => {
+                    messages.retain(|message| !message.starts_with(&i18n::label("label.timeline")));
+                    messages.push(format!(
+                        "{} {}",
+                        i18n::label("label.timeline"),
+                        i18n::tf(
+                            "timeline.unavailable",
+                            &[("difficulty", &state.difficulty.label())]
+                        )
+                    ));
+                    render_board(
+                        &state.game_board,
+                        &colors,
+                        state.current_score,
+                        high_score,
+                        show_risk,
+                        &status.with_messages(&messages),
+                    )?;
+                }
+                KeyCode::Char('t') | KeyCode::Char('T') => {
+                    if let Some(game_board) = history::timeline(&mut recorder, &colors)? {
+                        state.game_board = game_board;
+                        reproducible = false;
+                        restore_position(
+                            &mut state,
+                            &recorder,
+                            &mut seen,
+                            &mut messages,
+                            &config,
+                            &spawner,
+                        );
+                        logging::overlay(&mut messages, &state.game_board, &draws);
+                    }
+                    render_board(
+                        &state.game_board,
+                        &colors,
+                        state.current_score,
+                        high_score,
+                        show_risk,
+                        &status.with_messages(&messages),
+                    )?;
+                }
+                KeyCode::Char('s') | KeyCode::Char('S') => {
+                    let (seed, draws) = spawner.checkpoint();
+                    let mut mode = match state.difficulty {
+                        Difficulty::Normal => "classic".to_string(),
+                        difficulty => format!("classic-{}", difficulty.name()),
+                    };
+                    if state.ruleset == Ruleset::Combo {
+                        mode.push_str("-combo");
+                    }
+                    if mask.is_some() {
+                        mode.push_str("-mask");
+                    }
+                    messages
+                        .retain(|message| !message.starts_with(&i18n::label("label.share_link")));
+                    messages.push(
+                        match BoardLink::new(mode, state.game_board.clone(), Some(seed), draws) {
+                            Ok(link) => format!("{} {}", i18n::label("label.share_link"), link),
+                            Err(tile) => format!(
+                                "{} {}",
+                                i18n::label("label.share_link"),
+                                i18n::tf("link.unshareable", &[("tile", &tile.to_string())])
+                            ),
+                        },
+                    );
+                    render_board(
+                        &state.game_board,
+                        &colors,
+                        state.current_score,
+                        high_score,
+                        show_risk,
+                        &status.with_messages(&messages),
+                    )?;
+                }
+                KeyCode::Char('r') | KeyCode::Char('R') => {
+                    show_risk = !show_risk;
+                    render_board(
+                        &state.game_board,
+                        &colors,
+                        state.current_score,
+                        high_score,
+                        show_risk,
+                        &status.with_messages(&messages),
+                    )?;
+                }
+                code => {
+                    let direction = key_direction(code);
+                    if let Some(direction) = direction {
+                        if config.game.corner_lock == CornerLock::Confirm
+                            && confirming != Some(direction)
+                            && leaves_corner(&state.game_board, direction)
+                        {
+                            confirming = Some(direction);
+                            messages.retain(|message| {
+                                !message.starts_with(&i18n::label("label.assist"))
+                            });
+                            messages.push(format!(
+                                "{} {}",
+                                i18n::label("label.assist"),
+                                i18n::tf(
+                                    "assist.confirm",
+                                    &[("direction", &i18n::direction(direction))]
+                                )
+                            ));
+                            render_board(
+                                &state.game_board,
+                                &colors,
+                                state.current_score,
+                                high_score,
+                                show_risk,
+                                &status.with_messages(&messages),
+                            )?;
+                            continue;
+                        }
+                    }
+                    confirming = None;
+                    let result: MoveResult = match direction {
+                        Some(direction) => apply_move(&mut state.game_board, direction),
+                        None => MoveResult::default(),
                     };
 
-                    if moved {
-                        spawn_random_tile(&mut state.game_board);
-                        state.current_score = calculate_score(&state.game_board);
+                    if let (true, Some(direction)) = (result.moved, direction) {
+                        sound.play_move(&result);
+                        broadcast::send_move(direction, &result);
+                        accessibility::record_move(direction, &result);
+                        status.record(direction, &result);
+                        popup::show(&result);
+                        for observer in &mut observers {
+                            observer.on_move(direction, &result);
+                        }
+                        if state.ruleset == Ruleset::Combo {
+                            state.combo.record(&result);
+                            messages.retain(|message| {
+                                !message.starts_with(&i18n::label("label.combo"))
+                            });
+                            messages.push(combo_meter(&state.combo));
+                        }
+                        let moved = state.game_board.clone();
+                        let value = spawner.next_value();
+                        if let Some(position) = spawner.spawn(&mut state.game_board) {
+                            draws.record(value, position);
+                            for observer in &mut observers {
+                                observer.on_spawn(position, value);
+                            }
+                        }
+                        logging::overlay(&mut messages, &state.game_board, &draws);
+                        if config.preview.enabled {
+                            messages.retain(|message| {
+                                !message.starts_with(&i18n::label("label.next_tile"))
+                            });
+                            messages.push(spawn_preview(
+                                &spawner,
+                                &state.game_board,
+                                config.preview.location,
+                            ));
+                        }
+                        recorder.record(direction, &moved, &state.game_board);
+                        update_pace(&mut messages, &recorder);
+                        update_graph(&mut messages, &recorder);
+                        corner_assist(&mut messages, &state.game_board, config.game.corner_lock);
+                        note_repetition(
+                            &mut messages,
+                            &mut seen,
+                            &state.game_board,
+                            recorder.len(),
+                        );
+                        for observer in &mut observers {
+                            observer.on_turn(&state.game_board);
+                        }
+                        state.current_score =
+                            calculate_score(&state.game_board) + state.combo.bonus;
+                        let reached = state.goals.check(
+                            &state.game_board,
+                            recorder.len(),
+                            recorder.elapsed().as_secs(),
+                        );
+                        if let Some(&tile) = reached.last() {
+                            status.announce(state.goals.banner(tile));
+                            messages
+                                .retain(|message| !message.starts_with(&i18n::label("label.goal")));
+                            messages.push(state.goals.message());
+                        }
 
                         if state.current_score > high_score {
                             high_score = state.current_score;
-                            if let Err(e) = write_high_score(high_score) {
-                                eprintln!(" > Failed to write high score: {}", e);
+                            if let Err(e) = scores::record(score_key, high_score) {
+                                logging::report(
+                                    &mut messages,
+                                    i18n::tf("error.save_high_score", &[("error", &e)]),
+                                );
                             }
                         }
 
-                        if !can_make_move(&state.game_board) {
+                        let rewind = config.game.rewind_moves;
+                        if legal_moves(&state.game_board).is_empty()
+                            && rewind > 0
+                            && !state.continued
+                            && !submit
+                            && state.difficulty.allows_undo()
+                        {
+                            messages.push(format!(
+                                "{} {}",
+                                i18n::label("label.continue"),
+                                i18n::tf("continue.offer", &[("moves", &rewind)])
+                            ));
                             render_board(
                                 &state.game_board,
                                 &colors,
                                 state.current_score,
                                 high_score,
+                                show_risk,
+                                &status.with_messages(&messages),
                             )?;
+                            messages.retain(|message| {
+                                !message.starts_with(&i18n::label("label.continue"))
+                            });
+                            let accepted = loop {
+                                if let Event::Key(key_event) = read()? {
+                                    break matches!(
+                                        key_event.code,
+                                        KeyCode::Char('y') | KeyCode::Char('Y')
+                                    );
+                                }
+                            };
+                            if accepted {
+                                state.continued = true;
+                                reproducible = false;
+                                state.game_board =
+                                    recorder.branch(recorder.len().saturating_sub(rewind));
+                                restore_position(
+                                    &mut state,
+                                    &recorder,
+                                    &mut seen,
+                                    &mut messages,
+                                    &config,
+                                    &spawner,
+                                );
+                                logging::overlay(&mut messages, &state.game_board, &draws);
+                                let saved = stats::load_stats().and_then(|mut stats| {
+                                    stats.continues_used += 1;
+                                    stats::save_stats(&stats)
+                                });
+                                if let Err(e) = saved {
+                                    logging::report(
+                                        &mut messages,
+                                        i18n::tf("error.save_stats", &[("error", &e)]),
+                                    );
+                                }
+                            }
+                        }
+
+                        if legal_moves(&state.game_board).is_empty() {
+                            render_board(
+                                &state.game_board,
+                                &colors,
+                                state.current_score,
+                                high_score,
+                                show_risk,
+                                &status.with_messages(&messages),
+                            )?;
+                            let size = state.difficulty.board_size(&config);
                             let start_state: GameState = GameState {
-                                game_board: vec![vec![0; 4]; 4],
+                                game_board: vec![vec![0; size]; size],
                                 current_score: 0,
-                                high_score: read_high_score(),
+                                difficulty: state.difficulty,
+                                ruleset: state.ruleset,
+                                combo: Combo::default(),
+                                assisted: false,
+                                continued: false,
+                                goals: Goals::new(&state.goals.tiles),
+                                size: 0,
+                                saved_at: 0,
                             };
 
                             if let Err(e) = save_game_state(&start_state) {
-                                eprintln!(" > Failed to save game state: {}", e);
+                                logging::error(i18n::tf("error.save_game", &[("error", &e)]));
+                            }
+
+                            let mut mode = match state.ruleset {
+                                Ruleset::Standard => format!("classic-{}", state.difficulty.name()),
+                                ruleset => format!(
+                                    "classic-{}-{}",
+                                    state.difficulty.name(),
+                                    ruleset.name()
+                                ),
+                            };
+                            if config.game.spawn != SpawnBias::Random {
+                                mode.push('-');
+                                mode.push_str(config.game.spawn.name());
+                            }
+                            if state.assisted {
+                                mode.push_str("-assisted");
+                            }
+                            if state.continued {
+                                mode.push_str("-continued");
+                            }
+                            let mut record = recorder.finish(&mode, &state.game_board);
+                            record.score = state.current_score;
+                            record.seed = reproducible.then_some(seed);
+                            record.goals = state.goals.reached.clone();
+                            finished = Some(record.clone());
+                            if let Err(e) = history::append(record) {
+                                logging::error(i18n::tf("error.save_history", &[("error", &e)]));
                             }
 
-                            println!(" >> Game Over! <<");
+                            for observer in &mut observers {
+                                observer.on_game_over(state.current_score);
+                            }
+                            println!(" >> {} <<", i18n::t("game.over"));
+                            if !state.goals.reached.is_empty() {
+                                println!(
+                                    " > {} {}",
+                                    i18n::label("label.goals_made"),
+                                    goals::timeline(&state.goals.reached)
+                                );
+                            }
+                            if state.current_score > best_before {
+                                println!(
+                                    " >> {} <<",
+                                    i18n::tf("game.new_best", &[("key", &score_key.describe())])
+                                );
+                            } else {
+                                println!(
+                                    " > {}",
+                                    i18n::tf(
+                                        "game.best",
+                                        &[("key", &score_key.describe()), ("score", &high_score)]
+                                    )
+                                );
+                            }
+                            broadcast::send_game_over(
+                                state.current_score,
+                                i18n::t("broadcast.no_moves"),
+                            );
                             break;
                         }
 
-                        render_board(&state.game_board, &colors, state.current_score, high_score)?;
+                        render_board(
+                            &state.game_board,
+                            &colors,
+                            state.current_score,
+                            high_score,
+                            show_risk,
+                            &status.with_messages(&messages),
+                        )?;
                     }
                 }
             }
         }
     }
     disable_raw_mode()?;
+    if let (true, Some(record)) = (submit, finished) {
+        submit_score(&config, &record);
+    }
     Ok(())
 }
 
-fn render_board(
-    game_board: &Vec<Vec<u32>>,
-    colors: &HashMap<u32, Color>,
-    current_score: u32,
-    high_score: u32,
-) -> crossterm::Result<()> {
-    let mut stdout: std::io::Stdout = stdout();
-    stdout.execute(Clear(ClearType::All))?;
-    stdout.execute(cursor::MoveTo(0, 0))?;
+#[cfg(feature = "online")]
+fn submit_score(config: &config::Config, record: &history::GameRecord) {
+    println!(" > {}", online::submit(&config.online, record));
+}
 
-    for row in game_board {
-        for &val in row {
-            let color = colors.get(&val).unwrap_or(&Color::White);
-            print!("{} ", format!("{:4}", val).color(*color));
-        }
-        println!();
+#[cfg(not(feature = "online"))]
+fn submit_score(_config: &config::Config, _record: &history::GameRecord) {}
+
+#[cfg_attr(not(feature = "discord"), allow(unused_variables))]
+pub fn integrations(mode: &str) -> Vec<Box<dyn GameObserver>> {
+    let mut observers: Vec<Box<dyn GameObserver>> = Vec::new();
+    if logging::enabled() {
+        observers.push(Box::new(logging::TraceObserver::new(mode)));
     }
-    println!(" > Current score : {}", current_score);
-    println!(" > High score    : {}", high_score);
-    println!();
-    println!(" > Press E to exit");
-    Ok(())
+    #[cfg(feature = "discord")]
+    if let Some(presence) = presence::Presence::start(&config::load_config().discord, mode) {
+        observers.push(Box::new(presence));
+    }
+    observers
 }
 
-fn can_make_move(game_board: &[Vec<u32>]) -> bool {
-    for row in game_board {
-        for i in 0..row.len() {
-            if row[i] == 0 {
-                return true;
-            }
-            if i < row.len() - 1 && row[i] == row[i + 1] {
-                return true;
-            }
-        }
+fn corner_assist(messages: &mut Vec<String>, game_board: &[Vec<u64>], corner_lock: CornerLock) {
+    messages.retain(|message| !message.starts_with(&i18n::label("label.assist")));
+    if corner_lock != CornerLock::Warn {
+        return;
     }
-
-    for col in 0..game_board[0].len() {
-        for row in 0..game_board.len() - 1 {
-            if game_board[row][col] == game_board[row + 1][col] {
-                return true;
-            }
-        }
+    let risky: Vec<String> = Direction::ALL
+        .into_iter()
+        .filter(|&direction| leaves_corner(game_board, direction))
+        .map(|direction| i18n::direction(direction).to_string())
+        .collect();
+    if !risky.is_empty() {
+        messages.push(format!(
+            "{} {}",
+            i18n::label("label.assist"),
+            i18n::tf("assist.warn", &[("directions", &risky.join(", "))])
+        ));
     }
+}
 
-    false
+fn restore_position(
+    state: &mut GameState,
+    recorder: &history::Recorder,
+    seen: &mut HashMap<u64, usize>,
+    messages: &mut Vec<String>,
+    config: &config::Config,
+    spawner: &Spawner,
+) {
+    if state.ruleset == Ruleset::Combo {
+        state.combo = recorder.combo();
+        messages.retain(|message| !message.starts_with(&i18n::label("label.combo")));
+        messages.push(combo_meter(&state.combo));
+    }
+    state.current_score = calculate_score(&state.game_board) + state.combo.bonus;
+    corner_assist(messages, &state.game_board, config.game.corner_lock);
+    update_graph(messages, recorder);
+    seen.retain(|_, step| *step <= recorder.len());
+    messages.retain(|message| !message.starts_with(&i18n::label("label.repeat")));
+    if config.preview.enabled {
+        messages.retain(|message| !message.starts_with(&i18n::label("label.next_tile")));
+        messages.push(spawn_preview(
+            spawner,
+            &state.game_board,
+            config.preview.location,
+        ));
+    }
 }
 
-fn calculate_score(game_board: &[Vec<u32>]) -> u32 {
-    game_board.iter().flatten().sum()
+fn note_repetition(
+    messages: &mut Vec<String>,
+    seen: &mut HashMap<u64, usize>,
+    game_board: &[Vec<u64>],
+    step: usize,
+) {
+    messages.retain(|message| !message.starts_with(&i18n::label("label.repeat")));
+    match seen.entry(canonical_hash(game_board)) {
+        Entry::Occupied(first) => messages.push(format!(
+            "{} {}",
+            i18n::label("label.repeat"),
+            i18n::tf("repeat.seen", &[("move", first.get())])
+        )),
+        Entry::Vacant(entry) => {
+            entry.insert(step);
+        }
+    }
 }
 
-fn read_high_score() -> u32 {
-    let file_path = "highscore.txt";
-    match fs::read_to_string(file_path) {
-        Ok(content) => content.trim().parse().unwrap_or(0),
-        Err(_) => 0,
+fn update_pace(messages: &mut Vec<String>, recorder: &history::Recorder) -> bool {
+    let pace = format!(
+        "{} {}",
+        i18n::label("label.pace"),
+        i18n::tf(
+            "pace.value",
+            &[
+                ("rate", &format!("{:.0}", recorder.moves_per_minute())),
+                ("secs", &recorder.thinking().as_secs())
+            ]
+        )
+    );
+    if messages.contains(&pace) {
+        return false;
     }
+    messages.retain(|message| !message.starts_with(&i18n::label("label.pace")));
+    messages.push(pace);
+    true
 }
 
-fn write_high_score(high_score: u32) -> io::Result<()> {
-    let mut file = File::create("highscore.txt")?;
-    write!(file, "{}", high_score)?;
-    Ok(())
+fn suggest_slow_terminal(messages: &mut Vec<String>) -> bool {
+    let Some(average) = frame::slow_suggestion() else {
+        return false;
+    };
+    messages.push(format!(
+        "{} {}",
+        i18n::label("label.slow_terminal"),
+        i18n::tf("slow.suggest", &[("ms", &average.as_millis())])
+    ));
+    true
+}
+
+fn update_graph(messages: &mut Vec<String>, recorder: &history::Recorder) {
+    messages.retain(|message| !message.starts_with(&i18n::label("label.score_graph")));
+    if recorder.len() > 1 {
+        messages.push(format!(
+            "{} {}",
+            i18n::label("label.score_graph"),
+            i18n::tf(
+                "graph.sparkline",
+                &[("line", &graph::sparkline(&recorder.score_samples()))]
+            )
+        ));
+    }
 }
 
-fn initialize_colors(colors: &mut HashMap<u32, Color>) {
-    colors.insert(2, Color::Red);
-    colors.insert(4, Color::Blue);
-    colors.insert(8, Color::Green);
-    colors.insert(16, Color::Yellow);
-    colors.insert(32, Color::Magenta);
-    colors.insert(64, Color::Cyan);
-    colors.insert(128, Color::BrightRed);
-    colors.insert(256, Color::BrightBlue);
-    colors.insert(512, Color::BrightGreen);
-    colors.insert(1024, Color::Green);
-    colors.insert(2048, Color::BrightCyan);
+fn combo_meter(combo: &Combo) -> String {
+    format!(
+        "{} x{} [{:<5}] {}",
+        i18n::label("label.combo"),
+        combo.multiplier(),
+        "#".repeat(combo.streak.min(5) as usize),
+        i18n::tf("combo.bonus", &[("bonus", &combo.bonus)])
+    )
 }
 
-fn spawn_random_tile(game_board: &mut [Vec<u32>]) {
-    let mut empty_cells_array: Vec<(usize, usize)> = Vec::new();
-    for (i, row) in game_board.iter().enumerate() {
-        for (j, &cell) in row.iter().enumerate() {
-            if cell == 0 {
-                empty_cells_array.push((i, j));
-            }
+fn spawn_preview(spawner: &Spawner, game_board: &[Vec<u64>], location: bool) -> String {
+    let mut preview = format!(
+        "{} {}",
+        i18n::label("label.next_tile"),
+        spawner.next_value()
+    );
+    if location {
+        let cells: Vec<String> = legal_moves(game_board)
+            .into_iter()
+            .filter_map(|direction| {
+                let mut next = game_board.to_vec();
+                apply_move(&mut next, direction);
+                spawner
+                    .next_cell(&next)
+                    .map(|(i, j)| format!("{} r{}c{}", i18n::direction(direction), i + 1, j + 1))
+            })
+            .collect();
+        if !cells.is_empty() {
+            preview.push_str(&format!(
+                " {}",
+                i18n::tf("preview.after", &[("cells", &cells.join(", "))])
+            ));
         }
     }
+    preview
+}
 
-    if let Some(&(i, j)) = empty_cells_array.iter().choose(&mut thread_rng()) {
-        let new_value = if thread_rng().gen_bool(0.9) { 2 } else { 4 };
-        game_board[i][j] = new_value;
+fn render_board(
+    game_board: &[Vec<u64>],
+    colors: &HashMap<u64, Color>,
+    current_score: u64,
+    high_score: u64,
+    show_risk: bool,
+    messages: &[String],
+) -> crossterm::Result<()> {
+    broadcast::send_board(game_board, current_score);
+    if output::is_json() {
+        output::print_turn(game_board, current_score, high_score, messages);
+        return Ok(());
     }
-}
+    let mut lines: Vec<String> = Vec::new();
 
-fn move_left(game_board: &mut [Vec<u32>]) -> bool {
-    let initial_board = game_board.to_vec();
-    let mut moved = false;
-
-    for row in game_board.iter_mut() {
-        for i in 1..row.len() {
-            let mut k = i;
-            while k > 0 && row[k - 1] == 0 {
-                row.swap(k, k - 1);
-                moved = true;
-                k -= 1;
+    if accessibility::accessible() {
+        lines.extend(accessibility::describe(game_board));
+    }
+    for (i, row) in game_board
+        .iter()
+        .enumerate()
+        .filter(|_| !accessibility::accessible())
+    {
+        let mut line = String::new();
+        for &val in row {
+            if val == OBSTACLE {
+                let blocked = format!("{:>width$}", "##", width = theme::cell_width());
+                line.push_str(&format!("{} ", blocked.dimmed()));
+                continue;
             }
+            line.push_str(&format!("{} ", theme::tile_cell(colors, val)));
         }
-        for i in 0..row.len() - 1 {
-            if row[i] != 0 && row[i] == row[i + 1] {
-                row[i] *= 2;
-                row[i + 1] = 0;
-                moved = true;
+        line.push_str(&popup::row(i));
+        lines.push(line);
+    }
+    lines.push(format!(
+        " > {} {}{}",
+        i18n::label("label.current_score"),
+        current_score,
+        popup::score()
+    ));
+    lines.push(format!(
+        " > {} {}",
+        i18n::label("label.high_score"),
+        high_score
+    ));
+    let legal = legal_moves(game_board);
+    let moves: Vec<String> = Direction::ALL
+        .into_iter()
+        .map(|direction| {
+            let name = i18n::direction(direction).to_string();
+            if legal.contains(&direction) {
+                name
+            } else if accessibility::colorless() {
+                "-".repeat(name.len())
+            } else {
+                name.dimmed().to_string()
             }
-        }
-        for i in 1..row.len() {
-            let mut k = i;
-            while k > 0 && row[k - 1] == 0 {
-                row.swap(k, k - 1);
-                k -= 1;
+        })
+        .collect();
+    if accessibility::accessible() {
+        let names: Vec<String> = legal
+            .iter()
+            .map(|&direction| i18n::direction(direction).to_lowercase())
+            .collect();
+        lines.push(format!(
+            " > {} {}",
+            i18n::label("label.legal_moves"),
+            names.join(", ")
+        ));
+    } else {
+        lines.push(format!(
+            " > {} {}",
+            i18n::label("label.moves"),
+            moves.join(" ")
+        ));
+    }
+    if show_risk {
+        let risk = heuristic::risk(game_board);
+        lines.push(format!(
+            " > {} {}",
+            i18n::label("label.risk"),
+            risk_gauge(risk)
+        ));
+        if (heuristic::COMPROMISED_RISK..1.0).contains(&risk) {
+            let plan: Vec<String> = ai::recovery_plan(game_board, 2, 3)
+                .iter()
+                .map(|step| i18n::direction(step.direction).to_string())
+                .collect();
+            if !plan.is_empty() {
+                lines.push(format!(
+                    " > {} {}",
+                    i18n::label("label.recovery_plan"),
+                    plan.join(" -> ")
+                ));
             }
         }
     }
-
-    if initial_board == *game_board {
-        moved = false;
+    if frame::net_friendly() {
+        let stats = frame::last_stats();
+        lines.push(format!(
+            " > {} {}",
+            i18n::label("label.last_frame"),
+            i18n::tf(
+                "frame.stats",
+                &[
+                    ("bytes", &stats.bytes),
+                    ("redrawn", &stats.lines_redrawn),
+                    ("lines", &stats.lines)
+                ]
+            )
+        ));
+    }
+    lines.push(String::new());
+    for message in messages {
+        lines.push(format!(" > {}", message));
+    }
+    if !messages.is_empty() {
+        lines.push(String::new());
+    }
+    lines.push(format!(" > {}", i18n::t("hint.help")));
+    lines.push(format!(" > {}", i18n::t("hint.risk")));
+    if cfg!(feature = "sound") {
+        lines.push(format!(" > {}", i18n::t("hint.sound")));
     }
+    lines.push(format!(" > {}", i18n::t("hint.exit")));
 
-    moved
+    let mut text = lines.join("\n");
+    text.push('\n');
+    frame::present(&text)
 }
 
-fn move_right(game_board: &mut [Vec<u32>]) -> bool {
-    let mut moved = false;
-    let initial_board = game_board.to_vec();
-
-    for row in game_board.iter_mut() {
-        for i in (0..row.len() - 1).rev() {
-            let mut k = i;
-            while k < row.len() - 1 && row[k + 1] == 0 {
-                row.swap(k, k + 1);
-                moved = true;
-                k += 1;
-            }
-        }
+fn risk_gauge(risk: f64) -> ColoredString {
+    let width = 10;
+    let filled = (risk * width as f64).round() as usize;
+    let gauge = format!(
+        "[{}{}] {:3.0}%",
+        "#".repeat(filled),
+        "-".repeat(width - filled),
+        risk * 100.0
+    );
+    if risk < 0.4 {
+        gauge.green()
+    } else if risk < 0.7 {
+        gauge.yellow()
+    } else {
+        gauge.red()
+    }
+}
 
-        for i in (0..row.len() - 1).rev() {
-            if row[i] != 0 && row[i] == row[i + 1] {
-                row[i + 1] *= 2;
-                row[i] = 0;
-                moved = true;
-            }
-        }
-        for i in (0..row.len() - 1).rev() {
-            let mut k = i;
-            while k < row.len() - 1 && row[k + 1] == 0 {
-                row.swap(k, k + 1);
-                k += 1;
+/// The signed best from `highscore.txt`, 0 when there is none yet, or why it was not trusted.
+fn load_high_score() -> Result<u64, GameError> {
+    let file_path = profile::path("highscore.txt");
+    let content = match fs::read_to_string(&file_path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(0),
+        Err(e) => return Err(e.into()),
+    };
+    let untrusted = |reason: &str| GameError::CorruptedSave {
+        path: file_path.clone(),
+        reason: reason.to_string(),
+    };
+
+    let mut parts = content.split_whitespace();
+    let score = parts.next().unwrap_or("0");
+    match parts.next() {
+        Some(signature) if signing::verify(score, signature) => Ok(score.parse().unwrap_or(0)),
+        Some(_) => Err(untrusted("its signature does not match")),
+        None if !signing::has_install_key()
+            && stats::load_stats().is_ok_and(|stats| !stats.scores_signed) =>
+        {
+            let legacy: u64 = score.parse().unwrap_or(0);
+            if let Err(e) = write_high_score(legacy) {
+                logging::error(i18n::tf("error.sign_legacy", &[("error", &e)]));
             }
+            Ok(legacy)
         }
+        None => Err(untrusted("it is not signed")),
     }
+}
 
-    if initial_board == *game_board {
-        moved = false;
-    }
-
-    moved
+/// Like `load_high_score`, counting an untrusted file as no score; the reconciliation at
+/// startup is what reports it.
+fn read_high_score() -> u64 {
+    load_high_score().unwrap_or_else(|e| {
+        tracing::warn!(error = %e, "ignoring high score");
+        0
+    })
 }
 
-fn move_up(game_board: &mut [Vec<u32>]) -> bool {
-    let mut moved = false;
-    let initial_board = game_board.to_vec();
-
-    for col in 0..game_board[0].len() {
-        for row in 1..game_board.len() {
-            let mut k = row;
-            while k > 0 && game_board[k - 1][col] == 0 {
-                game_board[k - 1][col] = game_board[k][col];
-                game_board[k][col] = 0;
-                moved = true;
-                k -= 1;
-            }
-        }
-        for row in 0..game_board.len() - 1 {
-            if game_board[row][col] != 0 && game_board[row][col] == game_board[row + 1][col] {
-                game_board[row][col] *= 2;
-                game_board[row + 1][col] = 0;
-                moved = true;
-            }
-        }
-        for row in 1..game_board.len() {
-            let mut k = row;
-            while k > 0 && game_board[k - 1][col] == 0 {
-                game_board[k - 1][col] = game_board[k][col];
-                game_board[k][col] = 0;
-                k -= 1;
-            }
-        }
-    }
+fn write_high_score(high_score: u64) -> Result<(), GameError> {
+    let score = high_score.to_string();
+    let signature = signing::sign(&score)?;
+    let mut file = File::create(profile::path("highscore.txt"))?;
+    write!(file, "{} {}", score, signature)?;
 
-    if initial_board == *game_board {
-        moved = false;
+    let mut stats = stats::load_stats()?;
+    let recorded = stats.best_score.as_ref().and_then(|best| best.verified());
+    let improved = recorded.is_none_or(|best| best < high_score);
+    if improved {
+        stats.best_score = Some(stats::SignedScore::new(high_score)?);
     }
-
-    moved
+    if improved || !stats.scores_signed {
+        stats.scores_signed = true;
+        stats::save_stats(&stats)?;
+    }
+    Ok(())
 }
 
-fn move_down(game_board: &mut [Vec<u32>]) -> bool {
-    let mut moved = false;
-    let initial_board = game_board.to_vec();
-
-    for col in 0..game_board[0].len() {
-        for row in (0..game_board.len() - 1).rev() {
-            let mut k = row;
-            while k < game_board.len() - 1 && game_board[k + 1][col] == 0 {
-                game_board[k + 1][col] = game_board[k][col];
-                game_board[k][col] = 0;
-                moved = true;
-                k += 1;
+fn key_direction(code: KeyCode) -> Option<Direction> {
+    let direction = match code {
+        KeyCode::Up => Some(Direction::Up),
+        KeyCode::Down => Some(Direction::Down),
+        KeyCode::Left => Some(Direction::Left),
+        KeyCode::Right => Some(Direction::Right),
+        KeyCode::Char(c) => match (config::key_bindings(), c.to_ascii_lowercase()) {
+            (config::KeyBindings::Ijkl, 'i') | (config::KeyBindings::Numpad, '8') => {
+                Some(Direction::Up)
             }
-        }
-        for row in (0..game_board.len() - 1).rev() {
-            if game_board[row][col] != 0 && game_board[row][col] == game_board[row + 1][col] {
-                game_board[row + 1][col] *= 2;
-                game_board[row][col] = 0;
-                moved = true;
+            (config::KeyBindings::Ijkl, 'k') | (config::KeyBindings::Numpad, '2') => {
+                Some(Direction::Down)
             }
-        }
-        for row in (0..game_board.len() - 1).rev() {
-            let mut k = row;
-            while k < game_board.len() - 1 && game_board[k + 1][col] == 0 {
-                game_board[k + 1][col] = game_board[k][col];
-                game_board[k][col] = 0;
-                k += 1;
+            (config::KeyBindings::Ijkl, 'j') | (config::KeyBindings::Numpad, '4') => {
+                Some(Direction::Left)
             }
-        }
-    }
-
-    if initial_board == *game_board {
-        moved = false;
-    }
-
-    moved
+            (config::KeyBindings::Ijkl, 'l') | (config::KeyBindings::Numpad, '6') => {
+                Some(Direction::Right)
+            }
+            _ => None,
+        },
+        _ => None,
+    };
+    direction.filter(|_| repeat::accept(code))
 }
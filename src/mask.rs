@@ -0,0 +1,101 @@
+use crate::engine::OBSTACLE;
+use std::str::FromStr;
+
+const MAX_SIZE: usize = 16;
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Mask {
+    cells: Vec<Vec<bool>>,
+}
+
+impl Mask {
+    pub fn size(&self) -> usize {
+        self.cells
+            .iter()
+            .map(Vec::len)
+            .chain([self.cells.len()])
+            .max()
+            .unwrap_or(0)
+    }
+
+    pub fn playable(&self, i: usize, j: usize) -> bool {
+        self.cells
+            .get(i)
+            .and_then(|row| row.get(j))
+            .copied()
+            .unwrap_or(false)
+    }
+
+    /// The mask a board's walls make, open wherever the board is not.
+    pub fn walls(board: &[Vec<u64>]) -> Self {
+        Mask {
+            cells: board
+                .iter()
+                .map(|row| row.iter().map(|&val| val != OBSTACLE).collect())
+                .collect(),
+        }
+    }
+
+    /// An empty square board with walls wherever the mask is closed, padding short rows.
+    pub fn board(&self) -> Vec<Vec<u64>> {
+        let size = self.size();
+        (0..size)
+            .map(|i| {
+                (0..size)
+                    .map(|j| if self.playable(i, j) { 0 } else { OBSTACLE })
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn parse_text(text: &str) -> Result<Vec<Vec<bool>>, String> {
+        text.lines()
+            .map(str::trim_end)
+            .filter(|line| !line.is_empty() && !line.starts_with(';'))
+            .map(|line| {
+                line.chars()
+                    .map(|c| match c {
+                        '.' | 'o' => Ok(true),
+                        '#' | 'x' | ' ' => Ok(false),
+                        _ => Err(format!("invalid mask cell '{}', use '.' or '#'", c)),
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn parse_json(text: &str) -> Result<Vec<Vec<bool>>, String> {
+        let rows: Vec<Vec<u8>> = serde_json::from_str(text).map_err(|e| e.to_string())?;
+        rows.into_iter()
+            .map(|row| {
+                row.into_iter()
+                    .map(|cell| match cell {
+                        0 => Ok(false),
+                        1 => Ok(true),
+                        _ => Err(format!("invalid mask cell {}, use 1 or 0", cell)),
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+impl FromStr for Mask {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let cells = if text.trim_start().starts_with('[') {
+            Self::parse_json(text)?
+        } else {
+            Self::parse_text(text)?
+        };
+        let mask = Mask { cells };
+        if !(2..=MAX_SIZE).contains(&mask.size()) {
+            return Err("mask must be between 2 and 16 cells wide".to_string());
+        }
+        if mask.cells.iter().flatten().filter(|&&open| open).count() < 2 {
+            return Err("mask must leave at least two playable cells".to_string());
+        }
+        Ok(mask)
+    }
+}
@@ -0,0 +1,113 @@
+use crate::ai::Evaluation;
+use crate::engine::{apply_move, legal_moves, Direction};
+use rand::{seq::SliceRandom, Rng};
+use std::time::{Duration, Instant};
+
+const ROLLOUT_LIMIT: u32 = 200;
+const EXPLORATION: f64 = std::f64::consts::SQRT_2;
+
+#[derive(Clone, Copy, Debug)]
+pub struct MctsConfig {
+    pub rollouts: u32,
+    pub budget: Option<Duration>,
+}
+
+impl Default for MctsConfig {
+    fn default() -> Self {
+        MctsConfig {
+            rollouts: 200,
+            budget: Some(Duration::from_millis(200)),
+        }
+    }
+}
+
+struct Arm {
+    direction: Direction,
+    board: Vec<Vec<u64>>,
+    visits: u32,
+    total: f64,
+}
+
+impl Arm {
+    fn ucb(&self, parent_visits: u32) -> f64 {
+        if self.visits == 0 {
+            return f64::INFINITY;
+        }
+        let mean = self.total / self.visits as f64;
+        mean + EXPLORATION * ((parent_visits as f64).ln() / self.visits as f64).sqrt()
+    }
+}
+
+fn spawn<R: Rng>(game_board: &mut [Vec<u64>], rng: &mut R) -> bool {
+    let empty_cells: Vec<(usize, usize)> = game_board
+        .iter()
+        .enumerate()
+        .flat_map(|(i, row)| {
+            row.iter()
+                .enumerate()
+                .filter(|&(_, &cell)| cell == 0)
+                .map(move |(j, _)| (i, j))
+        })
+        .collect();
+    let Some(&(i, j)) = empty_cells.choose(rng) else {
+        return false;
+    };
+    game_board[i][j] = if rng.gen_bool(0.9) { 2 } else { 4 };
+    true
+}
+
+fn rollout<R: Rng>(mut game_board: Vec<Vec<u64>>, rng: &mut R) -> f64 {
+    spawn(&mut game_board, rng);
+    let mut survived = 0;
+    while survived < ROLLOUT_LIMIT {
+        let Some(&direction) = legal_moves(&game_board).choose(rng) else {
+            break;
+        };
+        apply_move(&mut game_board, direction);
+        spawn(&mut game_board, rng);
+        survived += 1;
+    }
+    survived as f64 / ROLLOUT_LIMIT as f64
+}
+
+pub fn evaluate_moves<R: Rng>(
+    game_board: &[Vec<u64>],
+    config: &MctsConfig,
+    rng: &mut R,
+) -> Vec<Evaluation> {
+    let mut arms: Vec<Arm> = Direction::ALL
+        .into_iter()
+        .filter_map(|direction| {
+            let mut board = game_board.to_vec();
+            apply_move(&mut board, direction).moved.then_some(Arm {
+                direction,
+                board,
+                visits: 0,
+                total: 0.0,
+            })
+        })
+        .collect();
+    if arms.is_empty() {
+        return Vec::new();
+    }
+
+    let deadline = config.budget.map(|budget| Instant::now() + budget);
+    for played in 0..config.rollouts.max(arms.len() as u32) {
+        if played >= arms.len() as u32 && deadline.is_some_and(|end| Instant::now() >= end) {
+            break;
+        }
+        let arm = arms
+            .iter_mut()
+            .max_by(|a, b| a.ucb(played).total_cmp(&b.ucb(played)))
+            .expect("there is at least one legal move");
+        arm.total += rollout(arm.board.clone(), rng);
+        arm.visits += 1;
+    }
+
+    arms.into_iter()
+        .map(|arm| Evaluation {
+            direction: arm.direction,
+            expected: arm.total / arm.visits.max(1) as f64 * ROLLOUT_LIMIT as f64,
+        })
+        .collect()
+}
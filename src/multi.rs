@@ -0,0 +1,107 @@
+use crate::config::load_config;
+use crate::help;
+use crate::i18n::{label, t, tf};
+use crate::layout::{print_panels, Panel};
+use crate::sound::Sound;
+use crate::theme::initialize_colors;
+use crate::{campaign, key_direction};
+use colored::Color;
+use crossterm::{
+    cursor,
+    event::{read, Event, KeyCode},
+    terminal::{disable_raw_mode, enable_raw_mode, Clear, ClearType},
+    ExecutableCommand,
+};
+use rust_2048_game::game::Game;
+use std::{collections::HashMap, io::stdout};
+
+fn combined_score(games: &[Game]) -> u64 {
+    games.iter().map(Game::score).sum()
+}
+
+fn render_games(games: &[Game], colors: &HashMap<u64, Color>, moves: u32) -> crossterm::Result<()> {
+    let mut stdout: std::io::Stdout = stdout();
+    stdout.execute(Clear(ClearType::All))?;
+    stdout.execute(cursor::MoveTo(0, 0))?;
+
+    let panels: Vec<Panel> = games
+        .iter()
+        .enumerate()
+        .map(|(i, game)| {
+            let key = if game.is_over() {
+                "multi.board_stuck"
+            } else {
+                "multi.board"
+            };
+            Panel {
+                title: tf(key, &[("board", &(i + 1)), ("score", &game.score())]),
+                board: game.board(),
+            }
+        })
+        .collect();
+    print_panels(&panels, colors);
+    println!();
+    println!(
+        " > {} {}",
+        label("label.combined_score"),
+        combined_score(games)
+    );
+    println!(" > {} {}", label("label.moves"), moves);
+    println!();
+    println!(" > {}", t("multi.rule"));
+    println!(" > {}", t("multi.hint"));
+    Ok(())
+}
+
+pub fn run(boards: usize) -> crossterm::Result<()> {
+    let mut colors: HashMap<u64, Color> = HashMap::new();
+    initialize_colors(&mut colors, campaign::active_theme());
+    let mut sound = Sound::new(&load_config().sound);
+    let mut games: Vec<Game> = (0..boards).map(|_| Game::new(4)).collect();
+    let mut moves: u32 = 0;
+
+    enable_raw_mode()?;
+    loop {
+        render_games(&games, &colors, moves)?;
+        if games.iter().all(Game::is_over) {
+            println!(" >> {} <<", t("game.over"));
+            break;
+        }
+
+        if let Event::Key(key_event) = read()? {
+            match key_event.code {
+                KeyCode::Esc | KeyCode::Char('e') | KeyCode::Char('E') => break,
+                KeyCode::Char('m') | KeyCode::Char('M') => {
+                    sound.toggle_mute();
+                }
+                KeyCode::Char('?') | KeyCode::F(1) => {
+                    help::show(t("mode.multi"), t("multi.objective"), &[])?;
+                }
+                code => {
+                    let Some(direction) = key_direction(code) else {
+                        continue;
+                    };
+                    let results: Vec<_> = games
+                        .iter_mut()
+                        .filter(|game| !game.is_over())
+                        .map(|game| game.play(direction))
+                        .filter(|result| result.moved)
+                        .collect();
+                    if let Some(result) = results.first() {
+                        sound.play_move(result);
+                        moves += 1;
+                    }
+                }
+            }
+        }
+    }
+    disable_raw_mode()?;
+    println!(
+        " > {}",
+        tf(
+            "multi.result",
+            &[("boards", &boards), ("score", &combined_score(&games))]
+        )
+    );
+    Ok(())
+}
@@ -0,0 +1,109 @@
+use rodio::{buffer::SamplesBuffer, mixer::Mixer, Sink, Source};
+use std::{
+    f32::consts::TAU,
+    sync::mpsc::{self, Receiver, RecvTimeoutError, Sender},
+    thread,
+    time::{Duration, Instant},
+};
+
+const SAMPLE_RATE: u32 = 22_050;
+const BEAT_SECONDS: f32 = 0.6;
+const TICK: Duration = Duration::from_millis(30);
+const FADE_PER_TICK: f32 = 0.03;
+const DUCK_LEVEL: f32 = 0.4;
+const DUCK_TIME: Duration = Duration::from_millis(250);
+const MILESTONES: [u64; 4] = [0, 128, 512, 1024];
+const CHORDS: [[f32; 3]; 4] = [
+    [220.0, 261.63, 329.63],
+    [174.61, 220.0, 261.63],
+    [261.63, 329.63, 392.0],
+    [196.0, 246.94, 293.66],
+];
+
+pub enum Cue {
+    MaxTile(u64),
+    Duck,
+    Mute(bool),
+}
+
+fn note(samples: &mut Vec<f32>, freqs: &[f32], beats: f32, level: f32) {
+    let length = (beats * BEAT_SECONDS * SAMPLE_RATE as f32) as usize;
+    for n in 0..length {
+        let t = n as f32 / SAMPLE_RATE as f32;
+        let attack = (n as f32 / 400.0).min(1.0);
+        let release = ((length - n) as f32 / 800.0).min(1.0);
+        let value: f32 = freqs.iter().map(|freq| (TAU * freq * t).sin()).sum();
+        samples.push(value / freqs.len().max(1) as f32 * level * attack * release);
+    }
+}
+
+fn layer(index: usize) -> SamplesBuffer {
+    let mut samples: Vec<f32> = Vec::new();
+    for chord in CHORDS {
+        match index {
+            0 => note(&mut samples, &chord, 4.0, 0.5),
+            1 => {
+                for _ in 0..4 {
+                    note(&mut samples, &[chord[0] / 2.0], 0.5, 0.7);
+                    note(&mut samples, &[], 0.5, 0.0);
+                }
+            }
+            2 => {
+                for step in 0..8 {
+                    note(&mut samples, &[chord[step % 3] * 2.0], 0.5, 0.35);
+                }
+            }
+            _ => {
+                note(&mut samples, &[], 3.0, 0.0);
+                note(&mut samples, &[chord[2] * 4.0], 1.0, 0.25);
+            }
+        }
+    }
+    SamplesBuffer::new(1, SAMPLE_RATE, samples)
+}
+
+pub fn start(mixer: &Mixer, volume: f32, muted: bool) -> Sender<Cue> {
+    let (sender, receiver) = mpsc::channel();
+    let sinks: Vec<Sink> = (0..MILESTONES.len())
+        .map(|index| {
+            let sink = Sink::connect_new(mixer);
+            sink.set_volume(0.0);
+            sink.append(layer(index).repeat_infinite());
+            sink
+        })
+        .collect();
+    thread::spawn(move || mix_layers(sinks, receiver, volume, muted));
+    sender
+}
+
+fn mix_layers(sinks: Vec<Sink>, receiver: Receiver<Cue>, volume: f32, mut muted: bool) {
+    let mut levels = vec![0.0f32; sinks.len()];
+    let mut active = 1;
+    let mut ducked_until = Instant::now();
+    loop {
+        match receiver.recv_timeout(TICK) {
+            Ok(Cue::MaxTile(value)) => {
+                active = MILESTONES.iter().filter(|&&tile| value >= tile).count();
+            }
+            Ok(Cue::Duck) => ducked_until = Instant::now() + DUCK_TIME,
+            Ok(Cue::Mute(value)) => muted = value,
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => return,
+        }
+
+        let duck = if Instant::now() < ducked_until {
+            DUCK_LEVEL
+        } else {
+            1.0
+        };
+        for (index, (sink, level)) in sinks.iter().zip(levels.iter_mut()).enumerate() {
+            let target = if muted || index >= active { 0.0 } else { 1.0 };
+            if *level < target {
+                *level = (*level + FADE_PER_TICK).min(target);
+            } else {
+                *level = (*level - FADE_PER_TICK).max(target);
+            }
+            sink.set_volume(*level * duck * volume);
+        }
+    }
+}
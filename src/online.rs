@@ -0,0 +1,213 @@
+use crate::config::OnlineConfig;
+use crate::history::GameRecord;
+use crate::i18n::{t, tf};
+use crate::logging;
+use crate::profile;
+use crate::signing::to_hex;
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{
+    fs,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+const PENDING_FILE: &str = "pending_scores.json";
+const CACHE_FILE: &str = "global_leaderboard.json";
+const TOP: usize = 100;
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Submission {
+    pub player: String,
+    pub mode: String,
+    pub score: u64,
+    pub seed: Option<u64>,
+    pub moves: usize,
+    pub replay_hash: String,
+    pub finished: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct GlobalEntry {
+    pub player: String,
+    pub mode: String,
+    pub score: u64,
+    #[serde(default)]
+    pub finished: u64,
+}
+
+#[derive(Serialize, Deserialize)]
+struct LeaderboardCache {
+    fetched_at: u64,
+    entries: Vec<GlobalEntry>,
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+fn player(config: &OnlineConfig) -> String {
+    if !config.player.is_empty() {
+        return config.player.clone();
+    }
+    profile::active().unwrap_or("anonymous").to_string()
+}
+
+pub fn replay_hash(record: &GameRecord) -> String {
+    let replay = serde_json::to_vec(&(&record.start, &record.moves)).unwrap_or_default();
+    to_hex(&Sha256::digest(replay))
+}
+
+impl Submission {
+    pub fn new(config: &OnlineConfig, record: &GameRecord) -> Self {
+        Submission {
+            player: player(config),
+            mode: record.mode.clone(),
+            score: record.score,
+            seed: record.seed,
+            moves: record.moves.len(),
+            replay_hash: replay_hash(record),
+            finished: record.finished,
+        }
+    }
+}
+
+fn endpoint(config: &OnlineConfig) -> Result<String, Box<dyn std::error::Error>> {
+    if config.endpoint.is_empty() {
+        return Err(t("online.no_endpoint").into());
+    }
+    Ok(format!("{}/scores", config.endpoint.trim_end_matches('/')))
+}
+
+fn agent() -> ureq::Agent {
+    ureq::Agent::config_builder()
+        .timeout_global(Some(Duration::from_secs(5)))
+        .build()
+        .into()
+}
+
+fn signature(secret: &str, body: &str) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(body.as_bytes());
+    to_hex(&mac.finalize().into_bytes())
+}
+
+fn post(config: &OnlineConfig, submission: &Submission) -> Result<(), Box<dyn std::error::Error>> {
+    let body = serde_json::to_string(submission)?;
+    agent()
+        .post(&endpoint(config)?)
+        .header("User-Agent", "rust_2048_game")
+        .header("Content-Type", "application/json")
+        .header("X-Signature", &signature(&config.secret, &body))
+        .send(&body)?;
+    Ok(())
+}
+
+fn load_pending() -> Vec<Submission> {
+    fs::read_to_string(profile::path(PENDING_FILE))
+        .ok()
+        .and_then(|data| serde_json::from_str(&data).ok())
+        .unwrap_or_default()
+}
+
+fn flush(config: &OnlineConfig, mut pending: Vec<Submission>) -> (usize, Vec<Submission>) {
+    let mut sent = 0;
+    while let Some(submission) = pending.first() {
+        if post(config, submission).is_err() {
+            break;
+        }
+        pending.remove(0);
+        sent += 1;
+    }
+    let path = profile::path(PENDING_FILE);
+    let saved = if pending.is_empty() {
+        fs::remove_file(&path).or_else(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => Ok(()),
+            _ => Err(e),
+        })
+    } else {
+        serde_json::to_string(&pending)
+            .map_err(std::io::Error::other)
+            .and_then(|data| fs::write(&path, data))
+    };
+    if let Err(e) = saved {
+        logging::error(tf("error.save_pending", &[("error", &e)]));
+    }
+    (sent, pending)
+}
+
+pub fn submit(config: &OnlineConfig, record: &GameRecord) -> String {
+    let mut pending = load_pending();
+    pending.push(Submission::new(config, record));
+    let (sent, pending) = flush(config, pending);
+    match (sent, pending.len()) {
+        (_, 0) => tf("online.submitted", &[("score", &record.score)]),
+        (0, queued) => tf("online.queued", &[("queued", &queued)]),
+        (sent, queued) => tf(
+            "online.partly_sent",
+            &[("sent", &sent), ("queued", &queued)],
+        ),
+    }
+}
+
+fn fetch(config: &OnlineConfig) -> Result<Vec<GlobalEntry>, Box<dyn std::error::Error>> {
+    let mut entries: Vec<GlobalEntry> = agent()
+        .get(&endpoint(config)?)
+        .query("limit", TOP.to_string())
+        .header("User-Agent", "rust_2048_game")
+        .call()?
+        .body_mut()
+        .read_json()?;
+    entries.sort_by_key(|entry| std::cmp::Reverse(entry.score));
+    entries.truncate(TOP);
+    Ok(entries)
+}
+
+pub fn show_global(config: &OnlineConfig) -> Result<(), Box<dyn std::error::Error>> {
+    let pending = load_pending();
+    if !pending.is_empty() {
+        flush(config, pending);
+    }
+    let entries = match fetch(config) {
+        Ok(entries) => {
+            let cache = LeaderboardCache {
+                fetched_at: now(),
+                entries: entries.clone(),
+            };
+            if let Ok(serialized) = serde_json::to_string(&cache) {
+                let _ = fs::write(profile::path(CACHE_FILE), serialized);
+            }
+            println!(" > {}", t("online.leaderboard"));
+            entries
+        }
+        Err(e) => {
+            let cache: LeaderboardCache = fs::read_to_string(profile::path(CACHE_FILE))
+                .ok()
+                .and_then(|data| serde_json::from_str(&data).ok())
+                .ok_or_else(|| tf("online.no_cache", &[("error", &e)]))?;
+            println!(
+                " > {}",
+                tf(
+                    "online.offline_copy",
+                    &[("day", &(cache.fetched_at / 86_400)), ("error", &e)]
+                )
+            );
+            cache.entries
+        }
+    };
+    println!();
+    for (rank, entry) in entries.iter().enumerate() {
+        println!(
+            "   {:>3}. {:<16} {:<18} {}",
+            rank + 1,
+            entry.player,
+            entry.mode,
+            entry.score
+        );
+    }
+    Ok(())
+}
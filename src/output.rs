@@ -0,0 +1,71 @@
+use crate::i18n::tf;
+use crate::logging;
+use clap::ValueEnum;
+use rust_2048_game::engine::legal_moves;
+use serde::Serialize;
+use std::sync::OnceLock;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, ValueEnum)]
+pub enum OutputFormat {
+    #[default]
+    Text,
+    Json,
+}
+
+static FORMAT: OnceLock<OutputFormat> = OnceLock::new();
+
+pub fn set_format(format: OutputFormat) {
+    let _ = FORMAT.set(format);
+}
+
+pub fn is_json() -> bool {
+    FORMAT.get().copied().unwrap_or_default() == OutputFormat::Json
+}
+
+#[derive(Serialize)]
+pub struct TurnState<'a> {
+    pub board: &'a [Vec<u64>],
+    pub score: u64,
+    pub legal_moves: Vec<String>,
+    pub status: &'static str,
+}
+
+impl<'a> TurnState<'a> {
+    pub fn new(board: &'a [Vec<u64>], score: u64) -> Self {
+        let legal_moves: Vec<String> = legal_moves(board)
+            .into_iter()
+            .map(|direction| format!("{:?}", direction).to_lowercase())
+            .collect();
+        let status = if legal_moves.is_empty() {
+            "game_over"
+        } else {
+            "playing"
+        };
+        TurnState {
+            board,
+            score,
+            legal_moves,
+            status,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct RenderedTurn<'a> {
+    #[serde(flatten)]
+    turn: TurnState<'a>,
+    high_score: u64,
+    messages: &'a [String],
+}
+
+pub fn print_turn(board: &[Vec<u64>], score: u64, high_score: u64, messages: &[String]) {
+    let rendered = RenderedTurn {
+        turn: TurnState::new(board, score),
+        high_score,
+        messages,
+    };
+    match serde_json::to_string(&rendered) {
+        Ok(line) => println!("{}", line),
+        Err(e) => logging::error(tf("error.serialize_turn", &[("error", &e)])),
+    }
+}
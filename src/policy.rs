@@ -0,0 +1,88 @@
+use crate::engine::{legal_moves, OBSTACLE};
+use rand::{rngs::StdRng, seq::SliceRandom, thread_rng, Rng, SeedableRng};
+
+const SHUFFLE_ATTEMPTS: usize = 100;
+
+/// Decides what happens once a board has no legal moves left.
+pub trait GameOverPolicy {
+    /// Returns true if the board was changed so that play can go on.
+    fn rescue(&mut self, board: &mut [Vec<u64>]) -> bool;
+}
+
+pub struct EndGame;
+
+impl GameOverPolicy for EndGame {
+    fn rescue(&mut self, _board: &mut [Vec<u64>]) -> bool {
+        false
+    }
+}
+
+/// Clears every tile of the lowest value until a move is possible again.
+pub struct ClearLowest;
+
+impl GameOverPolicy for ClearLowest {
+    fn rescue(&mut self, board: &mut [Vec<u64>]) -> bool {
+        while legal_moves(board).is_empty() {
+            let Some(lowest) = tiles(board).min() else {
+                return false;
+            };
+            for cell in board.iter_mut().flatten() {
+                if *cell == lowest {
+                    *cell = 0;
+                }
+            }
+        }
+        true
+    }
+}
+
+/// Shuffles the tiles around the board, clearing the lowest ones if no shuffle helps.
+pub struct Shuffle {
+    rng: StdRng,
+}
+
+impl Shuffle {
+    pub fn new() -> Self {
+        Self::seeded(thread_rng().gen())
+    }
+
+    pub fn seeded(seed: u64) -> Self {
+        Shuffle {
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+}
+
+impl Default for Shuffle {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GameOverPolicy for Shuffle {
+    fn rescue(&mut self, board: &mut [Vec<u64>]) -> bool {
+        let mut values: Vec<u64> = tiles(board).collect();
+        for _ in 0..SHUFFLE_ATTEMPTS {
+            values.shuffle(&mut self.rng);
+            let cells = board
+                .iter_mut()
+                .flatten()
+                .filter(|cell| **cell != 0 && **cell != OBSTACLE);
+            for (cell, &value) in cells.zip(&values) {
+                *cell = value;
+            }
+            if !legal_moves(board).is_empty() {
+                return true;
+            }
+        }
+        ClearLowest.rescue(board)
+    }
+}
+
+fn tiles(board: &[Vec<u64>]) -> impl Iterator<Item = u64> + '_ {
+    board
+        .iter()
+        .flatten()
+        .copied()
+        .filter(|&cell| cell != 0 && cell != OBSTACLE)
+}
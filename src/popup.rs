@@ -0,0 +1,103 @@
+use crate::{accessibility, frame};
+use colored::Colorize;
+use rust_2048_game::engine::MoveResult;
+use std::{
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+const STAGE: Duration = Duration::from_millis(300);
+const STAGES: u128 = 3;
+
+struct Popups {
+    shown: Instant,
+    stage: u128,
+    merges: Vec<((usize, usize), u64)>,
+}
+
+static POPUPS: Mutex<Option<Popups>> = Mutex::new(None);
+
+impl Popups {
+    fn current_stage(&self) -> u128 {
+        self.shown.elapsed().as_millis() / STAGE.as_millis()
+    }
+
+    fn paint(&self, text: String) -> String {
+        if accessibility::colorless() {
+            return text;
+        }
+        match self.stage {
+            0 => text.bold().to_string(),
+            1 => text,
+            _ => text.dimmed().to_string(),
+        }
+    }
+}
+
+pub fn show(result: &MoveResult) {
+    if frame::net_friendly() {
+        return;
+    }
+    let mut merges: Vec<((usize, usize), u64)> = result
+        .merges
+        .iter()
+        .map(|merge| (merge.position, merge.value))
+        .collect();
+    merges.sort_unstable();
+    if let Ok(mut popups) = POPUPS.lock() {
+        *popups = (!merges.is_empty()).then(|| Popups {
+            shown: Instant::now(),
+            stage: 0,
+            merges,
+        });
+    }
+}
+
+/// Moves the popups on to their next fade stage, returning whether the board needs a redraw.
+pub fn tick() -> bool {
+    let Ok(mut guard) = POPUPS.lock() else {
+        return false;
+    };
+    let Some(popups) = guard.as_mut() else {
+        return false;
+    };
+    let stage = popups.current_stage();
+    if stage == popups.stage {
+        return false;
+    }
+    popups.stage = stage;
+    if stage >= STAGES {
+        *guard = None;
+    }
+    true
+}
+
+pub fn row(i: usize) -> String {
+    let Ok(guard) = POPUPS.lock() else {
+        return String::new();
+    };
+    let Some(popups) = guard.as_ref() else {
+        return String::new();
+    };
+    let labels: Vec<String> = popups
+        .merges
+        .iter()
+        .filter(|((row, _), _)| *row == i)
+        .map(|(_, value)| format!("+{}", value))
+        .collect();
+    if labels.is_empty() {
+        return String::new();
+    }
+    format!(" {}", popups.paint(labels.join(" ")))
+}
+
+pub fn score() -> String {
+    let Ok(guard) = POPUPS.lock() else {
+        return String::new();
+    };
+    let Some(popups) = guard.as_ref() else {
+        return String::new();
+    };
+    let total: u64 = popups.merges.iter().map(|(_, value)| value).sum();
+    format!("  {}", popups.paint(format!("+{}", total)))
+}
@@ -0,0 +1,201 @@
+use crate::engine::{face_back, face_left, Direction, MAX_TILE, OBSTACLE};
+use rand::{distributions::WeightedIndex, prelude::Distribution, seq::IteratorRandom, Rng};
+use serde::{Deserialize, Serialize};
+
+/// How often a spawn is a special tile instead of a 2 or a 4.
+pub const LUCKY_CHANCE: f64 = 0.05;
+/// Relative odds of each special once a spawn is lucky.
+const WEIGHTS: [(Special, u32); 3] = [
+    (Special::Wildcard, 5),
+    (Special::Bomb, 3),
+    (Special::Doubler, 2),
+];
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Special {
+    /// Merges with any numbered tile as if it were its twin.
+    Wildcard,
+    /// Merges with any numbered tile and clears the 3x3 area around the merge.
+    Bomb,
+    /// Merges with any numbered tile and doubles the result.
+    Doubler,
+}
+
+/// A cell of a power-up board, saved as a plain number or as the name of its special.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Tile {
+    Number(u64),
+    Special(Special),
+}
+
+pub const EMPTY: Tile = Tile::Number(0);
+
+impl Tile {
+    /// The tile's worth on the score, nothing for specials and blockers.
+    pub fn value(self) -> u64 {
+        match self {
+            Tile::Number(OBSTACLE) | Tile::Special(_) => 0,
+            Tile::Number(value) => value,
+        }
+    }
+
+    fn movable(self) -> bool {
+        self != EMPTY && self != Tile::Number(OBSTACLE)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Activation {
+    pub special: Special,
+    pub position: (usize, usize),
+    pub value: u64,
+    /// Points on the tiles this bomb cleared, 0 for the other specials.
+    pub cleared: u64,
+}
+
+#[derive(Default, Debug)]
+pub struct PowerMove {
+    pub moved: bool,
+    pub activations: Vec<Activation>,
+    /// Points on tiles the bombs cleared, which the score keeps.
+    pub cleared: u64,
+}
+
+fn merge(first: Tile, second: Tile) -> Option<(Tile, Option<Special>)> {
+    match (first, second) {
+        (Tile::Number(a), Tile::Number(b)) if a == b && a <= MAX_TILE / 2 => {
+            Some((Tile::Number(a * 2), None))
+        }
+        (Tile::Number(value), Tile::Special(special))
+        | (Tile::Special(special), Tile::Number(value)) => {
+            let factor = match special {
+                Special::Doubler => 4,
+                Special::Wildcard | Special::Bomb => 2,
+            };
+            (value <= MAX_TILE / factor).then(|| (Tile::Number(value * factor), Some(special)))
+        }
+        _ => None,
+    }
+}
+
+/// Slides and merges one row toward its start, returning where each special went off.
+fn shift_row(row: &mut [Tile]) -> Vec<(usize, Special, u64)> {
+    let mut activations = Vec::new();
+    let mut start = 0;
+    while start < row.len() {
+        let end = row[start..]
+            .iter()
+            .position(|&tile| tile == Tile::Number(OBSTACLE))
+            .map_or(row.len(), |offset| start + offset);
+        let tiles: Vec<Tile> = row[start..end]
+            .iter()
+            .copied()
+            .filter(|tile| tile.movable())
+            .collect();
+        let mut packed = Vec::new();
+        let mut i = 0;
+        while i < tiles.len() {
+            match tiles.get(i + 1).and_then(|&next| merge(tiles[i], next)) {
+                Some((merged, special)) => {
+                    if let Some(special) = special {
+                        activations.push((start + packed.len(), special, merged.value()));
+                    }
+                    packed.push(merged);
+                    i += 2;
+                }
+                None => {
+                    packed.push(tiles[i]);
+                    i += 1;
+                }
+            }
+        }
+        packed.resize(end - start, EMPTY);
+        row[start..end].copy_from_slice(&packed);
+        start = end + 1;
+    }
+    activations
+}
+
+fn explode(board: &mut [Vec<Tile>], (i, j): (usize, usize)) -> u64 {
+    let mut cleared = 0;
+    for row in i.saturating_sub(1)..=(i + 1).min(board.len() - 1) {
+        for col in j.saturating_sub(1)..=(j + 1).min(board[row].len() - 1) {
+            let tile = &mut board[row][col];
+            if tile.movable() {
+                cleared += tile.value();
+                *tile = EMPTY;
+            }
+        }
+    }
+    cleared
+}
+
+pub fn apply_move(board: &mut [Vec<Tile>], direction: Direction) -> PowerMove {
+    let initial = board.to_vec();
+    let coordinates: Vec<Vec<(usize, usize)>> = (0..board.len())
+        .map(|i| (0..board[i].len()).map(|j| (i, j)).collect())
+        .collect();
+    let coordinates = face_left(&coordinates, direction);
+    let mut turned = face_left(board, direction);
+    let mut activations = Vec::new();
+    for (i, row) in turned.iter_mut().enumerate() {
+        for (j, special, value) in shift_row(row) {
+            activations.push(Activation {
+                special,
+                position: coordinates[i][j],
+                value,
+                cleared: 0,
+            });
+        }
+    }
+    for (row, shifted) in board.iter_mut().zip(face_back(&turned, direction)) {
+        *row = shifted;
+    }
+    for activation in &mut activations {
+        if activation.special == Special::Bomb {
+            activation.cleared = explode(board, activation.position);
+        }
+    }
+    let cleared = activations
+        .iter()
+        .map(|activation| activation.cleared)
+        .sum();
+    PowerMove {
+        moved: initial != board,
+        activations,
+        cleared,
+    }
+}
+
+pub fn can_move(board: &[Vec<Tile>]) -> bool {
+    Direction::ALL
+        .into_iter()
+        .any(|direction| apply_move(&mut board.to_vec(), direction).moved)
+}
+
+pub fn score(board: &[Vec<Tile>]) -> u64 {
+    board.iter().flatten().map(|tile| tile.value()).sum()
+}
+
+/// Drops a 2, a 4 or, now and then, a special tile on a random empty cell.
+pub fn spawn<R: Rng>(board: &mut [Vec<Tile>], rng: &mut R, lucky_chance: f64) -> Option<Tile> {
+    let (i, j) = board
+        .iter()
+        .enumerate()
+        .flat_map(|(i, row)| row.iter().enumerate().map(move |(j, tile)| (i, j, *tile)))
+        .filter(|&(_, _, tile)| tile == EMPTY)
+        .map(|(i, j, _)| (i, j))
+        .choose(rng)?;
+    let tile = if rng.gen_bool(lucky_chance.clamp(0.0, 1.0)) {
+        let weights = WeightedIndex::new(WEIGHTS.iter().map(|&(_, weight)| weight)).ok()?;
+        Tile::Special(WEIGHTS[weights.sample(rng)].0)
+    } else if rng.gen_bool(0.9) {
+        Tile::Number(2)
+    } else {
+        Tile::Number(4)
+    };
+    board[i][j] = tile;
+    Some(tile)
+}
@@ -0,0 +1,141 @@
+use crate::accessibility;
+use crate::broadcast;
+use crate::config::load_config;
+use crate::help;
+use crate::i18n::{label, t, tf};
+use crate::sound::Sound;
+use crate::theme::initialize_colors;
+use crate::{campaign, key_direction, read_high_score, render_board};
+use crossterm::{
+    event::{poll, read, Event, KeyCode},
+    terminal::{disable_raw_mode, enable_raw_mode},
+};
+use rust_2048_game::engine::{apply_move, calculate_score, legal_moves, spawn_random_tile};
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+pub fn run(bpm: u32) -> crossterm::Result<()> {
+    let bpm = bpm.clamp(20, 300);
+    let beat = Duration::from_millis(60_000 / bpm as u64);
+    let mut colors: HashMap<u64, colored::Color> = HashMap::new();
+    initialize_colors(&mut colors, campaign::active_theme());
+    let mut sound = Sound::new(&load_config().sound);
+    let high_score = read_high_score();
+    let mut show_risk = false;
+
+    let mut game_board = vec![vec![0; 4]; 4];
+    spawn_random_tile(&mut game_board);
+    spawn_random_tile(&mut game_board);
+
+    let mut beats: u32 = 0;
+    let mut on_beat: u32 = 0;
+    let mut missed: u32 = 0;
+    let mut moved_this_beat = false;
+    let mut next_beat = Instant::now() + beat;
+
+    enable_raw_mode()?;
+    loop {
+        let pulse = if beats.is_multiple_of(2) {
+            "(*) ( )"
+        } else {
+            "( ) (*)"
+        };
+        let status = if moved_this_beat {
+            t("practice.wait")
+        } else {
+            t("practice.move_now")
+        };
+        let messages = vec![
+            format!(
+                "{} {}",
+                label("label.metronome"),
+                tf(
+                    "practice.metronome",
+                    &[("pulse", &pulse), ("bpm", &bpm), ("status", &status)]
+                )
+            ),
+            format!("{} {}", label("label.on_beat"), on_beat),
+            format!("{} {}", label("label.missed_beats"), missed),
+        ];
+        render_board(
+            &game_board,
+            &colors,
+            calculate_score(&game_board),
+            high_score,
+            show_risk,
+            &messages,
+        )?;
+        if legal_moves(&game_board).is_empty() {
+            println!(" >> {} <<", t("game.over"));
+            broadcast::send_game_over(calculate_score(&game_board), t("broadcast.no_moves"));
+            break;
+        }
+
+        let now = Instant::now();
+        if now >= next_beat {
+            if !moved_this_beat {
+                missed += 1;
+            }
+            beats += 1;
+            moved_this_beat = false;
+            next_beat += beat;
+            sound.play_beat(beats.is_multiple_of(4));
+            continue;
+        }
+        if !poll(next_beat - now)? {
+            continue;
+        }
+
+        if let Event::Key(key_event) = read()? {
+            match key_event.code {
+                KeyCode::Char('e') | KeyCode::Char('E') => break,
+                KeyCode::Char('r') | KeyCode::Char('R') => show_risk = !show_risk,
+                KeyCode::Char('m') | KeyCode::Char('M') => {
+                    sound.toggle_mute();
+                }
+                KeyCode::Char('?') | KeyCode::F(1) => {
+                    help::show(t("mode.practice"), t("practice.objective"), &[])?;
+                    next_beat = Instant::now() + beat;
+                }
+                code => {
+                    let Some(direction) = key_direction(code) else {
+                        continue;
+                    };
+                    if moved_this_beat {
+                        continue;
+                    }
+                    let result = apply_move(&mut game_board, direction);
+                    if result.moved {
+                        sound.play_move(&result);
+                        broadcast::send_move(direction, &result);
+                        accessibility::record_move(direction, &result);
+                        spawn_random_tile(&mut game_board);
+                        moved_this_beat = true;
+                        on_beat += 1;
+                    }
+                }
+            }
+        }
+    }
+    disable_raw_mode()?;
+
+    if beats > 0 {
+        println!(
+            " > {}",
+            tf(
+                "practice.result",
+                &[
+                    ("kept", &(beats - missed)),
+                    ("beats", &beats),
+                    (
+                        "percent",
+                        &format!("{:.0}", (beats - missed) as f64 / beats as f64 * 100.0)
+                    )
+                ]
+            )
+        );
+    }
+    Ok(())
+}
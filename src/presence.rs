@@ -0,0 +1,89 @@
+use crate::config::DiscordConfig;
+use crate::i18n::tf;
+use discord_rich_presence::{
+    activity::{Activity, Timestamps},
+    DiscordIpc, DiscordIpcClient,
+};
+use rust_2048_game::engine::{calculate_score, max_tile};
+use rust_2048_game::game::GameObserver;
+use std::{
+    sync::mpsc::{self, Receiver, RecvTimeoutError, Sender},
+    thread,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+// Discord accepts five presence updates per 20 seconds.
+const UPDATE_INTERVAL: Duration = Duration::from_secs(4);
+
+struct Update {
+    score: u64,
+    max_tile: u64,
+}
+
+pub struct Presence {
+    updates: Sender<Update>,
+}
+
+impl Presence {
+    pub fn start(config: &DiscordConfig, mode: &str) -> Option<Self> {
+        if config.app_id.is_empty() {
+            return None;
+        }
+        let mut client = DiscordIpcClient::new(&config.app_id);
+        client.connect().ok()?;
+        let (updates, receiver) = mpsc::channel();
+        let mode = mode.to_string();
+        thread::spawn(move || publish(client, &mode, receiver));
+        Some(Presence { updates })
+    }
+}
+
+impl GameObserver for Presence {
+    fn on_turn(&mut self, board: &[Vec<u64>]) {
+        let _ = self.updates.send(Update {
+            score: calculate_score(board),
+            max_tile: max_tile(board),
+        });
+    }
+}
+
+fn publish(mut client: DiscordIpcClient, mode: &str, receiver: Receiver<Update>) {
+    let started = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_millis() as i64)
+        .unwrap_or(0);
+    let mut latest: Option<Update> = None;
+    let mut last_sent: Option<Instant> = None;
+    loop {
+        let received = match (&latest, last_sent) {
+            (Some(_), Some(sent)) => {
+                receiver.recv_timeout(UPDATE_INTERVAL.saturating_sub(sent.elapsed()))
+            }
+            _ => receiver.recv().map_err(|_| RecvTimeoutError::Disconnected),
+        };
+        match received {
+            Ok(update) => latest = Some(update),
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => break,
+        }
+        if last_sent.is_some_and(|sent| sent.elapsed() < UPDATE_INTERVAL) {
+            continue;
+        }
+        if let Some(update) = latest.take() {
+            let state = tf(
+                "presence.state",
+                &[("score", &update.score), ("tile", &update.max_tile)],
+            );
+            let activity = Activity::new()
+                .details(mode)
+                .state(state)
+                .timestamps(Timestamps::new().start(started));
+            if client.set_activity(activity).is_err() {
+                break;
+            }
+            last_sent = Some(Instant::now());
+        }
+    }
+    let _ = client.clear_activity();
+    let _ = client.close();
+}
@@ -0,0 +1,112 @@
+use crate::error::GameError;
+use crate::i18n::{t, tf};
+use crossterm::{
+    cursor,
+    event::{read, Event, KeyCode},
+    terminal::{disable_raw_mode, enable_raw_mode, Clear, ClearType},
+    ExecutableCommand,
+};
+use std::{
+    fs,
+    io::stdout,
+    path::{Path, PathBuf},
+    sync::OnceLock,
+};
+
+const PROFILES_DIR: &str = "profiles";
+/// Everything a player would miss when moving to another machine.
+pub const DATA_FILES: [&str; 8] = [
+    "game_state.json",
+    "stats.json",
+    "history.json",
+    "campaign.json",
+    "config.toml",
+    "highscore.txt",
+    "scores.json",
+    "lucky_state.json",
+];
+
+static ACTIVE: OnceLock<Option<String>> = OnceLock::new();
+
+fn valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+pub fn activate(name: Option<String>) -> Result<(), GameError> {
+    if let Some(name) = &name {
+        if !valid_name(name) {
+            return Err(GameError::Config(tf(
+                "profile.bad_name",
+                &[("name", &name)],
+            )));
+        }
+        fs::create_dir_all(Path::new(PROFILES_DIR).join(name))?;
+    }
+    ACTIVE
+        .set(name)
+        .map_err(|_| GameError::Config(t("profile.already_active").to_string()))
+}
+
+pub fn active() -> Option<&'static str> {
+    ACTIVE.get().and_then(|name| name.as_deref())
+}
+
+pub fn path(file: &str) -> PathBuf {
+    match active() {
+        Some(name) => Path::new(PROFILES_DIR).join(name).join(file),
+        None => PathBuf::from(file),
+    }
+}
+
+pub fn list() -> Vec<String> {
+    let mut names: Vec<String> = fs::read_dir(PROFILES_DIR)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok())
+                .filter(|entry| entry.path().is_dir())
+                .filter_map(|entry| entry.file_name().into_string().ok())
+                .filter(|name| valid_name(name))
+                .collect()
+        })
+        .unwrap_or_default();
+    names.sort();
+    names
+}
+
+pub fn pick(names: &[String]) -> crossterm::Result<Option<Option<String>>> {
+    let mut stdout = stdout();
+    stdout.execute(Clear(ClearType::All))?;
+    stdout.execute(cursor::MoveTo(0, 0))?;
+    println!(" > {}", t("profile.who"));
+    println!();
+    println!("   0. {}", t("profile.default"));
+    for (i, name) in names.iter().enumerate().take(9) {
+        println!("   {}. {}", i + 1, name);
+    }
+    println!();
+    println!(" > {}", t("profile.hint"));
+
+    enable_raw_mode()?;
+    let choice = loop {
+        if let Event::Key(key_event) = read()? {
+            match key_event.code {
+                KeyCode::Esc => break None,
+                KeyCode::Char('0') => break Some(None),
+                KeyCode::Char(c) => {
+                    let Some(number) = c.to_digit(10) else {
+                        continue;
+                    };
+                    if let Some(name) = names.get(number as usize - 1) {
+                        break Some(Some(name.clone()));
+                    }
+                }
+                _ => {}
+            }
+        }
+    };
+    disable_raw_mode()?;
+    Ok(choice)
+}
@@ -0,0 +1,285 @@
+use crate::accessibility;
+use crate::broadcast;
+use crate::config::load_config;
+use crate::frame;
+use crate::help;
+use crate::i18n::{label, t, tf};
+use crate::logging;
+use crate::sound::Sound;
+use crate::stats::{load_stats, save_stats, ChallengeRecord, Stats};
+use crate::theme::initialize_colors;
+use crate::{campaign, key_direction, read_high_score, render_board};
+use crossterm::{
+    cursor,
+    event::{read, Event, KeyCode},
+    terminal::{disable_raw_mode, enable_raw_mode, Clear, ClearType},
+    ExecutableCommand,
+};
+use rust_2048_game::engine::{apply_move, calculate_score, legal_moves, max_tile, Spawner};
+use serde::Deserialize;
+use std::{collections::HashMap, io::stdout};
+
+const PACK: &str = include_str!("../puzzles/dirty_dozen.ron");
+
+#[derive(Deserialize)]
+pub struct Puzzle {
+    pub name: String,
+    pub board: Vec<Vec<u64>>,
+    pub goal: u64,
+    pub moves: u32,
+    pub par: u32,
+    pub seed: u64,
+}
+
+impl Puzzle {
+    fn objective(&self) -> String {
+        tf(
+            "puzzle.objective",
+            &[("goal", &self.goal), ("moves", &self.moves)],
+        )
+    }
+
+    fn key(&self) -> String {
+        self.name.clone()
+    }
+
+    /// Three stars at par, two within half the slack left above it, one for any solve.
+    pub fn stars(&self, used: u32) -> u32 {
+        if used <= self.par {
+            3
+        } else if used <= self.par + (self.moves - self.par) / 2 {
+            2
+        } else {
+            1
+        }
+    }
+}
+
+pub fn pack() -> Result<Vec<Puzzle>, String> {
+    ron::from_str(PACK).map_err(|e| e.to_string())
+}
+
+fn stars_label(stars: u32) -> String {
+    format!(
+        "{}{}",
+        "*".repeat(stars as usize),
+        ".".repeat(3 - stars as usize)
+    )
+}
+
+fn status(puzzle: &Puzzle, stats: &Stats) -> String {
+    match stats.puzzles.get(&puzzle.key()) {
+        Some(ChallengeRecord {
+            best_moves: Some(best),
+            ..
+        }) => format!(
+            "{} {}",
+            stars_label(puzzle.stars(*best)),
+            tf("puzzle.best", &[("moves", best)])
+        ),
+        Some(record) => format!(
+            "    {}",
+            tf("challenge.attempts", &[("attempts", &record.attempts)])
+        ),
+        None => format!("    {}", t("challenge.new")),
+    }
+}
+
+fn select_puzzle(puzzles: &[Puzzle], stats: &Stats) -> crossterm::Result<Option<usize>> {
+    let mut selected = puzzles
+        .iter()
+        .position(|puzzle| !stats.puzzles.contains_key(&puzzle.key()))
+        .unwrap_or(0);
+    loop {
+        frame::invalidate();
+        let mut stdout = stdout();
+        stdout.execute(Clear(ClearType::All))?;
+        stdout.execute(cursor::MoveTo(0, 0))?;
+        let earned: u32 = puzzles
+            .iter()
+            .filter_map(|puzzle| {
+                let best = stats.puzzles.get(&puzzle.key())?.best_moves?;
+                Some(puzzle.stars(best))
+            })
+            .sum();
+        println!(
+            " > {}",
+            tf(
+                "puzzle.pack",
+                &[("earned", &earned), ("stars", &(puzzles.len() * 3))]
+            )
+        );
+        println!();
+        for (i, puzzle) in puzzles.iter().enumerate() {
+            let marker = if i == selected { ">" } else { " " };
+            println!(
+                " {} {:>2}. {:<16} {:<28} {}",
+                marker,
+                i + 1,
+                puzzle.name,
+                puzzle.objective(),
+                status(puzzle, stats)
+            );
+        }
+        println!();
+        println!(" > {}", t("puzzle.hint"));
+
+        if let Event::Key(key_event) = read()? {
+            match key_event.code {
+                KeyCode::Char('e') | KeyCode::Char('E') | KeyCode::Esc => return Ok(None),
+                KeyCode::Up => selected = selected.saturating_sub(1),
+                KeyCode::Down => selected = (selected + 1).min(puzzles.len() - 1),
+                KeyCode::Enter => return Ok(Some(selected)),
+                _ => {}
+            }
+        }
+    }
+}
+
+pub fn run(number: Option<usize>) -> crossterm::Result<()> {
+    let puzzles = match pack() {
+        Ok(puzzles) => puzzles,
+        Err(e) => {
+            logging::error(tf("error.puzzle_pack", &[("error", &e)]));
+            return Ok(());
+        }
+    };
+    let mut stats = match load_stats() {
+        Ok(stats) => stats,
+        Err(e) => {
+            logging::error(tf("error.load_stats", &[("error", &e)]));
+            return Ok(());
+        }
+    };
+    let index = match number {
+        Some(number) if (1..=puzzles.len()).contains(&number) => number - 1,
+        Some(number) => {
+            println!(
+                " > {}",
+                tf(
+                    "puzzle.unknown",
+                    &[("number", &number), ("count", &puzzles.len())]
+                )
+            );
+            return Ok(());
+        }
+        None => {
+            enable_raw_mode()?;
+            let selected = select_puzzle(&puzzles, &stats);
+            disable_raw_mode()?;
+            match selected? {
+                Some(index) => index,
+                None => return Ok(()),
+            }
+        }
+    };
+    let puzzle = &puzzles[index];
+
+    let mut colors: HashMap<u64, colored::Color> = HashMap::new();
+    initialize_colors(&mut colors, campaign::active_theme());
+    let mut sound = Sound::new(&load_config().sound);
+    let high_score = read_high_score();
+
+    let mut game_board = puzzle.board.clone();
+    let mut spawner = Spawner::seeded(puzzle.seed);
+    let mut used: u32 = 0;
+    let mut show_risk = false;
+
+    enable_raw_mode()?;
+    let outcome = loop {
+        let messages = vec![
+            format!("{} {}. {}", label("label.puzzle"), index + 1, puzzle.name),
+            format!("{} {}", label("label.objective"), puzzle.objective()),
+            format!(
+                "{} {}",
+                label("label.moves_used"),
+                tf(
+                    "puzzle.used",
+                    &[
+                        ("used", &used),
+                        ("moves", &puzzle.moves),
+                        ("par", &puzzle.par)
+                    ]
+                )
+            ),
+        ];
+        render_board(
+            &game_board,
+            &colors,
+            calculate_score(&game_board),
+            high_score,
+            show_risk,
+            &messages,
+        )?;
+
+        let max_tile = max_tile(&game_board);
+        if max_tile >= puzzle.goal {
+            break Some(true);
+        }
+        if legal_moves(&game_board).is_empty() || used >= puzzle.moves {
+            break Some(false);
+        }
+
+        if let Event::Key(key_event) = read()? {
+            match key_event.code {
+                KeyCode::Char('e') | KeyCode::Char('E') => break None,
+                KeyCode::Char('r') | KeyCode::Char('R') => show_risk = !show_risk,
+                KeyCode::Char('m') | KeyCode::Char('M') => {
+                    sound.toggle_mute();
+                }
+                KeyCode::Char('?') | KeyCode::F(1) => help::show(
+                    t("mode.puzzle"),
+                    &tf("puzzle.help", &[("objective", &puzzle.objective())]),
+                    &[],
+                )?,
+                code => {
+                    if let Some(direction) = key_direction(code) {
+                        let result = apply_move(&mut game_board, direction);
+                        if result.moved {
+                            sound.play_move(&result);
+                            broadcast::send_move(direction, &result);
+                            accessibility::record_move(direction, &result);
+                            spawner.spawn(&mut game_board);
+                            used += 1;
+                        }
+                    }
+                }
+            }
+        }
+    };
+    disable_raw_mode()?;
+
+    let Some(solved) = outcome else {
+        return Ok(());
+    };
+    let record = stats.puzzles.entry(puzzle.key()).or_default();
+    record.attempts += 1;
+    if solved {
+        record.completions += 1;
+        record.best_moves = Some(record.best_moves.map_or(used, |best| best.min(used)));
+        println!(
+            " >> {} <<",
+            tf(
+                "puzzle.solved",
+                &[
+                    ("moves", &used),
+                    ("stars", &stars_label(puzzle.stars(used)))
+                ]
+            )
+        );
+    } else if used >= puzzle.moves {
+        println!(" >> {} <<", t("puzzle.out_of_moves"));
+    } else {
+        println!(" >> {} <<", t("puzzle.no_moves"));
+    }
+    let reason = if solved {
+        t("broadcast.puzzle_solved")
+    } else {
+        t("broadcast.puzzle_failed")
+    };
+    broadcast::send_game_over(calculate_score(&game_board), reason);
+    if let Err(e) = save_stats(&stats) {
+        logging::error(tf("error.save_stats", &[("error", &e)]));
+    }
+    Ok(())
+}
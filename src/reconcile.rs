@@ -0,0 +1,231 @@
+use crate::history::{load_history, GameRecord};
+use crate::i18n::{t, tf};
+use crate::load_high_score;
+use crate::scores::{self, ScoreKey};
+use crate::stats::{load_stats, save_stats, SignedScore};
+use rust_2048_game::engine::{apply_move, calculate_score, Spawner};
+
+/// Whether a finished game counts toward the classic best: 4x4 Normal with the standard
+/// rules, whatever the spawn bias or help it had.
+fn counts_as_classic(record: &GameRecord) -> bool {
+    let mut parts = record.mode.split('-');
+    parts.next() == Some("classic")
+        && record.start.len() == 4
+        && parts.enumerate().all(|(i, part)| {
+            (i == 0 && part == "normal")
+                || matches!(part, "gentle" | "evil" | "assisted" | "continued")
+        })
+}
+
+/// An opening as a fresh game deals it: nothing but 2s and 4s, at most two of them.
+fn is_opening(board: &[Vec<u64>]) -> bool {
+    let tiles: Vec<u64> = board
+        .iter()
+        .flatten()
+        .copied()
+        .filter(|&cell| cell != 0)
+        .collect();
+    tiles.len() <= 2 && tiles.iter().all(|&tile| matches!(tile, 2 | 4))
+}
+
+/// The spawner that dealt a seeded game, when it deals the recorded opening.
+fn dealer(record: &GameRecord, seed: u64) -> Option<Spawner> {
+    let mut spawner = Spawner::seeded(seed);
+    spawner.set_strategy(record.spawn_bias().strategy());
+    let mut opening = vec![vec![0; 4]; 4];
+    spawner.spawn(&mut opening);
+    spawner.spawn(&mut opening);
+    (opening == record.start).then_some(spawner)
+}
+
+/// The score a game replays to, when its moves lead to the board and score it recorded.
+/// Every move has to slide something and be followed by a 2 or a 4 on an empty cell,
+/// and a seeded game has to draw exactly the tiles its seed deals.
+fn replayed_score(record: &GameRecord) -> Option<u64> {
+    if record.start.iter().any(|row| row.len() != 4) || !is_opening(&record.start) {
+        return None;
+    }
+    let mut spawner = match record.seed {
+        Some(seed) => Some(dealer(record, seed)?),
+        None => None,
+    };
+    let mut game_board = record.start.clone();
+    for replay_step in &record.moves {
+        if !apply_move(&mut game_board, replay_step.direction).moved {
+            return None;
+        }
+        let (i, j, value) = replay_step.spawn?;
+        if !matches!(value, 2 | 4) || *game_board.get(i)?.get(j)? != 0 {
+            return None;
+        }
+        match &mut spawner {
+            Some(spawner) => {
+                let dealt = spawner.next_value();
+                if spawner.spawn(&mut game_board)? != (i, j) || dealt != value {
+                    return None;
+                }
+            }
+            None => game_board[i][j] = value,
+        }
+    }
+    let score = calculate_score(&game_board);
+    (game_board == record.board && score == record.score).then_some(score)
+}
+
+pub fn run() -> Vec<String> {
+    let mut report = Vec::new();
+    let file_score = load_high_score().unwrap_or_else(|e| {
+        report.push(tf("reconcile.bad_high_score", &[("error", &e)]));
+        0
+    });
+    let mut stats = match load_stats() {
+        Ok(stats) => Some(stats),
+        Err(e) => {
+            report.push(tf("reconcile.bad_stats", &[("error", &e)]));
+            None
+        }
+    };
+    let stats_score = match stats.as_ref().and_then(|stats| stats.best_score.as_ref()) {
+        Some(best) => match best.verified() {
+            Some(score) => score,
+            None => {
+                report.push(tf("reconcile.unverified", &[("score", &best.score)]));
+                0
+            }
+        },
+        None => 0,
+    };
+    let table_score = scores::best(ScoreKey::classic());
+    let history = load_history().unwrap_or_else(|e| {
+        report.push(tf("reconcile.bad_history", &[("error", &e)]));
+        Vec::new()
+    });
+    let replay_score = history
+        .iter()
+        .filter(|record| counts_as_classic(record))
+        .filter_map(replayed_score)
+        .max()
+        .unwrap_or(0);
+    let (source, best) = [
+        ("reconcile.from_file", file_score),
+        ("reconcile.from_stats", stats_score),
+        ("reconcile.from_table", table_score),
+        ("reconcile.from_replay", replay_score),
+    ]
+    .into_iter()
+    .rev()
+    .max_by_key(|&(_, score)| score)
+    .unwrap_or(("reconcile.from_file", 0));
+
+    if file_score < best || table_score < best {
+        match scores::record(ScoreKey::classic(), best) {
+            Ok(()) => report.push(tf(
+                "reconcile.restored",
+                &[
+                    ("score", &best),
+                    ("source", &t(source)),
+                    ("file", &file_score),
+                ],
+            )),
+            Err(e) => report.push(tf("reconcile.restore_failed", &[("error", &e)])),
+        }
+    } else if let Some(stats) = stats.as_mut().filter(|_| stats_score < best) {
+        match SignedScore::new(best) {
+            Ok(signed) => {
+                stats.best_score = Some(signed);
+                match save_stats(stats) {
+                    Ok(()) => report.push(tf(
+                        "reconcile.updated",
+                        &[("old", &stats_score), ("new", &best)],
+                    )),
+                    Err(e) => report.push(tf("reconcile.update_failed", &[("error", &e)])),
+                }
+            }
+            Err(e) => report.push(tf("reconcile.sign_failed", &[("error", &e)])),
+        }
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::GameConfig;
+    use crate::history::Recorder;
+    use rust_2048_game::engine::Direction;
+    use rust_2048_game::scoring::Combo;
+
+    /// A classic game dealt by `seed`, always taking the first direction that moves.
+    fn seeded_game(seed: u64, moves: usize) -> GameRecord {
+        let mut spawner = Spawner::seeded(seed);
+        let mut game_board = vec![vec![0; 4]; 4];
+        spawner.spawn(&mut game_board);
+        spawner.spawn(&mut game_board);
+        let mut recorder = Recorder::new(&game_board, &GameConfig::default(), Combo::default());
+        for _ in 0..moves {
+            let Some((direction, moved)) = Direction::ALL.into_iter().find_map(|direction| {
+                let mut moved = game_board.clone();
+                apply_move(&mut moved, direction)
+                    .moved
+                    .then_some((direction, moved))
+            }) else {
+                break;
+            };
+            game_board = moved.clone();
+            spawner.spawn(&mut game_board);
+            recorder.record(direction, &moved, &game_board);
+        }
+        let mut record = recorder.finish("classic-normal", &game_board);
+        record.seed = Some(seed);
+        record
+    }
+
+    #[test]
+    fn only_normal_classic_games_count() {
+        let mut record = seeded_game(5, 0);
+        for (mode, counts) in [
+            ("classic", true),
+            ("classic-normal", true),
+            ("classic-normal-evil-assisted", true),
+            ("classic-hard", false),
+            ("classic-normal-combo", false),
+            ("gravity-down", false),
+        ] {
+            record.mode = mode.to_string();
+            assert_eq!(counts_as_classic(&record), counts, "{}", mode);
+        }
+    }
+
+    #[test]
+    fn honest_game_replays_to_its_score() {
+        let record = seeded_game(5, 40);
+        assert_eq!(record.moves.len(), 40);
+        assert_eq!(replayed_score(&record), Some(record.score));
+
+        let mut unseeded = record.clone();
+        unseeded.seed = None;
+        assert_eq!(replayed_score(&unseeded), Some(record.score));
+    }
+
+    #[test]
+    fn doctored_games_do_not_replay() {
+        let record = seeded_game(5, 40);
+
+        let mut inflated = record.clone();
+        inflated.score += 4;
+        assert_eq!(replayed_score(&inflated), None);
+
+        let mut other_seed = record.clone();
+        other_seed.seed = Some(6);
+        assert_eq!(replayed_score(&other_seed), None);
+
+        let mut lucky = record.clone();
+        let (i, j, _) = lucky.moves[10].spawn.unwrap();
+        lucky.moves[10].spawn = Some((i, j, 1024));
+        assert_eq!(replayed_score(&lucky), None);
+
+        let mut rigged = record.clone();
+        rigged.start[0] = vec![1024, 1024, 0, 0];
+        assert_eq!(replayed_score(&rigged), None);
+    }
+}
@@ -0,0 +1,167 @@
+use crate::accessibility;
+use crate::broadcast;
+use crate::config::load_config;
+use crate::help;
+use crate::i18n::{self, label, t, tf};
+use crate::sound::Sound;
+use crate::theme::initialize_colors;
+use crate::{campaign, key_direction, read_high_score, render_board};
+use crossterm::{
+    event::{read, Event, KeyCode},
+    terminal::{disable_raw_mode, enable_raw_mode},
+};
+use rust_2048_game::engine::{
+    apply_move, calculate_score, legal_moves, max_tile, spawn_random_tile,
+};
+use std::collections::HashMap;
+
+struct Player {
+    name: String,
+    moves: u32,
+    points: u64,
+}
+
+pub fn run(names: Vec<String>, turn_moves: u32, budget: u32, goal: u64) -> crossterm::Result<()> {
+    let names = if names.is_empty() {
+        vec![i18n::player(1), i18n::player(2)]
+    } else {
+        names
+    };
+    let mut players: Vec<Player> = names
+        .into_iter()
+        .map(|name| Player {
+            name,
+            moves: 0,
+            points: 0,
+        })
+        .collect();
+    if players.len() < 2 {
+        println!(" > {}", t("relay.too_few"));
+        return Ok(());
+    }
+    let turn_moves = turn_moves.max(1);
+
+    let mut colors: HashMap<u64, colored::Color> = HashMap::new();
+    initialize_colors(&mut colors, campaign::active_theme());
+    let mut sound = Sound::new(&load_config().sound);
+    let high_score = read_high_score();
+
+    let mut game_board = vec![vec![0; 4]; 4];
+    spawn_random_tile(&mut game_board);
+    spawn_random_tile(&mut game_board);
+
+    let mut used: u32 = 0;
+    let mut current = 0;
+    let mut show_risk = false;
+
+    enable_raw_mode()?;
+    // The locale key shown here and sent to broadcast listeners.
+    let outcome = loop {
+        let max_tile = max_tile(&game_board);
+        if max_tile >= goal {
+            break "relay.solved";
+        }
+        if legal_moves(&game_board).is_empty() {
+            break "relay.no_moves";
+        }
+        if used >= budget {
+            break "relay.out_of_moves";
+        }
+
+        let left_in_turn = turn_moves - players[current].moves % turn_moves;
+        let mut messages = vec![
+            tf(
+                "relay.to_play",
+                &[("name", &players[current].name), ("moves", &left_in_turn)],
+            ),
+            format!("{} {}", label("label.goal"), goal),
+            format!(
+                "{} {}/{}",
+                label("label.shared_budget"),
+                budget - used,
+                budget
+            ),
+        ];
+        for player in &players {
+            messages.push(format!(
+                "{:<13} : {}",
+                player.name,
+                tf(
+                    "versus.points",
+                    &[("points", &player.points), ("moves", &player.moves)]
+                )
+            ));
+        }
+        render_board(
+            &game_board,
+            &colors,
+            calculate_score(&game_board),
+            high_score,
+            show_risk,
+            &messages,
+        )?;
+
+        if let Event::Key(key_event) = read()? {
+            match key_event.code {
+                KeyCode::Char('e') | KeyCode::Char('E') => break "relay.abandoned",
+                KeyCode::Char('r') | KeyCode::Char('R') => show_risk = !show_risk,
+                KeyCode::Char('m') | KeyCode::Char('M') => {
+                    sound.toggle_mute();
+                }
+                KeyCode::Char('?') | KeyCode::F(1) => help::show(
+                    t("mode.relay"),
+                    &tf(
+                        "relay.objective",
+                        &[("goal", &goal), ("turn", &turn_moves), ("budget", &budget)],
+                    ),
+                    &[],
+                )?,
+                code => {
+                    if let Some(direction) = key_direction(code) {
+                        let result = apply_move(&mut game_board, direction);
+                        if result.moved {
+                            sound.play_move(&result);
+                            broadcast::send_move(direction, &result);
+                            accessibility::record_move(direction, &result);
+                            spawn_random_tile(&mut game_board);
+                            used += 1;
+                            let player = &mut players[current];
+                            player.moves += 1;
+                            player.points += result.merges.iter().map(|m| m.value).sum::<u64>();
+                            if player.moves.is_multiple_of(turn_moves) {
+                                current = (current + 1) % players.len();
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    };
+    disable_raw_mode()?;
+    broadcast::send_game_over(calculate_score(&game_board), t(outcome));
+
+    println!(
+        " >> {} <<",
+        tf(
+            "relay.result",
+            &[
+                ("outcome", &t(outcome)),
+                ("used", &used),
+                ("budget", &budget)
+            ]
+        )
+    );
+    let team_points: u64 = players.iter().map(|player| player.points).sum();
+    for player in &players {
+        println!(
+            " > {:<13} : {}",
+            player.name,
+            tf(
+                "versus.points",
+                &[("points", &player.points), ("moves", &player.moves)]
+            )
+        );
+    }
+    println!(" > {} {}", label("label.team_score"), team_points);
+    Ok(())
+}
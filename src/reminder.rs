@@ -0,0 +1,107 @@
+use crate::challenge::{daily_index, CHALLENGES};
+use crate::config::{load_config, ReminderConfig};
+use crate::i18n::{t, tf};
+use crate::logging;
+use crate::stats::{load_stats, save_stats, today};
+use std::{process::Command, thread, time::Duration};
+
+const CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+fn parse_minutes(text: &str) -> Option<u32> {
+    let (hours, minutes) = text.trim().split_once(':')?;
+    let (hours, minutes): (u32, u32) = (hours.parse().ok()?, minutes.parse().ok()?);
+    (hours < 24 && minutes < 60).then_some(hours * 60 + minutes)
+}
+
+fn local_minutes() -> Option<u32> {
+    let output = Command::new("date").arg("+%H:%M").output().ok()?;
+    parse_minutes(&String::from_utf8_lossy(&output.stdout))
+}
+
+fn is_quiet(now: u32, start: u32, end: u32) -> bool {
+    if start <= end {
+        (start..end).contains(&now)
+    } else {
+        now >= start || now < end
+    }
+}
+
+fn notify(title: &str, body: &str) -> std::io::Result<()> {
+    let status = if cfg!(target_os = "macos") {
+        Command::new("osascript")
+            .arg("-e")
+            .arg(format!(
+                "display notification {:?} with title {:?}",
+                body, title
+            ))
+            .status()?
+    } else {
+        Command::new("notify-send").arg(title).arg(body).status()?
+    };
+    if status.success() {
+        Ok(())
+    } else {
+        Err(std::io::Error::other(format!(
+            "notifier exited with {}",
+            status
+        )))
+    }
+}
+
+fn check(config: &ReminderConfig) {
+    let (Some(time), Some(quiet_start), Some(quiet_end)) = (
+        parse_minutes(&config.time),
+        parse_minutes(&config.quiet_start),
+        parse_minutes(&config.quiet_end),
+    ) else {
+        eprintln!(" > {}", t("reminder.bad_time"));
+        return;
+    };
+    let Some(now) = local_minutes() else {
+        logging::error(t("error.local_time"));
+        return;
+    };
+
+    let mut stats = match load_stats() {
+        Ok(stats) => stats,
+        Err(e) => {
+            logging::error(tf("error.load_stats", &[("error", &e)]));
+            return;
+        }
+    };
+    let day = today();
+    if now < time
+        || is_quiet(now, quiet_start, quiet_end)
+        || stats.daily_completed == Some(day)
+        || stats.daily_reminded == Some(day)
+    {
+        return;
+    }
+
+    let challenge = &CHALLENGES[daily_index(day)];
+    let body = tf("reminder.body", &[("challenge", &challenge.title())]);
+    match notify("2048", &body) {
+        Ok(()) => {
+            stats.daily_reminded = Some(day);
+            if let Err(e) = save_stats(&stats) {
+                logging::error(tf("error.save_stats", &[("error", &e)]));
+            }
+        }
+        Err(e) => logging::error(tf("error.reminder", &[("error", &e)])),
+    }
+}
+
+pub fn run(watch: bool) {
+    loop {
+        let config = load_config().reminders;
+        if !config.enabled {
+            println!(" > {}", t("reminder.off"));
+            return;
+        }
+        check(&config);
+        if !watch {
+            return;
+        }
+        thread::sleep(CHECK_INTERVAL);
+    }
+}
@@ -0,0 +1,51 @@
+use crate::config;
+use crossterm::event::KeyCode;
+use std::{
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+/// Terminal key repeat sends the same key every few tens of milliseconds, so a key that
+/// comes back sooner than this is taken as still being held down.
+const HOLD_GAP: Duration = Duration::from_millis(100);
+
+struct Hold {
+    code: KeyCode,
+    started: Instant,
+    accepted: Instant,
+    seen: Instant,
+}
+
+static HOLD: Mutex<Option<Hold>> = Mutex::new(None);
+
+/// Decides whether a move key should act, turning a held key into moves at the configured
+/// delay and rate and dropping the extra repeats the terminal sends in between.
+pub fn accept(code: KeyCode) -> bool {
+    let settings = config::input();
+    let now = Instant::now();
+    let mut hold = HOLD.lock().unwrap_or_else(|e| e.into_inner());
+    match hold.as_mut() {
+        Some(held) if held.code == code && now.duration_since(held.seen) < HOLD_GAP => {
+            held.seen = now;
+            let delay = Duration::from_millis(settings.repeat_delay_ms);
+            let rate = Duration::from_millis(settings.repeat_rate_ms);
+            if !settings.repeat
+                || now.duration_since(held.started) < delay
+                || now.duration_since(held.accepted) < rate
+            {
+                return false;
+            }
+            held.accepted = now;
+            true
+        }
+        _ => {
+            *hold = Some(Hold {
+                code,
+                started: now,
+                accepted: now,
+                seen: now,
+            });
+            true
+        }
+    }
+}
@@ -0,0 +1,26 @@
+use crate::engine::{Merge, Slide};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum NodeKind {
+    Max,
+    Chance,
+    Leaf,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct SearchNode {
+    pub kind: NodeKind,
+    pub depth: u32,
+    pub probability: f64,
+    pub value: f64,
+}
+
+pub trait Tracer {
+    fn on_slide(&mut self, _slide: &Slide) {}
+    fn on_merge(&mut self, _merge: &Merge) {}
+    fn on_search_node(&mut self, _node: &SearchNode) {}
+}
+
+pub(crate) struct NoTrace;
+
+impl Tracer for NoTrace {}
@@ -0,0 +1,153 @@
+use crate::i18n::{t, tf};
+use crate::scores::ScoreKey;
+use crate::{profile, GameState};
+use crossterm::{
+    event::{read, Event, KeyCode},
+    terminal::{disable_raw_mode, enable_raw_mode},
+};
+use rust_2048_game::engine::{calculate_score, MAX_TILE, OBSTACLE};
+use std::{
+    fs,
+    path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+pub enum Choice {
+    Resume,
+    NewGame,
+    Archived(PathBuf),
+    Quit,
+}
+
+/// Why the save cannot be played at all, such as a board that is not square.
+pub fn unplayable(state: &GameState) -> Option<String> {
+    let size = state.game_board.len();
+    if size == 0 || state.game_board.iter().any(|row| row.len() != size) {
+        return Some(t("resume.not_square").to_string());
+    }
+    if let Some(&tile) = state
+        .game_board
+        .iter()
+        .flatten()
+        .find(|&&cell| cell != 0 && cell != OBSTACLE && (cell < 2 || !cell.is_power_of_two()))
+    {
+        return Some(tf("resume.bad_tile", &[("tile", &tile)]));
+    }
+    if let Some(&tile) = state
+        .game_board
+        .iter()
+        .flatten()
+        .find(|&&cell| cell != OBSTACLE && cell > MAX_TILE)
+    {
+        return Some(tf(
+            "resume.huge_tile",
+            &[("tile", &tile), ("max", &MAX_TILE)],
+        ));
+    }
+    if state.size != 0 && state.size != size {
+        return Some(tf(
+            "resume.wrong_size",
+            &[("saved", &state.size), ("size", &size)],
+        ));
+    }
+    None
+}
+
+fn age(saved_at: u64) -> Option<String> {
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).ok()?.as_secs();
+    let secs = now.checked_sub(saved_at).filter(|_| saved_at > 0)?;
+    Some(match secs {
+        0..=59 => t("resume.just_now").to_string(),
+        60..=3599 => tf("resume.minutes_ago", &[("count", &(secs / 60))]),
+        3600..=86_399 => tf("resume.hours_ago", &[("count", &(secs / 3600))]),
+        _ => tf("resume.days_ago", &[("count", &(secs / 86_400))]),
+    })
+}
+
+/// Moves the save aside as `game_state-<time>.json`, stamped with `saved_at` when known,
+/// so a new game does not save over it.
+pub fn archive(saved_at: u64) -> std::io::Result<PathBuf> {
+    let stamp = if saved_at > 0 {
+        saved_at
+    } else {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs())
+            .unwrap_or(0)
+    };
+    let archived = profile::path(&format!("game_state-{}.json", stamp));
+    fs::rename(profile::path("game_state.json"), &archived)?;
+    Ok(archived)
+}
+
+fn confirm() -> crossterm::Result<Option<bool>> {
+    enable_raw_mode()?;
+    let answer = loop {
+        if let Event::Key(key_event) = read()? {
+            match key_event.code {
+                KeyCode::Char('y')
+                | KeyCode::Char('Y')
+                | KeyCode::Char('s')
+                | KeyCode::Char('S')
+                | KeyCode::Enter => break Some(true),
+                KeyCode::Char('n') | KeyCode::Char('N') => break Some(false),
+                KeyCode::Char('e') | KeyCode::Char('E') | KeyCode::Esc => break None,
+                _ => {}
+            }
+        }
+    };
+    disable_raw_mode()?;
+    Ok(answer)
+}
+
+/// Asks before resuming a save, offering to archive it instead when it does not fit the
+/// settings the game was started with. `wanted` is `None` when the player already chose
+/// to continue, so only a save that cannot be played at all is questioned.
+pub fn ask(state: &GameState, wanted: Option<ScoreKey>) -> crossterm::Result<Choice> {
+    let unplayable = unplayable(state);
+    let saved = ScoreKey::new(state.game_board.len(), state.difficulty, state.ruleset);
+    let mismatch = unplayable.clone().or_else(|| {
+        wanted.filter(|wanted| *wanted != saved).map(|wanted| {
+            tf(
+                "resume.mismatch",
+                &[("saved", &saved.describe()), ("wanted", &wanted.describe())],
+            )
+        })
+    });
+    if mismatch.is_none() && wanted.is_none() {
+        return Ok(Choice::Resume);
+    }
+
+    let score = calculate_score(&state.game_board) + state.combo.bonus;
+    let when = age(state.saved_at)
+        .map(|age| format!(", {}", age))
+        .unwrap_or_default();
+    let Some(mismatch) = mismatch else {
+        println!(
+            " > {}",
+            tf("resume.prompt", &[("score", &score), ("when", &when)])
+        );
+        return Ok(match confirm()? {
+            Some(true) => Choice::Resume,
+            Some(false) => Choice::NewGame,
+            None => Choice::Quit,
+        });
+    };
+    println!(
+        " > {}",
+        tf("resume.saved", &[("score", &score), ("when", &when)])
+    );
+    println!(" > {}", mismatch);
+    println!(" > {}", t("resume.archive_prompt"));
+    match confirm()? {
+        Some(true) => match archive(state.saved_at) {
+            Ok(archived) => Ok(Choice::Archived(archived)),
+            Err(e) => {
+                crate::logging::error(tf("error.archive", &[("error", &e)]));
+                Ok(Choice::Quit)
+            }
+        },
+        Some(false) if unplayable.is_none() => Ok(Choice::Resume),
+        _ => Ok(Choice::Quit),
+    }
+}
@@ -0,0 +1,187 @@
+use crate::config::load_config;
+use crate::difficulty::Difficulty;
+use crate::error::GameError;
+use crate::i18n::tf;
+use crate::stats::{load_stats, SignedScore};
+use crate::{logging, profile, read_high_score, vault, write_high_score};
+use rust_2048_game::scoring::Ruleset;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+const SCORES_FILE: &str = "scores.json";
+
+/// The settings a high score is kept separately for.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct ScoreKey {
+    pub size: usize,
+    pub difficulty: Difficulty,
+    pub ruleset: Ruleset,
+}
+
+impl ScoreKey {
+    pub fn new(size: usize, difficulty: Difficulty, ruleset: Ruleset) -> Self {
+        ScoreKey {
+            size,
+            difficulty,
+            ruleset,
+        }
+    }
+
+    fn id(&self) -> String {
+        match self.ruleset {
+            Ruleset::Standard => format!("{0}x{0}-{1}", self.size, self.difficulty.name()),
+            ruleset => format!(
+                "{0}x{0}-{1}-{2}",
+                self.size,
+                self.difficulty.name(),
+                ruleset.name()
+            ),
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        match self.ruleset {
+            Ruleset::Standard => format!("{0}x{0} {1}", self.size, self.difficulty.label()),
+            ruleset => format!(
+                "{0}x{0} {1}, {2}",
+                self.size,
+                self.difficulty.label(),
+                ruleset.name()
+            ),
+        }
+    }
+
+    /// The classic 4x4 game whose best also lives in `highscore.txt`.
+    pub fn classic() -> Self {
+        ScoreKey::new(4, Difficulty::Normal, Ruleset::Standard)
+    }
+
+    fn is_classic(&self) -> bool {
+        *self == ScoreKey::classic()
+    }
+}
+
+#[derive(Serialize, Deserialize, Default)]
+struct Scores {
+    best: BTreeMap<String, SignedScore>,
+}
+
+/// Builds the table from `highscore.txt` and the per-difficulty bests that stats used to
+/// hold, filing each under the board size its difficulty had at the time.
+fn migrate() -> Result<Scores, GameError> {
+    let config = load_config();
+    let mut scores = Scores::default();
+    let mut keep = |key: ScoreKey, score: u64| {
+        let known = scores.best.get(&key.id()).and_then(SignedScore::verified);
+        if score > 0 && known.is_none_or(|best| best < score) {
+            if let Ok(signed) = SignedScore::new(score) {
+                scores.best.insert(key.id(), signed);
+            }
+        }
+    };
+    keep(ScoreKey::classic(), read_high_score());
+    for (name, best) in load_stats()?.difficulty_best {
+        let (difficulty, ruleset) = name.split_once('-').unwrap_or((&name, "standard"));
+        let difficulty = Difficulty::ALL.into_iter().find(|d| d.name() == difficulty);
+        let ruleset = [Ruleset::Standard, Ruleset::Combo]
+            .into_iter()
+            .find(|r| r.name() == ruleset);
+        if let (Some(difficulty), Some(ruleset), Some(score)) =
+            (difficulty, ruleset, best.verified())
+        {
+            keep(
+                ScoreKey::new(difficulty.board_size(&config), difficulty, ruleset),
+                score,
+            );
+        }
+    }
+    Ok(scores)
+}
+
+fn load() -> Result<Scores, GameError> {
+    if let Some(scores) = vault::load(&profile::path(SCORES_FILE))? {
+        return Ok(scores);
+    }
+    let scores = migrate()?;
+    if let Err(e) = save(&scores) {
+        logging::error(tf("error.save_scores", &[("error", &e)]));
+    }
+    Ok(scores)
+}
+
+fn save(scores: &Scores) -> Result<(), GameError> {
+    let serialized = serde_json::to_string_pretty(scores)?;
+    vault::write(&profile::path(SCORES_FILE), &serialized)
+}
+
+/// The verified best for `key`, 0 when there is none or the table cannot be read.
+pub fn best(key: ScoreKey) -> u64 {
+    match load() {
+        Ok(scores) => scores
+            .best
+            .get(&key.id())
+            .and_then(SignedScore::verified)
+            .unwrap_or(0),
+        Err(e) => {
+            tracing::warn!(error = %e, "reading high scores");
+            0
+        }
+    }
+}
+
+pub fn record(key: ScoreKey, score: u64) -> Result<(), GameError> {
+    let mut scores = load()?;
+    scores.best.insert(key.id(), SignedScore::new(score)?);
+    save(&scores)?;
+    if key.is_classic() {
+        write_high_score(score)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scratch;
+    use std::fs;
+
+    #[test]
+    fn each_key_keeps_its_own_best() {
+        scratch::run(|_| {
+            let hard = ScoreKey::new(4, Difficulty::Hard, Ruleset::Standard);
+            let combo = ScoreKey::new(4, Difficulty::Normal, Ruleset::Combo);
+            record(hard, 1200).unwrap();
+            record(combo, 800).unwrap();
+            assert_eq!(best(hard), 1200);
+            assert_eq!(best(combo), 800);
+            assert_eq!(best(ScoreKey::classic()), 0);
+
+            record(ScoreKey::classic(), 3000).unwrap();
+            assert_eq!(best(ScoreKey::classic()), 3000);
+            assert_eq!(read_high_score(), 3000);
+        });
+    }
+
+    #[test]
+    fn edited_scores_do_not_verify() {
+        scratch::run(|_| {
+            let hard = ScoreKey::new(4, Difficulty::Hard, Ruleset::Standard);
+            record(hard, 1200).unwrap();
+            let edited = fs::read_to_string(SCORES_FILE)
+                .unwrap()
+                .replace("1200", "99999");
+            fs::write(SCORES_FILE, edited).unwrap();
+            assert_eq!(best(hard), 0);
+        });
+    }
+
+    #[test]
+    fn unreadable_table_is_left_alone() {
+        scratch::run(|_| {
+            fs::write(SCORES_FILE, "{ not json").unwrap();
+            assert_eq!(best(ScoreKey::classic()), 0);
+            assert!(record(ScoreKey::classic(), 512).is_err());
+            assert_eq!(fs::read_to_string(SCORES_FILE).unwrap(), "{ not json");
+        });
+    }
+}
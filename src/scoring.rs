@@ -0,0 +1,45 @@
+use crate::engine::MoveResult;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Ruleset {
+    #[default]
+    Standard,
+    Combo,
+    Lucky,
+}
+
+impl Ruleset {
+    pub fn name(self) -> &'static str {
+        match self {
+            Ruleset::Standard => "standard",
+            Ruleset::Combo => "combo",
+            Ruleset::Lucky => "lucky",
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+pub struct Combo {
+    pub streak: u32,
+    pub bonus: u64,
+}
+
+impl Combo {
+    pub fn multiplier(&self) -> u64 {
+        self.streak.max(1) as u64
+    }
+
+    pub fn record(&mut self, result: &MoveResult) -> u64 {
+        if result.merges.is_empty() {
+            self.streak = 0;
+            return 0;
+        }
+        self.streak += 1;
+        let merged: u64 = result.merges.iter().map(|merge| merge.value).sum();
+        let bonus = merged * (self.multiplier() - 1);
+        self.bonus += bonus;
+        bonus
+    }
+}
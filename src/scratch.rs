@@ -0,0 +1,32 @@
+//! An empty working directory for tests of modules that keep their files next to the game.
+
+use std::{
+    env, fs,
+    panic::{self, AssertUnwindSafe},
+    path::Path,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+};
+
+/// The working directory belongs to the whole process, so tests take turns with it.
+static TURN: Mutex<()> = Mutex::new(());
+static NEXT: AtomicUsize = AtomicUsize::new(0);
+
+/// Runs `test` in a fresh directory under the system temp dir, removed again afterwards.
+pub fn run<T>(test: impl FnOnce(&Path) -> T) -> T {
+    let _turn = TURN.lock().unwrap_or_else(|e| e.into_inner());
+    let dir = env::temp_dir().join(format!(
+        "rust_2048_game-{}-{}",
+        std::process::id(),
+        NEXT.fetch_add(1, Ordering::Relaxed)
+    ));
+    fs::create_dir_all(&dir).expect("temp dir is writable");
+    let previous = env::current_dir().expect("working directory exists");
+    env::set_current_dir(&dir).expect("scratch dir is enterable");
+    let result = panic::catch_unwind(AssertUnwindSafe(|| test(&dir)));
+    env::set_current_dir(previous).expect("working directory still exists");
+    let _ = fs::remove_dir_all(&dir);
+    result.unwrap_or_else(|panic| panic::resume_unwind(panic))
+}
@@ -0,0 +1,82 @@
+use crate::i18n::tf;
+use crate::output::TurnState;
+use rust_2048_game::engine::{
+    apply_move, calculate_score, legal_moves, spawn_random_tile, Direction,
+};
+use serde::Serialize;
+use std::{
+    fs,
+    io::{self, Read},
+    path::Path,
+};
+
+#[derive(Serialize)]
+struct ScriptState<'a> {
+    step: usize,
+    #[serde(rename = "move")]
+    direction: Option<String>,
+    moved: bool,
+    #[serde(flatten)]
+    turn: TurnState<'a>,
+}
+
+fn script_direction(c: char) -> Option<Direction> {
+    match c.to_ascii_uppercase() {
+        'L' => Some(Direction::Left),
+        'U' => Some(Direction::Up),
+        'R' => Some(Direction::Right),
+        'D' => Some(Direction::Down),
+        _ => None,
+    }
+}
+
+fn print_state(
+    step: usize,
+    direction: Option<Direction>,
+    moved: bool,
+    game_board: &[Vec<u64>],
+) -> Result<bool, serde_json::Error> {
+    let game_over = legal_moves(game_board).is_empty();
+    let state = ScriptState {
+        step,
+        direction: direction.map(|direction| format!("{:?}", direction).to_lowercase()),
+        moved,
+        turn: TurnState::new(game_board, calculate_score(game_board)),
+    };
+    println!("{}", serde_json::to_string(&state)?);
+    Ok(game_over)
+}
+
+pub fn run(source: Option<&Path>) -> Result<(), Box<dyn std::error::Error>> {
+    let script = match source {
+        Some(path) if path != Path::new("-") => fs::read_to_string(path)?,
+        _ => {
+            let mut script = String::new();
+            io::stdin().read_to_string(&mut script)?;
+            script
+        }
+    };
+
+    let mut game_board = vec![vec![0; 4]; 4];
+    spawn_random_tile(&mut game_board);
+    spawn_random_tile(&mut game_board);
+    if print_state(0, None, false, &game_board)? {
+        return Ok(());
+    }
+
+    let mut step = 0;
+    for c in script.chars().filter(|c| !c.is_whitespace()) {
+        let Some(direction) = script_direction(c) else {
+            return Err(tf("script.bad_move", &[("character", &c)]).into());
+        };
+        step += 1;
+        let result = apply_move(&mut game_board, direction);
+        if result.moved {
+            spawn_random_tile(&mut game_board);
+        }
+        if print_state(step, Some(direction), result.moved, &game_board)? {
+            break;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,255 @@
+use crate::campaign::{load_progress, save_progress};
+use crate::config::{
+    save_config, set_input, set_key_bindings, set_language, set_tile_marks, Config, CornerLock,
+    InputConfig, KeyBindings, Language, SpawnBias, TileMarks,
+};
+use crate::frame;
+use crate::i18n::{self, t, tf};
+use crate::logging;
+use crate::theme::{initialize_colors, tile_cell, Theme};
+use colored::*;
+use crossterm::{
+    cursor,
+    event::{read, Event, KeyCode},
+    terminal::{Clear, ClearType},
+    ExecutableCommand,
+};
+use std::{collections::HashMap, io::stdout};
+
+const ITEMS: usize = 11;
+
+fn preview_board(size: usize) -> Vec<Vec<u64>> {
+    (0..size)
+        .map(|i| {
+            (0..size)
+                .map(|j| {
+                    let exponent = i * size + j + 1;
+                    if exponent <= 11 {
+                        1 << exponent
+                    } else {
+                        0
+                    }
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn keys_name(keys: KeyBindings) -> &'static str {
+    match keys {
+        KeyBindings::Arrows => t("settings.keys.arrows"),
+        KeyBindings::Ijkl => t("settings.keys.ijkl"),
+        KeyBindings::Numpad => t("settings.keys.numpad"),
+    }
+}
+
+fn marks_name(marks: TileMarks) -> &'static str {
+    match marks {
+        TileMarks::Off => t("settings.marks.off"),
+        TileMarks::Symbols => t("settings.marks.symbols"),
+        TileMarks::Shading => t("settings.marks.shading"),
+    }
+}
+
+fn corner_lock_name(corner_lock: CornerLock) -> &'static str {
+    match corner_lock {
+        CornerLock::Off => t("settings.off"),
+        CornerLock::Warn => t("settings.corner.warn"),
+        CornerLock::Confirm => t("settings.corner.confirm"),
+    }
+}
+
+fn spawn_name(spawn: SpawnBias) -> &'static str {
+    match spawn {
+        SpawnBias::Random => t("settings.spawn.random"),
+        SpawnBias::Gentle => t("settings.spawn.gentle"),
+        SpawnBias::Evil => t("settings.spawn.evil"),
+    }
+}
+
+fn repeat_name(input: &InputConfig) -> String {
+    if input.repeat {
+        tf(
+            "settings.repeat.on",
+            &[
+                ("delay", &input.repeat_delay_ms),
+                ("rate", &input.repeat_rate_ms),
+            ],
+        )
+    } else {
+        t("settings.off").to_string()
+    }
+}
+
+pub fn cycle<T: Copy + PartialEq>(options: &[T], current: T, forward: bool) -> T {
+    let index = options
+        .iter()
+        .position(|&option| option == current)
+        .unwrap_or(0);
+    let next = if forward {
+        (index + 1) % options.len()
+    } else {
+        (index + options.len() - 1) % options.len()
+    };
+    options[next]
+}
+
+pub fn show(config: &mut Config, colors: &mut HashMap<u64, Color>) -> crossterm::Result<()> {
+    let loaded = load_progress();
+    if let Err(e) = &loaded {
+        logging::error(tf("error.load_campaign", &[("error", &e)]));
+    }
+    let progress_loaded = loaded.is_ok();
+    let mut progress = loaded.unwrap_or_default();
+    let mut selected = 0;
+
+    frame::invalidate();
+    loop {
+        let mut stdout = stdout();
+        stdout.execute(Clear(ClearType::All))?;
+        stdout.execute(cursor::MoveTo(0, 0))?;
+        println!(" > {}", t("settings.title"));
+        println!();
+        for row in preview_board(config.game.board_size) {
+            let mut line = String::from("   ");
+            for val in row {
+                line.push_str(&format!("{} ", tile_cell(colors, val)));
+            }
+            println!("{}", line);
+        }
+        println!();
+
+        let preview = match (config.preview.enabled, config.preview.location) {
+            (false, _) => t("settings.off"),
+            (true, false) => t("settings.preview.value"),
+            (true, true) => t("settings.preview.cell"),
+        };
+        let items = [
+            (t("settings.theme"), format!("{:?}", progress.active_theme)),
+            (
+                t("settings.board_size"),
+                tf(
+                    "settings.board_size.value",
+                    &[("size", &config.game.board_size)],
+                ),
+            ),
+            (
+                t("settings.animation"),
+                tf(
+                    "settings.animation.value",
+                    &[("ms", &config.game.animation_ms)],
+                ),
+            ),
+            (
+                t("settings.four_chance"),
+                format!("{:.0}%", config.game.four_chance * 100.0),
+            ),
+            (t("settings.keys"), keys_name(config.game.keys).to_string()),
+            (t("settings.preview"), preview.to_string()),
+            (
+                t("settings.marks"),
+                marks_name(config.display.marks).to_string(),
+            ),
+            (
+                t("settings.corner"),
+                corner_lock_name(config.game.corner_lock).to_string(),
+            ),
+            (
+                t("settings.spawn"),
+                spawn_name(config.game.spawn).to_string(),
+            ),
+            (t("settings.repeat"), repeat_name(&config.input)),
+            (
+                t("settings.language"),
+                i18n::language_name(config.display.language).to_string(),
+            ),
+        ];
+        for (i, (name, value)) in items.iter().enumerate() {
+            let marker = if i == selected { ">" } else { " " };
+            println!(" {} {:<14} : {}", marker, name, value);
+        }
+        println!();
+        println!(" > {}", t("settings.hint"));
+
+        let Event::Key(key_event) = read()? else {
+            continue;
+        };
+        let forward = match key_event.code {
+            KeyCode::Char('e') | KeyCode::Char('E') | KeyCode::Esc => break,
+            KeyCode::Up => {
+                selected = (selected + ITEMS - 1) % ITEMS;
+                continue;
+            }
+            KeyCode::Down => {
+                selected = (selected + 1) % ITEMS;
+                continue;
+            }
+            KeyCode::Right => true,
+            KeyCode::Left => false,
+            _ => continue,
+        };
+        let step: i64 = if forward { 1 } else { -1 };
+        match selected {
+            0 => {
+                let themes = Theme::available(&progress.unlocked_themes);
+                progress.active_theme = cycle(&themes, progress.active_theme, forward);
+                initialize_colors(colors, progress.active_theme);
+            }
+            1 => {
+                config.game.board_size =
+                    (config.game.board_size as i64 + step).clamp(3, 8) as usize;
+            }
+            2 => {
+                config.game.animation_ms =
+                    (config.game.animation_ms as i64 + step * 30).clamp(0, 300) as u64;
+            }
+            3 => {
+                let percent = (config.game.four_chance * 100.0).round() as i64 + step * 5;
+                config.game.four_chance = percent.clamp(0, 50) as f64 / 100.0;
+            }
+            4 => {
+                let options = [KeyBindings::Arrows, KeyBindings::Ijkl, KeyBindings::Numpad];
+                config.game.keys = cycle(&options, config.game.keys, forward);
+                set_key_bindings(config.game.keys);
+            }
+            5 => {
+                let options = [(false, false), (true, false), (true, true)];
+                let current = (config.preview.enabled, config.preview.location);
+                (config.preview.enabled, config.preview.location) =
+                    cycle(&options, current, forward);
+            }
+            6 => {
+                let options = [TileMarks::Off, TileMarks::Symbols, TileMarks::Shading];
+                config.display.marks = cycle(&options, config.display.marks, forward);
+                set_tile_marks(config.display.marks);
+            }
+            7 => {
+                let options = [CornerLock::Off, CornerLock::Warn, CornerLock::Confirm];
+                config.game.corner_lock = cycle(&options, config.game.corner_lock, forward);
+            }
+            8 => {
+                let options = [SpawnBias::Random, SpawnBias::Gentle, SpawnBias::Evil];
+                config.game.spawn = cycle(&options, config.game.spawn, forward);
+            }
+            9 => {
+                config.input.repeat = !config.input.repeat;
+                set_input(config.input);
+            }
+            _ => {
+                let options = [Language::Auto, Language::English, Language::Spanish];
+                config.display.language = cycle(&options, config.display.language, forward);
+                set_language(config.display.language);
+            }
+        }
+    }
+
+    if let Err(e) = save_config(config) {
+        logging::error(tf("error.save_settings", &[("error", &e)]));
+    }
+    if progress_loaded {
+        if let Err(e) = save_progress(&progress) {
+            logging::error(tf("error.save_theme", &[("error", &e)]));
+        }
+    }
+    Ok(())
+}
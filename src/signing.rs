@@ -0,0 +1,97 @@
+use hmac::{Hmac, Mac};
+use rand::{thread_rng, RngCore};
+use sha2::Sha256;
+use std::{fs, io, path::Path};
+
+pub const KEY_FILE: &str = ".install_key";
+
+type HmacSha256 = Hmac<Sha256>;
+
+pub fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+pub fn from_hex(text: &str) -> Option<Vec<u8>> {
+    if !text.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..text.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(text.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+pub fn has_install_key() -> bool {
+    Path::new(KEY_FILE).exists()
+}
+
+fn install_key() -> io::Result<Vec<u8>> {
+    if let Some(key) = fs::read_to_string(KEY_FILE)
+        .ok()
+        .and_then(|data| from_hex(data.trim()))
+    {
+        return Ok(key);
+    }
+    let mut key = vec![0u8; 32];
+    thread_rng().fill_bytes(&mut key);
+    fs::write(KEY_FILE, to_hex(&key))?;
+    Ok(key)
+}
+
+fn mac_for(key: &[u8], payload: &str) -> HmacSha256 {
+    let mut mac = HmacSha256::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(payload.as_bytes());
+    mac
+}
+
+pub fn sign(payload: &str) -> io::Result<String> {
+    let key = install_key()?;
+    Ok(to_hex(&mac_for(&key, payload).finalize().into_bytes()))
+}
+
+pub fn verify(payload: &str, signature: &str) -> bool {
+    let Ok(key) = install_key() else {
+        return false;
+    };
+    match from_hex(signature) {
+        Some(bytes) => mac_for(&key, payload).verify_slice(&bytes).is_ok(),
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scratch;
+
+    #[test]
+    fn hex_round_trips() {
+        let bytes = [0x00, 0x7f, 0x80, 0xff];
+        assert_eq!(to_hex(&bytes), "007f80ff");
+        assert_eq!(from_hex("007f80ff").as_deref(), Some(&bytes[..]));
+        assert_eq!(from_hex("abc"), None);
+        assert_eq!(from_hex("zz"), None);
+    }
+
+    #[test]
+    fn signature_verifies_only_its_payload() {
+        scratch::run(|_| {
+            assert!(!has_install_key());
+            let signature = sign("score=2048").unwrap();
+            assert!(has_install_key());
+            assert!(verify("score=2048", &signature));
+            assert!(!verify("score=4096", &signature));
+            assert!(!verify("score=2048", &signature[2..]));
+            assert!(!verify("score=2048", "not hex"));
+        });
+    }
+
+    #[test]
+    fn signature_needs_the_key_that_made_it() {
+        scratch::run(|_| {
+            let signature = sign("score=2048").unwrap();
+            fs::write(KEY_FILE, to_hex(&[7u8; 32])).unwrap();
+            assert!(!verify("score=2048", &signature));
+        });
+    }
+}
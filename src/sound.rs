@@ -0,0 +1,140 @@
+use crate::config::SoundConfig;
+use rust_2048_game::engine::MoveResult;
+
+#[cfg(feature = "sound")]
+use crate::music::{self, Cue};
+#[cfg(feature = "sound")]
+use rodio::{source::SineWave, OutputStream, OutputStreamBuilder, Source};
+#[cfg(feature = "sound")]
+use std::{sync::mpsc::Sender, time::Duration};
+
+pub struct Sound {
+    muted: bool,
+    #[cfg(feature = "sound")]
+    volume: f32,
+    #[cfg(feature = "sound")]
+    stream: Option<OutputStream>,
+    #[cfg(feature = "sound")]
+    music: Option<Sender<Cue>>,
+}
+
+impl Sound {
+    #[cfg(feature = "sound")]
+    pub fn new(config: &SoundConfig) -> Self {
+        let stream = OutputStreamBuilder::open_default_stream()
+            .map(|mut stream| {
+                stream.log_on_drop(false);
+                stream
+            })
+            .ok();
+        let music = match &stream {
+            Some(stream) if config.music => Some(music::start(
+                stream.mixer(),
+                config.music_volume.clamp(0.0, 1.0),
+                config.muted,
+            )),
+            _ => None,
+        };
+        Sound {
+            muted: config.muted,
+            volume: config.volume.clamp(0.0, 1.0),
+            stream,
+            music,
+        }
+    }
+
+    #[cfg(not(feature = "sound"))]
+    pub fn new(config: &SoundConfig) -> Self {
+        Sound {
+            muted: config.muted,
+        }
+    }
+
+    pub fn toggle_mute(&mut self) -> bool {
+        self.muted = !self.muted;
+        #[cfg(feature = "sound")]
+        self.cue(Cue::Mute(self.muted));
+        self.muted
+    }
+
+    #[cfg(feature = "sound")]
+    fn cue(&self, cue: Cue) {
+        if let Some(music) = &self.music {
+            let _ = music.send(cue);
+        }
+    }
+
+    #[cfg(feature = "sound")]
+    pub fn note_max_tile(&self, value: u64) {
+        self.cue(Cue::MaxTile(value));
+    }
+
+    #[cfg(not(feature = "sound"))]
+    pub fn note_max_tile(&self, _value: u64) {}
+
+    #[cfg(feature = "sound")]
+    pub fn play_move(&self, result: &MoveResult) {
+        let merged = result.merges.iter().map(|merge| merge.value).max();
+        if let Some(value) = merged {
+            self.note_max_tile(value);
+        }
+        let Some(stream) = &self.stream else {
+            return;
+        };
+        if self.muted || !result.moved {
+            return;
+        }
+
+        self.cue(Cue::Duck);
+        let mixer = stream.mixer();
+        match merged {
+            Some(value) if value >= 2048 => {
+                for (step, freq) in [523.25, 659.25, 783.99, 1046.5].into_iter().enumerate() {
+                    mixer.add(
+                        SineWave::new(freq)
+                            .take_duration(Duration::from_millis(160))
+                            .delay(Duration::from_millis(140 * step as u64))
+                            .amplify(self.volume * 0.4),
+                    );
+                }
+            }
+            Some(value) => {
+                let rank = (value as f32).log2();
+                mixer.add(
+                    SineWave::new(392.0 * 2f32.powf(rank / 6.0))
+                        .take_duration(Duration::from_millis(120))
+                        .amplify(self.volume * 0.3),
+                );
+            }
+            None => {
+                mixer.add(
+                    SineWave::new(220.0)
+                        .take_duration(Duration::from_millis(25))
+                        .amplify(self.volume * 0.15),
+                );
+            }
+        }
+    }
+
+    #[cfg(not(feature = "sound"))]
+    pub fn play_move(&self, _result: &MoveResult) {}
+
+    #[cfg(feature = "sound")]
+    pub fn play_beat(&self, accent: bool) {
+        let Some(stream) = &self.stream else {
+            return;
+        };
+        if self.muted {
+            return;
+        }
+        let freq = if accent { 1760.0 } else { 880.0 };
+        stream.mixer().add(
+            SineWave::new(freq)
+                .take_duration(Duration::from_millis(30))
+                .amplify(self.volume * 0.25),
+        );
+    }
+
+    #[cfg(not(feature = "sound"))]
+    pub fn play_beat(&self, _accent: bool) {}
+}
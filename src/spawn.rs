@@ -0,0 +1,110 @@
+use crate::ai::worst_placement;
+use crate::engine::{apply_move, Direction, OBSTACLE};
+use crate::heuristic::evaluate;
+
+/// Picks the cell a new tile lands in. `slot` is a uniform draw in [0, 1) made ahead
+/// of time, so the same board and slot always give the same cell.
+pub trait SpawnStrategy {
+    fn place(&self, board: &[Vec<u64>], value: u64, slot: f64) -> Option<(usize, usize)>;
+}
+
+pub fn open_cells(board: &[Vec<u64>]) -> Vec<(usize, usize)> {
+    board
+        .iter()
+        .enumerate()
+        .flat_map(|(i, row)| {
+            row.iter()
+                .enumerate()
+                .filter(|&(_, &cell)| cell == 0)
+                .map(move |(j, _)| (i, j))
+        })
+        .collect()
+}
+
+/// Every empty cell is equally likely.
+pub struct Uniform;
+
+impl SpawnStrategy for Uniform {
+    fn place(&self, board: &[Vec<u64>], _value: u64, slot: f64) -> Option<(usize, usize)> {
+        let cells = open_cells(board);
+        let index = (slot * cells.len() as f64) as usize;
+        cells.get(index).copied()
+    }
+}
+
+/// Favours cells far from the largest tile, weighting each by its distance to it.
+pub struct Gentle;
+
+impl SpawnStrategy for Gentle {
+    fn place(&self, board: &[Vec<u64>], _value: u64, slot: f64) -> Option<(usize, usize)> {
+        let cells = open_cells(board);
+        let Some((mi, mj)) = largest_tile(board) else {
+            return Uniform.place(board, 0, slot);
+        };
+        let weights: Vec<usize> = cells
+            .iter()
+            .map(|&(i, j)| i.abs_diff(mi) + j.abs_diff(mj))
+            .collect();
+        let mut target = (slot * weights.iter().sum::<usize>() as f64) as usize;
+        for (&cell, &weight) in cells.iter().zip(&weights) {
+            if target < weight {
+                return Some(cell);
+            }
+            target -= weight;
+        }
+        cells.last().copied()
+    }
+}
+
+/// Searches one ply ahead and spawns wherever the player's best reply scores lowest.
+pub struct Evil;
+
+impl Evil {
+    fn best_reply(board: &[Vec<u64>]) -> f64 {
+        Direction::ALL
+            .into_iter()
+            .filter_map(|direction| {
+                let mut next = board.to_vec();
+                apply_move(&mut next, direction)
+                    .moved
+                    .then(|| evaluate(&next))
+            })
+            .fold(f64::NEG_INFINITY, f64::max)
+    }
+}
+
+impl SpawnStrategy for Evil {
+    fn place(&self, board: &[Vec<u64>], value: u64, _slot: f64) -> Option<(usize, usize)> {
+        let mut worst: Option<((usize, usize), f64)> = None;
+        for (i, j) in open_cells(board) {
+            let mut next = board.to_vec();
+            next[i][j] = value;
+            let reply = Self::best_reply(&next);
+            if worst.is_none_or(|(_, score)| reply < score) {
+                worst = Some(((i, j), reply));
+            }
+        }
+        worst.map(|(cell, _)| cell)
+    }
+}
+
+/// An opponent that searches `depth` moves ahead for the placement most likely to end the game.
+pub struct Minimax {
+    pub depth: u32,
+}
+
+impl SpawnStrategy for Minimax {
+    fn place(&self, board: &[Vec<u64>], value: u64, _slot: f64) -> Option<(usize, usize)> {
+        worst_placement(board, value, self.depth)
+    }
+}
+
+fn largest_tile(board: &[Vec<u64>]) -> Option<(usize, usize)> {
+    board
+        .iter()
+        .enumerate()
+        .flat_map(|(i, row)| row.iter().enumerate().map(move |(j, &cell)| ((i, j), cell)))
+        .filter(|&(_, cell)| cell != 0 && cell != OBSTACLE)
+        .max_by_key(|&(_, cell)| cell)
+        .map(|(position, _)| position)
+}
@@ -0,0 +1,157 @@
+use crate::config::load_config;
+use crate::i18n::{t, tf};
+use crate::layout::{print_panels, Panel};
+use crate::sound::Sound;
+use crate::theme::initialize_colors;
+use crate::{campaign, key_direction};
+use colored::*;
+use crossterm::{
+    cursor,
+    event::{read, Event, KeyCode},
+    terminal::{disable_raw_mode, enable_raw_mode, Clear, ClearType},
+    ExecutableCommand,
+};
+use rust_2048_game::engine::{
+    apply_move, calculate_score, legal_moves, spawn_random_tile, Direction,
+};
+use std::{collections::HashMap, io::stdout};
+
+struct Seat {
+    name: &'static str,
+    keys: &'static str,
+    game_board: Vec<Vec<u64>>,
+    moves: u32,
+}
+
+impl Seat {
+    fn new(name: &'static str, keys: &'static str) -> Self {
+        let mut game_board = vec![vec![0; 4]; 4];
+        spawn_random_tile(&mut game_board);
+        spawn_random_tile(&mut game_board);
+        Seat {
+            name,
+            keys,
+            game_board,
+            moves: 0,
+        }
+    }
+
+    fn stuck(&self) -> bool {
+        legal_moves(&self.game_board).is_empty()
+    }
+}
+
+fn wasd_direction(code: KeyCode) -> Option<Direction> {
+    match code {
+        KeyCode::Char('w') | KeyCode::Char('W') => Some(Direction::Up),
+        KeyCode::Char('s') | KeyCode::Char('S') => Some(Direction::Down),
+        KeyCode::Char('a') | KeyCode::Char('A') => Some(Direction::Left),
+        KeyCode::Char('d') | KeyCode::Char('D') => Some(Direction::Right),
+        _ => None,
+    }
+}
+
+fn render_seats(seats: &[Seat; 2], colors: &HashMap<u64, Color>) -> crossterm::Result<()> {
+    let mut stdout: std::io::Stdout = stdout();
+    stdout.execute(Clear(ClearType::All))?;
+    stdout.execute(cursor::MoveTo(0, 0))?;
+
+    let panels = seats.each_ref().map(|seat| Panel {
+        title: seat.name.to_string(),
+        board: &seat.game_board,
+    });
+    print_panels(&panels, colors);
+    println!();
+    for seat in seats {
+        let status = if seat.stuck() {
+            format!("  ({})", t("exhibition.stuck"))
+        } else {
+            String::new()
+        };
+        println!(
+            " > {:<9}: {}{}",
+            seat.name,
+            tf(
+                "versus.points",
+                &[
+                    ("points", &calculate_score(&seat.game_board)),
+                    ("moves", &seat.moves)
+                ]
+            ),
+            status
+        );
+    }
+    println!();
+    for seat in seats {
+        println!(
+            " > {}",
+            tf(
+                "splitscreen.keys",
+                &[("name", &seat.name), ("keys", &seat.keys)]
+            )
+        );
+    }
+    println!(" > {}", t("splitscreen.hint"));
+    Ok(())
+}
+
+pub fn run() -> crossterm::Result<()> {
+    let mut colors: HashMap<u64, Color> = HashMap::new();
+    initialize_colors(&mut colors, campaign::active_theme());
+    let mut sound = Sound::new(&load_config().sound);
+    let mut seats = [
+        Seat::new(t("splitscreen.player_one"), "W A S D"),
+        Seat::new(t("splitscreen.player_two"), t("splitscreen.arrows")),
+    ];
+
+    enable_raw_mode()?;
+    loop {
+        render_seats(&seats, &colors)?;
+        if seats.iter().all(Seat::stuck) {
+            break;
+        }
+
+        if let Event::Key(key_event) = read()? {
+            match key_event.code {
+                KeyCode::Esc => break,
+                KeyCode::Char('m') | KeyCode::Char('M') => {
+                    sound.toggle_mute();
+                    continue;
+                }
+                _ => {}
+            }
+            let (seat, direction) = match (
+                wasd_direction(key_event.code),
+                key_direction(key_event.code),
+            ) {
+                (Some(direction), _) => (&mut seats[0], direction),
+                (_, Some(direction)) => (&mut seats[1], direction),
+                _ => continue,
+            };
+            let result = apply_move(&mut seat.game_board, direction);
+            if result.moved {
+                sound.play_move(&result);
+                spawn_random_tile(&mut seat.game_board);
+                seat.moves += 1;
+            }
+        }
+    }
+    disable_raw_mode()?;
+
+    let scores = seats
+        .each_ref()
+        .map(|seat| calculate_score(&seat.game_board));
+    if scores[0] == scores[1] {
+        println!(" >> {} <<", tf("versus.draw", &[("score", &scores[0])]));
+    } else {
+        let winner = if scores[0] > scores[1] { 0 } else { 1 };
+        println!(
+            " >> {} <<",
+            tf(
+                "versus.wins",
+                &[("name", &seats[winner].name), ("score", &scores[winner])]
+            )
+        );
+    }
+    Ok(())
+}
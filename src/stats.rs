@@ -0,0 +1,75 @@
+use crate::error::GameError;
+use crate::{profile, signing, vault};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+const STATS_FILE: &str = "stats.json";
+
+#[derive(Serialize, Deserialize, Default)]
+pub struct ChallengeRecord {
+    pub attempts: u32,
+    pub completions: u32,
+    pub best_moves: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct SignedScore {
+    pub score: u64,
+    pub signature: String,
+}
+
+impl SignedScore {
+    pub fn new(score: u64) -> std::io::Result<Self> {
+        Ok(SignedScore {
+            score,
+            signature: signing::sign(&score.to_string())?,
+        })
+    }
+
+    pub fn verified(&self) -> Option<u64> {
+        signing::verify(&self.score.to_string(), &self.signature).then_some(self.score)
+    }
+}
+
+#[derive(Serialize, Deserialize, Default)]
+pub struct Stats {
+    #[serde(default)]
+    pub challenges: BTreeMap<String, ChallengeRecord>,
+    #[serde(default)]
+    pub daily_completed: Option<u64>,
+    #[serde(default)]
+    pub daily_reminded: Option<u64>,
+    #[serde(default)]
+    pub best_score: Option<SignedScore>,
+    /// Superseded by `scores.json`, only read to migrate older bests.
+    #[serde(default)]
+    pub difficulty_best: BTreeMap<String, SignedScore>,
+    #[serde(default)]
+    pub continues_used: u32,
+    #[serde(default)]
+    pub puzzles: BTreeMap<String, ChallengeRecord>,
+    /// Set once a signed high score has been written, after which an unsigned
+    /// `highscore.txt` is never taken as a legacy score again.
+    #[serde(default)]
+    pub scores_signed: bool,
+}
+
+pub fn today() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs() / 86_400)
+        .unwrap_or(0)
+}
+
+/// The saved stats, empty when there are none yet.
+pub fn load_stats() -> Result<Stats, GameError> {
+    Ok(vault::load(&profile::path(STATS_FILE))?.unwrap_or_default())
+}
+
+pub fn save_stats(stats: &Stats) -> Result<(), GameError> {
+    let serialized = serde_json::to_string(stats)?;
+    vault::write(&profile::path(STATS_FILE), &serialized)
+}
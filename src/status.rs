@@ -0,0 +1,61 @@
+use crate::i18n::{self, label, tf};
+use rust_2048_game::engine::{Direction, MoveResult};
+use std::time::{Duration, Instant};
+
+const STATUS_TTL: Duration = Duration::from_secs(3);
+
+#[derive(Default)]
+pub struct StatusLine {
+    text: Option<(String, Instant)>,
+    combo: u32,
+}
+
+impl StatusLine {
+    pub fn record(&mut self, direction: Direction, result: &MoveResult) {
+        let points: u64 = result.merges.iter().map(|merge| merge.value).sum();
+        let largest = result.merges.iter().map(|merge| merge.value).max();
+        self.combo = if points > 0 { self.combo + 1 } else { 0 };
+
+        let mut text = format!(
+            "{} {:<5} +{}",
+            label("label.last_move"),
+            i18n::direction(direction),
+            points
+        );
+        if let Some(largest) = largest {
+            text.push_str(&format!(
+                ", {}",
+                tf("status.largest", &[("value", &largest)])
+            ));
+        }
+        if self.combo > 1 {
+            text.push_str(&format!(
+                ", {}",
+                tf("status.combo", &[("combo", &self.combo)])
+            ));
+        }
+        self.text = Some((text, Instant::now()));
+    }
+
+    pub fn announce(&mut self, text: String) {
+        self.text = Some((text, Instant::now()));
+    }
+
+    pub fn expire(&mut self) -> bool {
+        match &self.text {
+            Some((_, shown)) if shown.elapsed() >= STATUS_TTL => {
+                self.text = None;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub fn with_messages(&self, messages: &[String]) -> Vec<String> {
+        let mut lines = messages.to_vec();
+        if let Some((text, _)) = &self.text {
+            lines.push(text.clone());
+        }
+        lines
+    }
+}
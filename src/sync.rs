@@ -0,0 +1,330 @@
+use crate::config::{load_config, SyncConfig};
+use crate::error::GameError;
+use crate::i18n::{t, tf};
+use crate::logging;
+use crate::profile;
+use crate::signing::{self, to_hex, KEY_FILE};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+const MANIFEST_FILE: &str = ".sync.json";
+/// Files that hold nothing but scores signed with the install key, so they are left alone
+/// when the two machines sign with different keys.
+const SIGNED_FILES: [&str; 2] = ["highscore.txt", "scores.json"];
+
+/// Hashes of each file as they were after the last sync, so a copy that merely fell
+/// behind can be told apart from one that was changed on another machine.
+#[derive(Serialize, Deserialize, Default)]
+struct Manifest {
+    synced: BTreeMap<String, String>,
+}
+
+pub enum Outcome {
+    Pushed,
+    Pulled,
+}
+
+pub struct Change {
+    pub file: &'static str,
+    pub outcome: Outcome,
+    pub backup: Option<PathBuf>,
+}
+
+struct Copy {
+    path: PathBuf,
+    data: Vec<u8>,
+    modified: SystemTime,
+}
+
+impl Copy {
+    fn read(path: PathBuf) -> Result<Option<Copy>, GameError> {
+        let data = match fs::read(&path) {
+            Ok(data) => data,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+        let modified = fs::metadata(&path)?.modified()?;
+        Ok(Some(Copy {
+            path,
+            data,
+            modified,
+        }))
+    }
+}
+
+fn hash(data: &[u8]) -> String {
+    to_hex(&Sha256::digest(data))
+}
+
+pub fn folder(config: &SyncConfig) -> Option<PathBuf> {
+    if config.folder.trim().is_empty() {
+        return None;
+    }
+    Some(Path::new(config.folder.trim()).join(profile::active().unwrap_or("default")))
+}
+
+fn backup(loser: &Copy, file: &str) -> Result<PathBuf, GameError> {
+    let stamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or(0);
+    let path = loser
+        .path
+        .with_file_name(format!("{}.conflict-{}", file, stamp));
+    fs::write(&path, &loser.data)?;
+    Ok(path)
+}
+
+/// Shares the install key through the root of the sync folder, which every profile uses.
+/// Like an imported bundle, a machine only takes the key over while it has none of its
+/// own. Returns the change made, if any, and whether both sides now sign with one key.
+fn sync_key(remote_root: &Path) -> Result<(Option<Change>, bool), GameError> {
+    let remote_key = remote_root.join(KEY_FILE);
+    let remote = Copy::read(remote_key.clone())?;
+    let change = |outcome| Change {
+        file: KEY_FILE,
+        outcome,
+        backup: None,
+    };
+    match (signing::has_install_key(), remote) {
+        (false, None) => Ok((None, true)),
+        (true, None) => {
+            fs::write(&remote_key, fs::read(KEY_FILE)?)?;
+            Ok((Some(change(Outcome::Pushed)), true))
+        }
+        (false, Some(remote)) => {
+            fs::write(KEY_FILE, &remote.data)?;
+            Ok((Some(change(Outcome::Pulled)), true))
+        }
+        (true, Some(remote)) => Ok((None, fs::read(KEY_FILE)? == remote.data)),
+    }
+}
+
+/// Mirrors the saves, stats and config with the sync folder. A file that only changed on
+/// one side since the last sync is copied to the other; when both sides changed, the newer
+/// copy wins and the other is kept as a backup. When the two machines sign scores with
+/// different keys, the high score files stay local and the reconciliation at startup
+/// checks the best score that came with the stats.
+pub fn run(config: &SyncConfig) -> Result<Vec<Change>, GameError> {
+    let Some(remote_dir) = folder(config) else {
+        return Ok(Vec::new());
+    };
+    if !remote_dir.parent().is_some_and(Path::is_dir) {
+        return Err(GameError::Config(tf(
+            "sync.no_folder",
+            &[("folder", &config.folder)],
+        )));
+    }
+    fs::create_dir_all(&remote_dir)?;
+
+    let manifest_path = profile::path(MANIFEST_FILE);
+    let mut manifest: Manifest = fs::read_to_string(&manifest_path)
+        .ok()
+        .and_then(|text| serde_json::from_str(&text).ok())
+        .unwrap_or_default();
+    let mut changes = Vec::new();
+    let (key_change, shared_key) = sync_key(Path::new(config.folder.trim()))?;
+    changes.extend(key_change);
+    for file in profile::DATA_FILES {
+        if SIGNED_FILES.contains(&file) && !shared_key {
+            tracing::debug!(file, "skipped, signed with another install key");
+            continue;
+        }
+        let local = Copy::read(profile::path(file))?;
+        let remote = Copy::read(remote_dir.join(file))?;
+        let (winner, loser, target, outcome) = match (local, remote) {
+            (None, None) => continue,
+            (Some(local), None) => (local, None, remote_dir.join(file), Outcome::Pushed),
+            (None, Some(remote)) => (remote, None, profile::path(file), Outcome::Pulled),
+            (Some(local), Some(remote)) if local.data == remote.data => {
+                manifest.synced.insert(file.to_string(), hash(&local.data));
+                continue;
+            }
+            (Some(local), Some(remote)) => {
+                let synced = manifest.synced.get(file);
+                let local_changed = synced != Some(&hash(&local.data));
+                let remote_changed = synced != Some(&hash(&remote.data));
+                let push = match (local_changed, remote_changed) {
+                    (true, false) => true,
+                    (false, true) => false,
+                    _ => local.modified >= remote.modified,
+                };
+                if push {
+                    let target = remote.path.clone();
+                    (local, Some(remote), target, Outcome::Pushed)
+                } else {
+                    let target = local.path.clone();
+                    (remote, Some(local), target, Outcome::Pulled)
+                }
+            }
+        };
+        let backup = match loser {
+            Some(loser) if manifest.synced.get(file) != Some(&hash(&loser.data)) => {
+                Some(backup(&loser, file)?)
+            }
+            _ => None,
+        };
+        fs::write(&target, &winner.data)?;
+        manifest.synced.insert(file.to_string(), hash(&winner.data));
+        tracing::debug!(file, backup = ?backup, "synced");
+        changes.push(Change {
+            file,
+            outcome,
+            backup,
+        });
+    }
+    fs::write(&manifest_path, serde_json::to_string_pretty(&manifest)?)?;
+    Ok(changes)
+}
+
+fn describe(change: &Change) -> String {
+    let action = match change.outcome {
+        Outcome::Pushed => tf("sync.pushed", &[("file", &change.file)]),
+        Outcome::Pulled => tf("sync.pulled", &[("file", &change.file)]),
+    };
+    match &change.backup {
+        Some(backup) => tf(
+            "sync.conflict",
+            &[("change", &action), ("backup", &backup.display())],
+        ),
+        None => action,
+    }
+}
+
+/// Syncs on launch and exit, only speaking up when a conflict left a backup behind.
+pub fn automatic() {
+    match run(&load_config().sync) {
+        Ok(changes) => {
+            for change in changes.iter().filter(|change| change.backup.is_some()) {
+                println!(" > {}", describe(change));
+            }
+        }
+        Err(e) => logging::error(tf("error.sync", &[("error", &e)])),
+    }
+}
+
+pub fn show() {
+    let config = load_config().sync;
+    let Some(folder) = folder(&config) else {
+        println!(" > {}", t("sync.unset"));
+        return;
+    };
+    match run(&config) {
+        Ok(changes) if changes.is_empty() => {
+            println!(" > {}", tf("sync.matches", &[("path", &folder.display())]))
+        }
+        Ok(changes) => {
+            for change in &changes {
+                println!(" > {}", describe(change));
+            }
+        }
+        Err(e) => logging::error(tf("error.sync", &[("error", &e)])),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scratch;
+    use std::time::Duration;
+
+    fn config(dir: &Path) -> SyncConfig {
+        SyncConfig {
+            folder: dir.join("cloud").display().to_string(),
+        }
+    }
+
+    /// Writes `data` to `path` and dates it `age` seconds back.
+    fn write_aged(path: &Path, data: &str, age: u64) {
+        fs::write(path, data).unwrap();
+        fs::File::options()
+            .write(true)
+            .open(path)
+            .unwrap()
+            .set_modified(SystemTime::now() - Duration::from_secs(age))
+            .unwrap();
+    }
+
+    fn outcomes(changes: &[Change]) -> Vec<(&str, bool, bool)> {
+        changes
+            .iter()
+            .map(|change| {
+                let pushed = matches!(change.outcome, Outcome::Pushed);
+                (change.file, pushed, change.backup.is_some())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn missing_folder_is_an_error() {
+        scratch::run(|dir| {
+            assert!(matches!(run(&config(dir)), Err(GameError::Config(_))));
+        });
+    }
+
+    #[test]
+    fn first_sync_pushes_and_second_finds_nothing() {
+        scratch::run(|dir| {
+            fs::create_dir(dir.join("cloud")).unwrap();
+            fs::write("stats.json", "local").unwrap();
+            let changes = run(&config(dir)).unwrap();
+            assert_eq!(outcomes(&changes), [("stats.json", true, false)]);
+            assert_eq!(
+                fs::read_to_string(dir.join("cloud/default/stats.json")).unwrap(),
+                "local"
+            );
+            assert!(run(&config(dir)).unwrap().is_empty());
+        });
+    }
+
+    #[test]
+    fn a_change_on_one_side_wins_whatever_the_clock_says() {
+        scratch::run(|dir| {
+            fs::create_dir(dir.join("cloud")).unwrap();
+            let remote = dir.join("cloud/default/stats.json");
+            fs::write("stats.json", "synced").unwrap();
+            run(&config(dir)).unwrap();
+
+            // Only the other machine played, but this copy looks newer.
+            write_aged(&remote, "played elsewhere", 600);
+            write_aged(Path::new("stats.json"), "synced", 0);
+            let changes = run(&config(dir)).unwrap();
+            assert_eq!(outcomes(&changes), [("stats.json", false, false)]);
+            assert_eq!(
+                fs::read_to_string("stats.json").unwrap(),
+                "played elsewhere"
+            );
+
+            // And the other way round.
+            write_aged(Path::new("stats.json"), "played here", 600);
+            write_aged(&remote, "played elsewhere", 0);
+            let changes = run(&config(dir)).unwrap();
+            assert_eq!(outcomes(&changes), [("stats.json", true, false)]);
+            assert_eq!(fs::read_to_string(&remote).unwrap(), "played here");
+        });
+    }
+
+    #[test]
+    fn changes_on_both_sides_keep_the_newer_and_back_up_the_other() {
+        scratch::run(|dir| {
+            fs::create_dir(dir.join("cloud")).unwrap();
+            let remote = dir.join("cloud/default/stats.json");
+            fs::write("stats.json", "synced").unwrap();
+            run(&config(dir)).unwrap();
+
+            write_aged(Path::new("stats.json"), "older here", 600);
+            write_aged(&remote, "newer elsewhere", 0);
+            let changes = run(&config(dir)).unwrap();
+            assert_eq!(outcomes(&changes), [("stats.json", false, true)]);
+            assert_eq!(fs::read_to_string("stats.json").unwrap(), "newer elsewhere");
+            let backup = changes[0].backup.as_ref().unwrap();
+            assert_eq!(fs::read_to_string(backup).unwrap(), "older here");
+        });
+    }
+}
@@ -0,0 +1,227 @@
+use crate::engine::{Direction, OBSTACLE};
+use std::{
+    collections::HashMap,
+    fs,
+    io::{self, ErrorKind},
+    path::Path,
+};
+
+const MAGIC: &[u8; 6] = b"2048TB";
+pub const MAX_SIZE: usize = 3;
+
+pub struct MoveValue {
+    pub direction: Direction,
+    pub expected: f64,
+}
+
+pub struct Tablebase {
+    size: usize,
+    values: HashMap<u64, f32>,
+    limit: Option<usize>,
+    exhausted: bool,
+}
+
+fn to_exponents(game_board: &[Vec<u64>]) -> Option<Vec<u8>> {
+    game_board
+        .iter()
+        .flatten()
+        .map(|&val| match val {
+            0 => Some(0),
+            OBSTACLE => None,
+            _ if val.is_power_of_two() && val.trailing_zeros() < 16 => {
+                Some(val.trailing_zeros() as u8)
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+fn pack(cells: &[u8]) -> u64 {
+    cells
+        .iter()
+        .fold(0, |key, &exponent| (key << 4) | exponent as u64)
+}
+
+fn line(size: usize, direction: Direction, index: usize) -> Vec<usize> {
+    (0..size)
+        .map(|k| match direction {
+            Direction::Left => index * size + k,
+            Direction::Right => index * size + size - 1 - k,
+            Direction::Up => k * size + index,
+            Direction::Down => (size - 1 - k) * size + index,
+        })
+        .collect()
+}
+
+fn shift(cells: &[u8], size: usize, direction: Direction) -> Option<(Vec<u8>, u32)> {
+    let mut next = cells.to_vec();
+    let mut points = 0;
+    for index in 0..size {
+        let positions = line(size, direction, index);
+        let tiles: Vec<u8> = positions
+            .iter()
+            .map(|&p| cells[p])
+            .filter(|&e| e != 0)
+            .collect();
+        let mut merged: Vec<u8> = Vec::with_capacity(size);
+        let mut i = 0;
+        while i < tiles.len() {
+            if i + 1 < tiles.len() && tiles[i] == tiles[i + 1] {
+                merged.push(tiles[i] + 1);
+                points += 1 << (tiles[i] + 1);
+                i += 2;
+            } else {
+                merged.push(tiles[i]);
+                i += 1;
+            }
+        }
+        merged.resize(size, 0);
+        for (&p, e) in positions.iter().zip(merged) {
+            next[p] = e;
+        }
+    }
+    (next != cells).then_some((next, points))
+}
+
+fn spawns(cells: &[u8]) -> Vec<(Vec<u8>, f64)> {
+    let empty: Vec<usize> = (0..cells.len()).filter(|&p| cells[p] == 0).collect();
+    let mut outcomes = Vec::with_capacity(empty.len() * 2);
+    for &p in &empty {
+        for (exponent, probability) in [(1, 0.9), (2, 0.1)] {
+            let mut next = cells.to_vec();
+            next[p] = exponent;
+            outcomes.push((next, probability / empty.len() as f64));
+        }
+    }
+    outcomes
+}
+
+impl Tablebase {
+    pub fn new(size: usize) -> Self {
+        Tablebase {
+            size,
+            values: HashMap::new(),
+            limit: None,
+            exhausted: false,
+        }
+    }
+
+    pub fn limited(self, limit: usize) -> Self {
+        Tablebase {
+            limit: Some(self.values.len() + limit),
+            ..self
+        }
+    }
+
+    pub fn exhausted(&self) -> bool {
+        self.exhausted
+    }
+
+    pub fn generate(size: usize) -> Self {
+        let mut tablebase = Tablebase::new(size);
+        for (board, _) in spawns(&vec![0; size * size]) {
+            for (start, _) in spawns(&board) {
+                tablebase.solve(&start);
+            }
+        }
+        tablebase
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    pub fn supports(&self, game_board: &[Vec<u64>]) -> bool {
+        game_board.len() == self.size
+            && game_board.iter().all(|row| row.len() == self.size)
+            && to_exponents(game_board).is_some()
+    }
+
+    fn solve(&mut self, cells: &[u8]) -> f64 {
+        let key = pack(cells);
+        if let Some(&value) = self.values.get(&key) {
+            return value as f64;
+        }
+        if self.limit.is_some_and(|limit| self.values.len() >= limit) {
+            self.exhausted = true;
+            return 0.0;
+        }
+        let value = self
+            .expected_values(cells)
+            .into_iter()
+            .map(|(_, expected)| expected)
+            .fold(0.0, f64::max);
+        self.values.insert(key, value as f32);
+        value
+    }
+
+    fn expected_values(&mut self, cells: &[u8]) -> Vec<(Direction, f64)> {
+        let mut values = Vec::new();
+        for direction in Direction::ALL {
+            let Some((next, points)) = shift(cells, self.size, direction) else {
+                continue;
+            };
+            let future: f64 = spawns(&next)
+                .iter()
+                .map(|(board, probability)| probability * self.solve(board))
+                .sum();
+            values.push((direction, points as f64 + future));
+        }
+        values
+    }
+
+    pub fn move_values(&mut self, game_board: &[Vec<u64>]) -> Vec<MoveValue> {
+        if !self.supports(game_board) {
+            return Vec::new();
+        }
+        let cells = to_exponents(game_board).unwrap_or_default();
+        self.expected_values(&cells)
+            .into_iter()
+            .map(|(direction, expected)| MoveValue {
+                direction,
+                expected,
+            })
+            .collect()
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let mut bytes = Vec::with_capacity(MAGIC.len() + 1 + self.values.len() * 12);
+        bytes.extend_from_slice(MAGIC);
+        bytes.push(self.size as u8);
+        for (&key, &value) in &self.values {
+            bytes.extend_from_slice(&key.to_le_bytes());
+            bytes.extend_from_slice(&value.to_le_bytes());
+        }
+        fs::write(path, bytes)
+    }
+
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let bytes = fs::read(path)?;
+        let invalid = || io::Error::new(ErrorKind::InvalidData, "not a 2048 tablebase file");
+        let body = bytes.strip_prefix(MAGIC.as_slice()).ok_or_else(invalid)?;
+        let (&size, entries) = body.split_first().ok_or_else(invalid)?;
+        if !(2..=MAX_SIZE).contains(&(size as usize)) || !entries.len().is_multiple_of(12) {
+            return Err(invalid());
+        }
+        let values = entries
+            .chunks_exact(12)
+            .map(|entry| {
+                let key = u64::from_le_bytes(entry[..8].try_into().unwrap_or_default());
+                let value = f32::from_le_bytes(entry[8..].try_into().unwrap_or_default());
+                (key, value)
+            })
+            .collect();
+        Ok(Tablebase {
+            values,
+            ..Tablebase::new(size as usize)
+        })
+    }
+}
@@ -0,0 +1,253 @@
+use crate::config::{tile_marks, TileMarks};
+use clap::ValueEnum;
+use colored::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+const SYMBOLS: [char; 11] = ['.', ':', '+', '*', 'o', '#', '%', '@', '=', '~', '^'];
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize, ValueEnum)]
+pub enum Theme {
+    Classic,
+    Ocean,
+    Ember,
+    Deuteranopia,
+    Protanopia,
+    Tritanopia,
+    HighContrast,
+}
+
+impl Theme {
+    pub const ACCESSIBLE: [Theme; 4] = [
+        Theme::Deuteranopia,
+        Theme::Protanopia,
+        Theme::Tritanopia,
+        Theme::HighContrast,
+    ];
+
+    pub fn available(unlocked: &[Theme]) -> Vec<Theme> {
+        let mut themes = unlocked.to_vec();
+        themes.extend(
+            Theme::ACCESSIBLE
+                .iter()
+                .filter(|theme| !unlocked.contains(theme)),
+        );
+        themes
+    }
+}
+
+fn insert_palette(colors: &mut HashMap<u64, Color>, palette: [(u8, u8, u8); 11]) {
+    for (exponent, (r, g, b)) in (1..).zip(palette) {
+        colors.insert(1 << exponent, Color::TrueColor { r, g, b });
+    }
+}
+
+pub fn initialize_colors(colors: &mut HashMap<u64, Color>, theme: Theme) {
+    colors.clear();
+    match theme {
+        Theme::Classic => {
+            colors.insert(2, Color::Red);
+            colors.insert(4, Color::Blue);
+            colors.insert(8, Color::Green);
+            colors.insert(16, Color::Yellow);
+            colors.insert(32, Color::Magenta);
+            colors.insert(64, Color::Cyan);
+            colors.insert(128, Color::BrightRed);
+            colors.insert(256, Color::BrightBlue);
+            colors.insert(512, Color::BrightGreen);
+            colors.insert(1024, Color::Green);
+            colors.insert(2048, Color::BrightCyan);
+        }
+        Theme::Ocean => {
+            colors.insert(2, Color::BrightWhite);
+            colors.insert(4, Color::BrightCyan);
+            colors.insert(8, Color::Cyan);
+            colors.insert(16, Color::BrightBlue);
+            colors.insert(32, Color::Blue);
+            colors.insert(
+                64,
+                Color::TrueColor {
+                    r: 0,
+                    g: 95,
+                    b: 135,
+                },
+            );
+            colors.insert(
+                128,
+                Color::TrueColor {
+                    r: 0,
+                    g: 135,
+                    b: 175,
+                },
+            );
+            colors.insert(
+                256,
+                Color::TrueColor {
+                    r: 0,
+                    g: 175,
+                    b: 215,
+                },
+            );
+            colors.insert(512, Color::BrightGreen);
+            colors.insert(1024, Color::Green);
+            colors.insert(2048, Color::BrightMagenta);
+        }
+        Theme::Ember => {
+            colors.insert(2, Color::BrightYellow);
+            colors.insert(4, Color::Yellow);
+            colors.insert(
+                8,
+                Color::TrueColor {
+                    r: 255,
+                    g: 175,
+                    b: 0,
+                },
+            );
+            colors.insert(
+                16,
+                Color::TrueColor {
+                    r: 255,
+                    g: 135,
+                    b: 0,
+                },
+            );
+            colors.insert(
+                32,
+                Color::TrueColor {
+                    r: 255,
+                    g: 95,
+                    b: 0,
+                },
+            );
+            colors.insert(64, Color::BrightRed);
+            colors.insert(128, Color::Red);
+            colors.insert(256, Color::TrueColor { r: 175, g: 0, b: 0 });
+            colors.insert(512, Color::BrightMagenta);
+            colors.insert(1024, Color::Magenta);
+            colors.insert(2048, Color::BrightWhite);
+        }
+        Theme::Deuteranopia => insert_palette(
+            colors,
+            [
+                (90, 110, 170),
+                (60, 130, 210),
+                (90, 170, 240),
+                (150, 200, 255),
+                (200, 220, 235),
+                (240, 230, 200),
+                (250, 210, 120),
+                (245, 180, 40),
+                (230, 140, 0),
+                (255, 235, 60),
+                (255, 255, 255),
+            ],
+        ),
+        Theme::Protanopia => insert_palette(
+            colors,
+            [
+                (0, 114, 178),
+                (86, 180, 233),
+                (160, 210, 240),
+                (230, 230, 230),
+                (240, 228, 66),
+                (230, 159, 0),
+                (180, 140, 60),
+                (120, 120, 220),
+                (180, 180, 255),
+                (255, 240, 150),
+                (255, 255, 255),
+            ],
+        ),
+        Theme::Tritanopia => insert_palette(
+            colors,
+            [
+                (0, 150, 150),
+                (0, 200, 200),
+                (150, 240, 240),
+                (230, 230, 230),
+                (255, 170, 190),
+                (255, 110, 140),
+                (220, 50, 80),
+                (170, 0, 40),
+                (255, 200, 200),
+                (200, 255, 255),
+                (255, 255, 255),
+            ],
+        ),
+        Theme::HighContrast => {
+            colors.insert(2, Color::White);
+            colors.insert(4, Color::BrightCyan);
+            colors.insert(8, Color::BrightYellow);
+            colors.insert(16, Color::BrightGreen);
+            colors.insert(32, Color::BrightMagenta);
+            colors.insert(64, Color::BrightRed);
+            colors.insert(128, Color::BrightBlue);
+            colors.insert(256, Color::Cyan);
+            colors.insert(512, Color::Yellow);
+            colors.insert(1024, Color::Magenta);
+            colors.insert(2048, Color::BrightWhite);
+        }
+    }
+}
+
+pub fn tile_color(colors: &HashMap<u64, Color>, value: u64) -> Color {
+    if let Some(&color) = colors.get(&value) {
+        return color;
+    }
+    let mut palette: Vec<(&u64, &Color)> = colors.iter().collect();
+    palette.sort_by_key(|&(&tile, _)| tile);
+    match palette.last() {
+        Some(&(&largest, _)) if value > largest && value.is_power_of_two() => {
+            let steps = (value.trailing_zeros() - largest.trailing_zeros()) as usize;
+            *palette[(steps - 1) % palette.len()].1
+        }
+        _ => Color::White,
+    }
+}
+
+pub fn tile_label(value: u64) -> String {
+    const SUFFIXES: [&str; 6] = ["k", "M", "G", "T", "P", "E"];
+    if value < 10_000 {
+        return value.to_string();
+    }
+    let mut scaled = value / 1000;
+    let mut suffix = 0;
+    while scaled >= 1000 {
+        scaled /= 1000;
+        suffix += 1;
+    }
+    format!("{}{}", scaled, SUFFIXES[suffix])
+}
+
+pub fn marked_label(value: u64) -> String {
+    match tile_marks() {
+        TileMarks::Symbols if value.is_power_of_two() && value > 1 => {
+            let symbol = SYMBOLS[(value.trailing_zeros() as usize - 1) % SYMBOLS.len()];
+            format!("{}{}", symbol, tile_label(value))
+        }
+        _ => tile_label(value),
+    }
+}
+
+pub fn cell_width() -> usize {
+    match tile_marks() {
+        TileMarks::Symbols => 5,
+        _ => 4,
+    }
+}
+
+pub fn paint(text: &str, colors: &HashMap<u64, Color>, value: u64) -> ColoredString {
+    let painted = text.color(tile_color(colors, value));
+    match tile_marks() {
+        TileMarks::Shading if value.is_power_of_two() && value > 1 => {
+            let level = 40 + 25 * ((value.trailing_zeros() - 1) % 5) as u8;
+            painted.on_truecolor(level, level, level)
+        }
+        _ => painted,
+    }
+}
+
+pub fn tile_cell(colors: &HashMap<u64, Color>, value: u64) -> ColoredString {
+    let text = format!("{:>width$}", marked_label(value), width = cell_width());
+    paint(&text, colors, value)
+}
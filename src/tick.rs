@@ -0,0 +1,72 @@
+use std::time::{Duration, Instant};
+
+const IDLE: Duration = Duration::from_secs(3600);
+
+struct Job<T> {
+    task: T,
+    every: Duration,
+    due: Instant,
+}
+
+/// Periodic jobs for a loop that polls for input. Each job keeps wall-clock time on its own
+/// interval, so timers run at the same speed however often keys wake the loop.
+pub struct Ticks<T> {
+    jobs: Vec<Job<T>>,
+}
+
+impl<T: Copy + PartialEq> Ticks<T> {
+    pub fn new() -> Self {
+        Ticks { jobs: Vec::new() }
+    }
+
+    pub fn every(&mut self, task: T, every: Duration) {
+        self.jobs.retain(|job| job.task != task);
+        self.jobs.push(Job {
+            task,
+            every,
+            due: Instant::now() + every,
+        });
+    }
+
+    /// Pushes a job a full interval into the future, e.g. an idle timer after a key press.
+    pub fn reset(&mut self, task: T) {
+        let now = Instant::now();
+        for job in self.jobs.iter_mut().filter(|job| job.task == task) {
+            job.due = now + job.every;
+        }
+    }
+
+    /// How long the loop may wait for input before the next job is due.
+    pub fn timeout(&self) -> Duration {
+        let now = Instant::now();
+        self.jobs
+            .iter()
+            .map(|job| job.due.saturating_duration_since(now))
+            .min()
+            .unwrap_or(IDLE)
+    }
+
+    /// The jobs that came due, each rescheduled from now so a stalled loop skips the
+    /// intervals it missed instead of running them back to back.
+    pub fn due(&mut self) -> Vec<T> {
+        let now = Instant::now();
+        let mut due = Vec::new();
+        for job in &mut self.jobs {
+            if job.due <= now {
+                due.push(job.task);
+                job.due = now + job.every;
+            }
+        }
+        due
+    }
+}
+
+impl<T: Copy + PartialEq> Default for Ticks<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub fn frame_interval(fps: u32) -> Duration {
+    Duration::from_millis(1000 / u64::from(fps.clamp(1, 60)))
+}
@@ -0,0 +1,361 @@
+use crate::accessibility;
+use crate::adversary;
+use crate::bot;
+use crate::config::{load_config, Config};
+use crate::difficulty::Difficulty;
+use crate::frame;
+use crate::i18n::t;
+use crate::theme::initialize_colors;
+use crate::tick::Ticks;
+use crate::zen::Relief;
+use crate::{campaign, history, load_game_state, play, puzzle, settings, zen, PlayOptions};
+use colored::{Color, Colorize};
+use crossterm::{
+    cursor,
+    event::{poll, read, Event, KeyCode},
+    terminal::{disable_raw_mode, enable_raw_mode, Clear, ClearType},
+    ExecutableCommand,
+};
+use rust_2048_game::engine::calculate_score;
+use std::{collections::HashMap, io::stdout, time::Duration};
+
+const LOGO: [[&str; 5]; 4] = [
+    [" #### ", "     #", " #### ", " #    ", " #####"],
+    [" #### ", " #   #", " #   #", " #   #", " #### "],
+    [" #  # ", " #  # ", " #####", "    # ", "    # "],
+    [" #### ", " #   #", " #### ", " #   #", " #### "],
+];
+const FRAME: Duration = Duration::from_millis(150);
+const MIN_SIZE: usize = 3;
+const MAX_SIZE: usize = 8;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Tick {
+    Frame,
+    Attract,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Item {
+    NewGame,
+    Continue,
+    Puzzles,
+    Stats,
+    Settings,
+    Quit,
+}
+
+impl Item {
+    const ALL: [Item; 6] = [
+        Item::NewGame,
+        Item::Continue,
+        Item::Puzzles,
+        Item::Stats,
+        Item::Settings,
+        Item::Quit,
+    ];
+
+    fn label(self) -> &'static str {
+        match self {
+            Item::NewGame => t("title.new_game"),
+            Item::Continue => t("title.continue"),
+            Item::Puzzles => t("title.puzzles"),
+            Item::Stats => t("title.stats"),
+            Item::Settings => t("title.settings"),
+            Item::Quit => t("title.quit"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    Classic,
+    Combo,
+    Zen,
+    Adversary,
+}
+
+impl Mode {
+    const ALL: [Mode; 4] = [Mode::Classic, Mode::Combo, Mode::Zen, Mode::Adversary];
+
+    fn describe(self) -> &'static str {
+        match self {
+            Mode::Classic => t("title.mode.classic"),
+            Mode::Combo => t("title.mode.combo"),
+            Mode::Zen => t("title.mode.zen"),
+            Mode::Adversary => t("title.mode.adversary"),
+        }
+    }
+
+    fn classic(self) -> bool {
+        matches!(self, Mode::Classic | Mode::Combo)
+    }
+}
+
+struct NewGame {
+    mode: Mode,
+    difficulty: Difficulty,
+    size: usize,
+}
+
+fn has_save() -> bool {
+    matches!(load_game_state(), Ok(Some(state)) if calculate_score(&state.game_board) > 0)
+}
+
+fn logo_line(row: usize, tick: usize, colors: &HashMap<u64, Color>) -> String {
+    let mut line = String::from("  ");
+    for (digit, glyph) in LOGO.iter().enumerate() {
+        let tile = 1u64 << ((digit + tick) % 11 + 1);
+        let text = glyph[row];
+        match colors.get(&tile) {
+            Some(&color) if !accessibility::colorless() => {
+                line.push_str(&text.color(color).bold().to_string())
+            }
+            _ => line.push_str(text),
+        }
+    }
+    line
+}
+
+fn draw_title(
+    selected: usize,
+    can_continue: bool,
+    tick: usize,
+    colors: &HashMap<u64, Color>,
+) -> crossterm::Result<()> {
+    let mut stdout = stdout();
+    if tick == 0 {
+        frame::invalidate();
+        stdout.execute(Clear(ClearType::All))?;
+    }
+    stdout.execute(cursor::MoveTo(0, 0))?;
+    println!();
+    for row in 0..5 {
+        println!("{}", logo_line(row, tick, colors));
+    }
+    println!();
+    for (i, item) in Item::ALL.iter().enumerate() {
+        let marker = if i == selected { ">" } else { " " };
+        if *item == Item::Continue && !can_continue {
+            println!(
+                " {} {:<10} ({})",
+                marker,
+                item.label().dimmed(),
+                t("title.no_save")
+            );
+        } else {
+            println!(" {} {}", marker, item.label());
+        }
+    }
+    println!();
+    println!(" > {}", t("title.hint"));
+    Ok(())
+}
+
+fn step(selected: usize, forward: bool, can_continue: bool) -> usize {
+    let count = Item::ALL.len();
+    let mut next = selected;
+    loop {
+        next = if forward {
+            (next + 1) % count
+        } else {
+            (next + count - 1) % count
+        };
+        if Item::ALL[next] != Item::Continue || can_continue {
+            return next;
+        }
+    }
+}
+
+fn menu(config: &Config, colors: &HashMap<u64, Color>) -> crossterm::Result<Item> {
+    let can_continue = has_save();
+    let mut selected = if can_continue { 1 } else { 0 };
+    let mut ticks = Ticks::new();
+    if !frame::net_friendly() {
+        ticks.every(Tick::Frame, FRAME);
+    }
+    if config.game.attract_secs > 0 {
+        ticks.every(Tick::Attract, Duration::from_secs(config.game.attract_secs));
+    }
+    let mut tick = 0;
+    loop {
+        draw_title(selected, can_continue, tick, colors)?;
+        if !poll(ticks.timeout())? {
+            for due in ticks.due() {
+                match due {
+                    Tick::Frame => tick += 1,
+                    Tick::Attract => {
+                        bot::demo()?;
+                        tick = 0;
+                        ticks.reset(Tick::Attract);
+                    }
+                }
+            }
+            continue;
+        }
+        ticks.reset(Tick::Attract);
+        if let Event::Key(key_event) = read()? {
+            match key_event.code {
+                KeyCode::Char('e') | KeyCode::Char('E') | KeyCode::Esc => return Ok(Item::Quit),
+                KeyCode::Up => selected = step(selected, false, can_continue),
+                KeyCode::Down => selected = step(selected, true, can_continue),
+                KeyCode::Enter => return Ok(Item::ALL[selected]),
+                _ => {}
+            }
+        }
+    }
+}
+
+fn cycle_size(size: usize, forward: bool) -> usize {
+    if forward {
+        (size + 1).min(MAX_SIZE)
+    } else {
+        size.saturating_sub(1).max(MIN_SIZE)
+    }
+}
+
+fn pick_game(config: &Config) -> crossterm::Result<Option<NewGame>> {
+    let mut choice = NewGame {
+        mode: Mode::Classic,
+        difficulty: Difficulty::Normal,
+        size: Difficulty::Normal.board_size(config),
+    };
+    let mut selected = 0;
+    loop {
+        frame::invalidate();
+        let mut stdout = stdout();
+        stdout.execute(Clear(ClearType::All))?;
+        stdout.execute(cursor::MoveTo(0, 0))?;
+        println!(" > {}", t("title.new_game"));
+        println!();
+        let mut rows = vec![format!(
+            "{:<11} : {}",
+            t("picker.mode"),
+            choice.mode.describe()
+        )];
+        if choice.mode.classic() {
+            rows.push(format!(
+                "{:<11} : {}, {}",
+                t("picker.difficulty"),
+                choice.difficulty.label(),
+                choice.difficulty.summary(config)
+            ));
+            rows.push(format!(
+                "{:<11} : {1}x{1}",
+                t("picker.board_size"),
+                choice.size
+            ));
+        }
+        selected = selected.min(rows.len() - 1);
+        for (i, row) in rows.iter().enumerate() {
+            let marker = if i == selected { ">" } else { " " };
+            println!(" {} {}", marker, row);
+        }
+        println!();
+        println!(" > {}", t("picker.hint"));
+
+        let Event::Key(key_event) = read()? else {
+            continue;
+        };
+        let forward = match key_event.code {
+            KeyCode::Esc | KeyCode::Char('e') | KeyCode::Char('E') => return Ok(None),
+            KeyCode::Enter => return Ok(Some(choice)),
+            KeyCode::Up => {
+                selected = selected.saturating_sub(1);
+                continue;
+            }
+            KeyCode::Down => {
+                selected = (selected + 1).min(rows.len() - 1);
+                continue;
+            }
+            KeyCode::Right => true,
+            KeyCode::Left => false,
+            _ => continue,
+        };
+        match selected {
+            0 => choice.mode = settings::cycle(&Mode::ALL, choice.mode, forward),
+            1 => {
+                choice.difficulty = settings::cycle(&Difficulty::ALL, choice.difficulty, forward);
+                choice.size = choice.difficulty.board_size(config);
+            }
+            _ => choice.size = cycle_size(choice.size, forward),
+        }
+    }
+}
+
+fn start(choice: NewGame, check_updates: bool, options: &PlayOptions) -> crossterm::Result<()> {
+    match choice.mode {
+        Mode::Zen => zen::run(Relief::Clear),
+        Mode::Adversary => adversary::run(2),
+        mode => play(PlayOptions {
+            check_updates,
+            difficulty: Some(choice.difficulty),
+            combo: mode == Mode::Combo,
+            submit: options.submit,
+            size: Some(choice.size),
+            fresh: true,
+            goals: options.goals.clone(),
+            ..PlayOptions::default()
+        }),
+    }
+}
+
+fn wait_for_key() -> crossterm::Result<()> {
+    println!(" > {}", t("title.back"));
+    enable_raw_mode()?;
+    let result = loop {
+        if let Event::Key(_) = read()? {
+            break Ok(());
+        }
+    };
+    disable_raw_mode()?;
+    result
+}
+
+/// Shows the title screen until the player quits, returning to it after every game.
+pub fn run(options: PlayOptions) -> crossterm::Result<()> {
+    let mut colors: HashMap<u64, Color> = HashMap::new();
+    let mut check_updates = options.check_updates;
+    loop {
+        let mut config = load_config();
+        initialize_colors(&mut colors, campaign::active_theme());
+        enable_raw_mode()?;
+        let item = menu(&config, &colors);
+        disable_raw_mode()?;
+        match item? {
+            Item::Quit => return Ok(()),
+            Item::NewGame => {
+                enable_raw_mode()?;
+                let choice = pick_game(&config);
+                disable_raw_mode()?;
+                let Some(choice) = choice? else {
+                    continue;
+                };
+                start(choice, check_updates, &options)?;
+                wait_for_key()?;
+            }
+            Item::Continue => {
+                play(PlayOptions {
+                    check_updates,
+                    submit: options.submit,
+                    resume: true,
+                    goals: options.goals.clone(),
+                    ..PlayOptions::default()
+                })?;
+                wait_for_key()?;
+            }
+            Item::Puzzles => {
+                puzzle::run(None)?;
+                wait_for_key()?;
+            }
+            Item::Stats => history::run()?,
+            Item::Settings => {
+                enable_raw_mode()?;
+                let result = settings::show(&mut config, &mut colors);
+                disable_raw_mode()?;
+                result?;
+            }
+        }
+        check_updates = false;
+    }
+}
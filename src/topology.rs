@@ -0,0 +1,228 @@
+use crate::engine::MAX_TILE;
+use std::collections::HashMap;
+
+pub trait Topology {
+    type Direction: Copy;
+
+    fn cell_count(&self) -> usize;
+    fn directions(&self) -> Vec<Self::Direction>;
+    fn neighbor(&self, cell: usize, direction: Self::Direction) -> Option<usize>;
+    fn opposite(&self, direction: Self::Direction) -> Self::Direction;
+}
+
+pub fn lines<T: Topology>(topology: &T, direction: T::Direction) -> Vec<Vec<usize>> {
+    let backwards = topology.opposite(direction);
+    (0..topology.cell_count())
+        .filter(|&cell| topology.neighbor(cell, direction).is_none())
+        .map(|edge| {
+            let mut line = vec![edge];
+            let mut cell = edge;
+            while let Some(next) = topology.neighbor(cell, backwards) {
+                line.push(next);
+                cell = next;
+            }
+            line
+        })
+        .collect()
+}
+
+fn shift_line(cells: &mut [u64], line: &[usize]) {
+    let tiles: Vec<u64> = line
+        .iter()
+        .map(|&i| cells[i])
+        .filter(|&val| val != 0)
+        .collect();
+    let mut shifted: Vec<u64> = Vec::with_capacity(line.len());
+    let mut i = 0;
+    while i < tiles.len() {
+        if i + 1 < tiles.len() && tiles[i] == tiles[i + 1] && tiles[i] <= MAX_TILE / 2 {
+            shifted.push(tiles[i] * 2);
+            i += 2;
+        } else {
+            shifted.push(tiles[i]);
+            i += 1;
+        }
+    }
+    shifted.resize(line.len(), 0);
+    for (&cell, value) in line.iter().zip(shifted) {
+        cells[cell] = value;
+    }
+}
+
+pub fn shift<T: Topology>(cells: &mut [u64], topology: &T, direction: T::Direction) -> bool {
+    let initial = cells.to_vec();
+    for line in lines(topology, direction) {
+        shift_line(cells, &line);
+    }
+    initial != cells
+}
+
+pub fn can_move<T: Topology>(cells: &[u64], topology: &T) -> bool {
+    topology.directions().into_iter().any(|direction| {
+        let mut next = cells.to_vec();
+        shift(&mut next, topology, direction)
+    })
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum HexDirection {
+    Up,
+    Down,
+    UpLeft,
+    UpRight,
+    DownLeft,
+    DownRight,
+}
+
+impl HexDirection {
+    fn offset(self) -> (i32, i32) {
+        match self {
+            HexDirection::Up => (0, -1),
+            HexDirection::Down => (0, 1),
+            HexDirection::UpLeft => (-1, 0),
+            HexDirection::UpRight => (1, -1),
+            HexDirection::DownLeft => (-1, 1),
+            HexDirection::DownRight => (1, 0),
+        }
+    }
+}
+
+pub struct HexTopology {
+    coords: Vec<(i32, i32)>,
+    index: HashMap<(i32, i32), usize>,
+}
+
+impl HexTopology {
+    pub fn new(radius: i32) -> Self {
+        let mut coords: Vec<(i32, i32)> = Vec::new();
+        for q in -radius..=radius {
+            for r in (-radius).max(-q - radius)..=radius.min(-q + radius) {
+                coords.push((q, r));
+            }
+        }
+        let index = coords.iter().enumerate().map(|(i, &c)| (c, i)).collect();
+        HexTopology { coords, index }
+    }
+
+    pub fn coords(&self) -> &[(i32, i32)] {
+        &self.coords
+    }
+
+    pub fn cell_at(&self, q: i32, r: i32) -> Option<usize> {
+        self.index.get(&(q, r)).copied()
+    }
+}
+
+impl Topology for HexTopology {
+    type Direction = HexDirection;
+
+    fn cell_count(&self) -> usize {
+        self.coords.len()
+    }
+
+    fn directions(&self) -> Vec<HexDirection> {
+        vec![
+            HexDirection::Up,
+            HexDirection::Down,
+            HexDirection::UpLeft,
+            HexDirection::UpRight,
+            HexDirection::DownLeft,
+            HexDirection::DownRight,
+        ]
+    }
+
+    fn neighbor(&self, cell: usize, direction: HexDirection) -> Option<usize> {
+        let (q, r) = self.coords[cell];
+        let (dq, dr) = direction.offset();
+        self.index.get(&(q + dq, r + dr)).copied()
+    }
+
+    fn opposite(&self, direction: HexDirection) -> HexDirection {
+        match direction {
+            HexDirection::Up => HexDirection::Down,
+            HexDirection::Down => HexDirection::Up,
+            HexDirection::UpLeft => HexDirection::DownRight,
+            HexDirection::UpRight => HexDirection::DownLeft,
+            HexDirection::DownLeft => HexDirection::UpRight,
+            HexDirection::DownRight => HexDirection::UpLeft,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct GridDirection {
+    pub axis: usize,
+    pub positive: bool,
+}
+
+pub struct GridTopology {
+    dims: Vec<usize>,
+}
+
+impl GridTopology {
+    pub fn new(dims: &[usize]) -> Self {
+        GridTopology {
+            dims: dims.to_vec(),
+        }
+    }
+
+    pub fn dims(&self) -> &[usize] {
+        &self.dims
+    }
+
+    pub fn coords(&self, cell: usize) -> Vec<usize> {
+        let mut rest = cell;
+        let mut coords = vec![0; self.dims.len()];
+        for (axis, &len) in self.dims.iter().enumerate().rev() {
+            coords[axis] = rest % len;
+            rest /= len;
+        }
+        coords
+    }
+
+    pub fn cell_at(&self, coords: &[usize]) -> usize {
+        coords
+            .iter()
+            .zip(&self.dims)
+            .fold(0, |cell, (&coord, &len)| cell * len + coord)
+    }
+}
+
+impl Topology for GridTopology {
+    type Direction = GridDirection;
+
+    fn cell_count(&self) -> usize {
+        self.dims.iter().product()
+    }
+
+    fn directions(&self) -> Vec<GridDirection> {
+        (0..self.dims.len())
+            .flat_map(|axis| {
+                [false, true]
+                    .into_iter()
+                    .map(move |positive| GridDirection { axis, positive })
+            })
+            .collect()
+    }
+
+    fn neighbor(&self, cell: usize, direction: GridDirection) -> Option<usize> {
+        let mut coords = self.coords(cell);
+        let coord = &mut coords[direction.axis];
+        if direction.positive {
+            if *coord + 1 >= self.dims[direction.axis] {
+                return None;
+            }
+            *coord += 1;
+        } else {
+            *coord = coord.checked_sub(1)?;
+        }
+        Some(self.cell_at(&coords))
+    }
+
+    fn opposite(&self, direction: GridDirection) -> GridDirection {
+        GridDirection {
+            positive: !direction.positive,
+            ..direction
+        }
+    }
+}
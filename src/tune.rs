@@ -0,0 +1,151 @@
+use crate::bench::{self, Summary};
+use rayon::prelude::*;
+use rust_2048_game::{ai, heuristic::Weights};
+use std::{fs, path::Path, str::FromStr, time::Instant};
+
+#[derive(Clone, Copy, Debug)]
+pub struct Sweep {
+    start: f64,
+    end: f64,
+    step: f64,
+}
+
+impl FromStr for Sweep {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let parse = |part: &str| {
+            part.trim()
+                .parse::<f64>()
+                .map_err(|_| format!("invalid number '{}'", part))
+        };
+        let parts: Vec<&str> = text.split(':').collect();
+        let sweep = match parts[..] {
+            [value] => Sweep {
+                start: parse(value)?,
+                end: parse(value)?,
+                step: 1.0,
+            },
+            [start, end, step] => Sweep {
+                start: parse(start)?,
+                end: parse(end)?,
+                step: parse(step)?,
+            },
+            _ => return Err("expected VALUE or START:END:STEP".to_string()),
+        };
+        if sweep.step <= 0.0 || sweep.end < sweep.start {
+            return Err("the step must be positive and END at least START".to_string());
+        }
+        Ok(sweep)
+    }
+}
+
+impl Sweep {
+    fn values(&self) -> Vec<f64> {
+        let steps = ((self.end - self.start) / self.step + 1e-9).floor() as usize;
+        (0..=steps)
+            .map(|i| self.start + i as f64 * self.step)
+            .collect()
+    }
+}
+
+struct Row {
+    weights: Weights,
+    summary: Summary,
+}
+
+impl Row {
+    fn csv(&self) -> String {
+        format!(
+            "{},{},{},{},{:.1},{},{:.3},{:.1}",
+            self.weights.empty_cells,
+            self.weights.monotonicity,
+            self.weights.corner,
+            self.summary.games,
+            self.summary.average_score,
+            self.summary.best_score,
+            self.summary.rate_2048,
+            self.summary.average_moves
+        )
+    }
+}
+
+pub fn run(
+    empty_cells: Sweep,
+    monotonicity: Sweep,
+    corner: Sweep,
+    games: u32,
+    seed: u64,
+    depth: u32,
+    output: &Path,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut candidates: Vec<Weights> = Vec::new();
+    for &empty_cells in &empty_cells.values() {
+        for &monotonicity in &monotonicity.values() {
+            for &corner in &corner.values() {
+                candidates.push(Weights {
+                    empty_cells,
+                    monotonicity,
+                    corner,
+                });
+            }
+        }
+    }
+    let games = games.max(1) as u64;
+    println!(
+        " > Playing {} games for each of {} weight sets at depth {} on {} threads...",
+        games,
+        candidates.len(),
+        depth,
+        rayon::current_num_threads()
+    );
+    let started = Instant::now();
+
+    let mut rows: Vec<Row> = candidates
+        .into_par_iter()
+        .map(|weights| {
+            let outcomes = bench::play_many(seed..seed + games, |seed| {
+                bench::play_game(seed, |game_board| {
+                    ai::evaluate_moves_with(game_board, depth, &weights)
+                })
+            });
+            Row {
+                weights,
+                summary: Summary::of(&outcomes),
+            }
+        })
+        .collect();
+
+    let mut csv = String::from(
+        "empty_cells,monotonicity,corner,games,avg_score,best_score,rate_2048,avg_moves\n",
+    );
+    for row in &rows {
+        csv.push_str(&row.csv());
+        csv.push('\n');
+    }
+    fs::write(output, csv)?;
+    println!(
+        " > Wrote {} rows to {} in {:.1}s",
+        rows.len(),
+        output.display(),
+        started.elapsed().as_secs_f64()
+    );
+
+    rows.sort_by(|a, b| b.summary.average_score.total_cmp(&a.summary.average_score));
+    let defaults = Weights::default();
+    for row in rows.iter().take(5) {
+        println!(
+            "   empty {:<5} monotonicity {:<5} corner {:<5} avg score {:.0}{}",
+            row.weights.empty_cells,
+            row.weights.monotonicity,
+            row.weights.corner,
+            row.summary.average_score,
+            if row.weights == defaults {
+                "  (current defaults)"
+            } else {
+                ""
+            }
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,156 @@
+use crate::accessibility;
+use crate::config::load_config;
+use crate::i18n::{label, t, tf};
+use crate::sound::Sound;
+use crate::theme::initialize_colors;
+use crate::{campaign, key_direction, read_high_score, render_board};
+use crossterm::{
+    event::{read, Event, KeyCode},
+    terminal::{disable_raw_mode, enable_raw_mode},
+};
+use rust_2048_game::engine::{apply_move, calculate_score, Direction};
+use std::collections::HashMap;
+
+struct Lesson {
+    name: &'static str,
+    board: [[u64; 4]; 4],
+    text: &'static [&'static str],
+    expected: &'static [Direction],
+    hint: &'static str,
+    success: &'static str,
+}
+
+const LESSONS: [Lesson; 5] = [
+    Lesson {
+        name: "tutorial.sliding.name",
+        board: [[0, 0, 0, 2], [0, 4, 0, 0], [0, 0, 0, 0], [2, 0, 0, 0]],
+        text: &["tutorial.sliding.text1", "tutorial.sliding.text2"],
+        expected: &[Direction::Left],
+        hint: "tutorial.sliding.hint",
+        success: "tutorial.sliding.success",
+    },
+    Lesson {
+        name: "tutorial.merging.name",
+        board: [[0, 0, 0, 0], [2, 0, 2, 0], [0, 0, 0, 0], [0, 4, 0, 4]],
+        text: &["tutorial.merging.text1", "tutorial.merging.text2"],
+        expected: &[Direction::Left, Direction::Right],
+        hint: "tutorial.merging.hint",
+        success: "tutorial.merging.success",
+    },
+    Lesson {
+        name: "tutorial.once.name",
+        board: [[2, 2, 4, 8], [0, 0, 0, 0], [0, 0, 0, 0], [0, 0, 0, 0]],
+        text: &["tutorial.once.text1", "tutorial.once.text2"],
+        expected: &[Direction::Left],
+        hint: "tutorial.once.hint",
+        success: "tutorial.once.success",
+    },
+    Lesson {
+        name: "tutorial.corner.name",
+        board: [[2, 0, 0, 0], [0, 0, 2, 4], [0, 4, 16, 32], [0, 8, 64, 256]],
+        text: &["tutorial.corner.text1", "tutorial.corner.text2"],
+        expected: &[Direction::Down, Direction::Right],
+        hint: "tutorial.corner.hint",
+        success: "tutorial.corner.success",
+    },
+    Lesson {
+        name: "tutorial.room.name",
+        board: [
+            [2, 4, 8, 16],
+            [4, 8, 16, 32],
+            [8, 16, 32, 64],
+            [2, 2, 4, 128],
+        ],
+        text: &["tutorial.room.text1", "tutorial.room.text2"],
+        expected: &[Direction::Right],
+        hint: "tutorial.room.hint",
+        success: "tutorial.room.success",
+    },
+];
+
+pub fn run() -> crossterm::Result<()> {
+    let mut colors: HashMap<u64, colored::Color> = HashMap::new();
+    initialize_colors(&mut colors, campaign::active_theme());
+    let mut sound = Sound::new(&load_config().sound);
+    let high_score = read_high_score();
+
+    enable_raw_mode()?;
+    let mut index = 0;
+    let mut game_board: Vec<Vec<u64>> = LESSONS[0].board.iter().map(|row| row.to_vec()).collect();
+    let mut feedback = String::new();
+    let mut passed = false;
+    while index < LESSONS.len() {
+        let lesson = &LESSONS[index];
+        let mut messages = vec![format!(
+            "{} {}",
+            label("label.tutorial"),
+            tf(
+                "tutorial.progress",
+                &[
+                    ("lesson", &(index + 1)),
+                    ("lessons", &LESSONS.len()),
+                    ("name", &t(lesson.name))
+                ]
+            )
+        )];
+        messages.extend(lesson.text.iter().map(|&line| t(line).to_string()));
+        if !feedback.is_empty() {
+            messages.push(feedback.clone());
+        }
+        messages.push(if passed {
+            t("tutorial.next").to_string()
+        } else {
+            t("tutorial.exit").to_string()
+        });
+        render_board(
+            &game_board,
+            &colors,
+            calculate_score(&game_board),
+            high_score,
+            false,
+            &messages,
+        )?;
+
+        let Event::Key(key_event) = read()? else {
+            continue;
+        };
+        if matches!(key_event.code, KeyCode::Char('e') | KeyCode::Char('E')) {
+            break;
+        }
+        if passed {
+            index += 1;
+            if let Some(next) = LESSONS.get(index) {
+                game_board = next.board.iter().map(|row| row.to_vec()).collect();
+            }
+            feedback.clear();
+            passed = false;
+            continue;
+        }
+        if key_event.code == KeyCode::Char('m') || key_event.code == KeyCode::Char('M') {
+            sound.toggle_mute();
+            continue;
+        }
+        let Some(direction) = key_direction(key_event.code) else {
+            continue;
+        };
+        let mut next = game_board.clone();
+        let result = apply_move(&mut next, direction);
+        if !result.moved {
+            feedback = t("tutorial.no_change").to_string();
+        } else if lesson.expected.contains(&direction) {
+            sound.play_move(&result);
+            accessibility::record_move(direction, &result);
+            game_board = next;
+            feedback = tf("tutorial.well_done", &[("success", &t(lesson.success))]);
+            passed = true;
+        } else {
+            feedback = t(lesson.hint).to_string();
+        }
+    }
+    disable_raw_mode()?;
+
+    if index >= LESSONS.len() {
+        println!(" > {}", t("tutorial.complete"));
+    }
+    Ok(())
+}
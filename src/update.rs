@@ -0,0 +1,131 @@
+use crate::accessibility;
+use crate::frame;
+use crate::i18n::{t, tf};
+use crossterm::{
+    cursor,
+    event::{read, Event, KeyCode},
+    terminal::{self, Clear, ClearType},
+    ExecutableCommand,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs,
+    io::stdout,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+const CACHE_FILE: &str = "update_check.json";
+const RELEASES_URL: &str = "https://api.github.com/repos/d3nis01/rust_2048_game/releases/latest";
+const CHECK_INTERVAL_SECS: u64 = 24 * 60 * 60;
+
+#[derive(Serialize, Deserialize)]
+struct UpdateCache {
+    checked_at: u64,
+    latest: Option<Release>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Release {
+    pub tag_name: String,
+    #[serde(default)]
+    pub body: Option<String>,
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+fn parse_version(version: &str) -> Vec<u64> {
+    version
+        .trim_start_matches('v')
+        .split('.')
+        .map(|part| part.parse().unwrap_or(0))
+        .collect()
+}
+
+fn fetch_latest() -> Option<Release> {
+    let agent: ureq::Agent = ureq::Agent::config_builder()
+        .timeout_global(Some(Duration::from_secs(3)))
+        .build()
+        .into();
+    agent
+        .get(RELEASES_URL)
+        .header("User-Agent", "rust_2048_game")
+        .call()
+        .ok()?
+        .body_mut()
+        .read_json()
+        .ok()
+}
+
+pub fn check_for_update() -> Option<Release> {
+    let cached: Option<UpdateCache> = fs::read_to_string(CACHE_FILE)
+        .ok()
+        .and_then(|data| serde_json::from_str(&data).ok());
+
+    let latest = match cached {
+        Some(cache) if now().saturating_sub(cache.checked_at) < CHECK_INTERVAL_SECS => cache.latest,
+        _ => {
+            let latest = fetch_latest();
+            let cache = UpdateCache {
+                checked_at: now(),
+                latest: latest.clone(),
+            };
+            if let Ok(serialized) = serde_json::to_string(&cache) {
+                let _ = fs::write(CACHE_FILE, serialized);
+            }
+            latest
+        }
+    }?;
+
+    if parse_version(&latest.tag_name) > parse_version(env!("CARGO_PKG_VERSION")) {
+        Some(latest)
+    } else {
+        None
+    }
+}
+
+pub fn show_changelog(release: &Release) -> crossterm::Result<()> {
+    let changelog = release
+        .body
+        .clone()
+        .unwrap_or_else(|| t("changelog.empty").to_string());
+    let lines: Vec<&str> = changelog.lines().collect();
+    let visible = terminal::size()
+        .map(|(_, rows)| rows.saturating_sub(4) as usize)
+        .unwrap_or(20)
+        .max(1);
+    let mut offset = 0;
+
+    frame::invalidate();
+    loop {
+        let mut stdout = stdout();
+        stdout.execute(Clear(ClearType::All))?;
+        stdout.execute(cursor::MoveTo(0, 0))?;
+        println!(
+            " > {}",
+            tf("changelog.title", &[("version", &release.tag_name)])
+        );
+        println!();
+        for line in lines.iter().skip(offset).take(visible) {
+            println!("   {}", accessibility::to_ascii(line));
+        }
+        println!();
+        println!(" > {}", t("changelog.hint"));
+
+        if let Event::Key(key_event) = read()? {
+            match key_event.code {
+                KeyCode::Up => offset = offset.saturating_sub(1),
+                KeyCode::Down => {
+                    if offset + visible < lines.len() {
+                        offset += 1;
+                    }
+                }
+                _ => return Ok(()),
+            }
+        }
+    }
+}
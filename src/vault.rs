@@ -0,0 +1,242 @@
+use crate::config::SavesConfig;
+use crate::error::GameError;
+use crate::i18n::{t, tf};
+use crate::profile;
+use crate::signing::{from_hex, to_hex};
+use argon2::Argon2;
+use chacha20poly1305::{
+    aead::{Aead, KeyInit},
+    ChaCha20Poly1305, Key, Nonce,
+};
+use rand::{thread_rng, RngCore};
+use serde::de::DeserializeOwned;
+use std::{
+    env, fs,
+    io::{self, IsTerminal},
+    path::{Path, PathBuf},
+    sync::{Mutex, OnceLock},
+};
+
+const PREFIX: &str = "2048-vault-v1:";
+const PASSPHRASE_VAR: &str = "RUST_2048_PASSPHRASE";
+const PROTECTED_FILES: [&str; 4] = [
+    "game_state.json",
+    "stats.json",
+    "history.json",
+    "scores.json",
+];
+
+struct Vault {
+    passphrase: String,
+    salt: [u8; 16],
+    keys: Mutex<Vec<([u8; 16], [u8; 32])>>,
+}
+
+static VAULT: OnceLock<Vault> = OnceLock::new();
+
+impl Vault {
+    fn key(&self, salt: [u8; 16]) -> Result<[u8; 32], GameError> {
+        let mut keys = self.keys.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some((_, key)) = keys.iter().find(|(known, _)| *known == salt) {
+            return Ok(*key);
+        }
+        let mut key = [0u8; 32];
+        Argon2::default()
+            .hash_password_into(self.passphrase.as_bytes(), &salt, &mut key)
+            .map_err(|e| GameError::Locked(e.to_string()))?;
+        keys.push((salt, key));
+        Ok(key)
+    }
+
+    fn encrypt(&self, data: &str) -> Result<String, GameError> {
+        let mut nonce = [0u8; 12];
+        thread_rng().fill_bytes(&mut nonce);
+        let key = self.key(self.salt)?;
+        let sealed = ChaCha20Poly1305::new(Key::from_slice(&key))
+            .encrypt(Nonce::from_slice(&nonce), data.as_bytes())
+            .map_err(|_| GameError::Locked(t("vault.encrypt_failed").to_string()))?;
+        Ok(format!(
+            "{}{}:{}:{}",
+            PREFIX,
+            to_hex(&self.salt),
+            to_hex(&nonce),
+            to_hex(&sealed)
+        ))
+    }
+
+    fn decrypt(&self, path: &Path, sealed: &str) -> Result<String, GameError> {
+        let corrupted = |reason: &str| GameError::CorruptedSave {
+            path: PathBuf::from(path),
+            reason: reason.to_string(),
+        };
+        let parts: Vec<Vec<u8>> = sealed
+            .trim()
+            .split(':')
+            .map(from_hex)
+            .collect::<Option<_>>()
+            .ok_or_else(|| corrupted(t("vault.not_hex")))?;
+        let [salt, nonce, data] = &parts[..] else {
+            return Err(corrupted(t("vault.incomplete")));
+        };
+        let salt: [u8; 16] = salt[..]
+            .try_into()
+            .map_err(|_| corrupted(t("vault.bad_salt")))?;
+        if nonce.len() != 12 {
+            return Err(corrupted(t("vault.bad_nonce")));
+        }
+        let key = self.key(salt)?;
+        let plain = ChaCha20Poly1305::new(Key::from_slice(&key))
+            .decrypt(Nonce::from_slice(nonce), &data[..])
+            .map_err(|_| {
+                GameError::Locked(tf("vault.wrong_passphrase", &[("path", &path.display())]))
+            })?;
+        String::from_utf8(plain).map_err(|e| corrupted(&e.to_string()))
+    }
+}
+
+pub fn unlock(config: &SavesConfig) -> Result<(), GameError> {
+    if !config.encrypt {
+        return Ok(());
+    }
+    let passphrase = match env::var(PASSPHRASE_VAR) {
+        Ok(passphrase) => passphrase,
+        Err(_) if io::stdin().is_terminal() => {
+            rpassword::prompt_password(format!(" > {}: ", t("vault.prompt")))
+                .map_err(GameError::Terminal)?
+        }
+        Err(_) => {
+            return Err(GameError::Locked(tf(
+                "vault.need_passphrase",
+                &[("variable", &PASSPHRASE_VAR)],
+            )))
+        }
+    };
+    if passphrase.is_empty() {
+        return Err(GameError::Config(t("vault.empty").to_string()));
+    }
+    let mut salt = [0u8; 16];
+    thread_rng().fill_bytes(&mut salt);
+    let vault = Vault {
+        passphrase,
+        salt,
+        keys: Mutex::new(Vec::new()),
+    };
+    for file in PROTECTED_FILES {
+        let path = profile::path(file);
+        if let Some(sealed) = fs::read_to_string(&path)
+            .ok()
+            .and_then(|data| data.strip_prefix(PREFIX).map(str::to_string))
+        {
+            vault.decrypt(&path, &sealed)?;
+        }
+    }
+    VAULT
+        .set(vault)
+        .map_err(|_| GameError::Locked(t("vault.unlocked").to_string()))
+}
+
+pub fn read(path: &Path) -> Result<String, GameError> {
+    let data = fs::read_to_string(path)?;
+    let Some(sealed) = data.strip_prefix(PREFIX) else {
+        return Ok(data);
+    };
+    match VAULT.get() {
+        Some(vault) => vault.decrypt(path, sealed),
+        None => Err(GameError::Locked(tf(
+            "vault.encrypted_read",
+            &[("path", &path.display())],
+        ))),
+    }
+}
+
+/// Reads and parses a save, `None` when there is none yet. A save that does not parse is
+/// an error rather than an empty one, so nothing gets saved over it.
+pub fn load<T: DeserializeOwned>(path: &Path) -> Result<Option<T>, GameError> {
+    let data = match read(path) {
+        Ok(data) => data,
+        Err(e) if e.is_missing() => return Ok(None),
+        Err(e) => return Err(e),
+    };
+    serde_json::from_str(&data)
+        .map(Some)
+        .map_err(|e| GameError::CorruptedSave {
+            path: path.to_path_buf(),
+            reason: e.to_string(),
+        })
+}
+
+pub fn write(path: &Path, data: &str) -> Result<(), GameError> {
+    match VAULT.get() {
+        Some(vault) => fs::write(path, vault.encrypt(data)?)?,
+        None if fs::read_to_string(path).is_ok_and(|old| old.starts_with(PREFIX)) => {
+            return Err(GameError::Locked(tf(
+                "vault.encrypted_write",
+                &[("path", &path.display())],
+            )))
+        }
+        None => fs::write(path, data)?,
+    }
+    tracing::debug!(path = %path.display(), bytes = data.len(), "saved");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scratch;
+
+    fn vault(passphrase: &str) -> Vault {
+        Vault {
+            passphrase: passphrase.to_string(),
+            salt: [3; 16],
+            keys: Mutex::new(Vec::new()),
+        }
+    }
+
+    fn seal(vault: &Vault, data: &str) -> String {
+        let sealed = vault.encrypt(data).unwrap();
+        sealed.strip_prefix(PREFIX).unwrap().to_string()
+    }
+
+    #[test]
+    fn sealed_data_opens_with_the_same_passphrase() {
+        let path = Path::new("stats.json");
+        let sealed = seal(&vault("correct horse"), r#"{"games": 3}"#);
+        assert!(!sealed.contains("games"));
+        let opened = vault("correct horse").decrypt(path, &sealed).unwrap();
+        assert_eq!(opened, r#"{"games": 3}"#);
+    }
+
+    #[test]
+    fn sealed_data_refuses_another_passphrase_or_tampering() {
+        let path = Path::new("stats.json");
+        let sealed = seal(&vault("correct horse"), "secret");
+        assert!(matches!(
+            vault("battery staple").decrypt(path, &sealed),
+            Err(GameError::Locked(_))
+        ));
+        let mut tampered = sealed.clone();
+        let last = if tampered.ends_with('0') { "1" } else { "0" };
+        tampered.replace_range(tampered.len() - 1.., last);
+        assert!(vault("correct horse").decrypt(path, &tampered).is_err());
+        assert!(matches!(
+            vault("correct horse").decrypt(path, "00:11"),
+            Err(GameError::CorruptedSave { .. })
+        ));
+    }
+
+    #[test]
+    fn load_tells_a_missing_save_from_a_broken_one() {
+        scratch::run(|dir| {
+            let path = dir.join("stats.json");
+            assert!(matches!(load::<Vec<u64>>(&path), Ok(None)));
+            fs::write(&path, "[1, 2").unwrap();
+            assert!(matches!(
+                load::<Vec<u64>>(&path),
+                Err(GameError::CorruptedSave { .. })
+            ));
+            fs::write(&path, "[1, 2]").unwrap();
+            assert_eq!(load::<Vec<u64>>(&path).unwrap(), Some(vec![1, 2]));
+        });
+    }
+}
@@ -0,0 +1,176 @@
+use crate::i18n::{self, tf};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use rayon::prelude::*;
+use rust_2048_game::conformance::{check, Divergence};
+use rust_2048_game::engine::{Direction, OBSTACLE};
+use std::time::Instant;
+
+const CHUNK: u64 = 100_000;
+/// Positions printed in full, the rest are only counted.
+const MAX_REPORTED: usize = 10;
+
+/// Mostly small tiles so neighbours often match, with gaps and the odd obstacle.
+fn random_board(seed: u64, size: Option<usize>) -> Vec<Vec<u64>> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let (rows, cols) = match size {
+        Some(size) => (size, size),
+        None => (rng.gen_range(1..=6), rng.gen_range(1..=6)),
+    };
+    let top = rng.gen_range(1..=11);
+    (0..rows)
+        .map(|_| {
+            (0..cols)
+                .map(|_| match rng.gen_range(0..20) {
+                    0..=6 => 0,
+                    7 => OBSTACLE,
+                    _ => 1 << rng.gen_range(1..=top),
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn print_board(board: &[Vec<u64>]) {
+    for row in board {
+        let cells: Vec<String> = row
+            .iter()
+            .map(|&cell| match cell {
+                OBSTACLE => "##".to_string(),
+                cell => cell.to_string(),
+            })
+            .collect();
+        println!("     {}", cells.join(" "));
+    }
+}
+
+fn directions(directions: &[Direction]) -> String {
+    let names: Vec<&str> = directions
+        .iter()
+        .map(|&direction| i18n::direction(direction))
+        .collect();
+    names.join(", ")
+}
+
+fn print_divergence(divergence: &Divergence) {
+    match divergence {
+        Divergence::Move {
+            direction,
+            expected_board,
+            expected_points,
+            actual_board,
+            actual_points,
+        } => {
+            println!(
+                "   {}",
+                tf(
+                    "verify.reference_move",
+                    &[
+                        ("direction", &i18n::direction(*direction)),
+                        ("points", expected_points)
+                    ]
+                )
+            );
+            print_board(expected_board);
+            println!(
+                "   {}",
+                tf("verify.engine_move", &[("points", actual_points)])
+            );
+            print_board(actual_board);
+        }
+        Divergence::Score { expected, actual } => {
+            println!(
+                "   {}",
+                tf(
+                    "verify.score",
+                    &[("expected", expected), ("actual", actual)]
+                )
+            );
+        }
+        Divergence::LegalMoves { expected, actual } => {
+            println!(
+                "   {}",
+                tf(
+                    "verify.legal_moves",
+                    &[
+                        ("expected", &directions(expected)),
+                        ("actual", &directions(actual))
+                    ]
+                )
+            );
+        }
+    }
+}
+
+/// Checks the engine against the slow reference on `positions` seeded random boards, one
+/// seed per board from `seed` up so any divergence can be replayed alone. Returns whether
+/// the engines agreed everywhere.
+pub fn run(positions: u64, seed: u64, size: Option<usize>) -> bool {
+    println!(
+        " > {}",
+        tf(
+            "verify.start",
+            &[
+                ("positions", &positions),
+                ("seed", &seed),
+                ("threads", &rayon::current_num_threads())
+            ]
+        )
+    );
+    println!();
+    let started = Instant::now();
+    let mut divergent = 0;
+    let mut reported = 0;
+    let mut start = seed;
+    let end = seed.saturating_add(positions);
+    while start < end {
+        let chunk = start..end.min(start.saturating_add(CHUNK));
+        let mut found: Vec<(u64, Vec<Vec<u64>>, Vec<Divergence>)> = chunk
+            .clone()
+            .into_par_iter()
+            .filter_map(|seed| {
+                let board = random_board(seed, size);
+                let divergences = check(&board);
+                (!divergences.is_empty()).then_some((seed, board, divergences))
+            })
+            .collect();
+        found.sort_by_key(|(seed, _, _)| *seed);
+        divergent += found.len();
+        for (seed, board, divergences) in found.iter().take(MAX_REPORTED - reported) {
+            println!(" > {}", tf("verify.position", &[("seed", seed)]));
+            print_board(board);
+            for divergence in divergences {
+                print_divergence(divergence);
+            }
+            println!();
+            reported += 1;
+        }
+        start = chunk.end;
+    }
+    let secs = started.elapsed().as_secs_f64();
+    println!(
+        " > {}",
+        tf(
+            "verify.rate",
+            &[
+                ("positions", &positions),
+                ("secs", &format!("{:.1}", secs)),
+                (
+                    "rate",
+                    &format!("{:.0}", positions as f64 / secs.max(f64::EPSILON))
+                )
+            ]
+        )
+    );
+    if divergent == 0 {
+        println!(" > {}", i18n::t("verify.matches"));
+    } else {
+        println!(
+            " > {}",
+            tf(
+                "verify.diverge",
+                &[("count", &divergent), ("positions", &positions)]
+            )
+        );
+    }
+    divergent == 0
+}
@@ -0,0 +1,191 @@
+use crate::i18n::t;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use sha1::{Digest, Sha1};
+use std::{
+    io::{self, BufRead, BufReader, Write},
+    net::{TcpListener, TcpStream},
+    sync::{
+        mpsc::{self, Sender},
+        Mutex, OnceLock,
+    },
+    thread,
+    time::Duration,
+};
+
+const VIEWER: &str = include_str!("../web/viewer.html");
+/// Fixed by RFC 6455, appended to the client's key before hashing.
+const WEBSOCKET_GUID: &str = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
+const READ_TIMEOUT: Duration = Duration::from_secs(1);
+const WRITE_TIMEOUT: Duration = Duration::from_millis(50);
+const MAX_HEADER_LINES: usize = 64;
+
+/// Events on their way to the thread that writes them to the viewers.
+static OUTBOX: OnceLock<Sender<(String, bool)>> = OnceLock::new();
+static VIEWERS: Mutex<Vec<TcpStream>> = Mutex::new(Vec::new());
+/// The last board sent, so a page opened mid-game has something to show straight away.
+static LATEST: Mutex<Option<String>> = Mutex::new(None);
+
+struct Request {
+    path: String,
+    websocket_key: Option<String>,
+}
+
+fn read_request(stream: &TcpStream) -> io::Result<Request> {
+    let mut reader = BufReader::new(stream);
+    let mut line = String::new();
+    reader.read_line(&mut line)?;
+    let path = line.split_whitespace().nth(1).unwrap_or("/").to_string();
+    let mut websocket_key = None;
+    for _ in 0..MAX_HEADER_LINES {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 || line.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("sec-websocket-key") {
+                websocket_key = Some(value.trim().to_string());
+            }
+        }
+    }
+    Ok(Request {
+        path,
+        websocket_key,
+    })
+}
+
+fn respond(mut stream: &TcpStream, status: &str, content_type: &str, body: &str) -> io::Result<()> {
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nAccess-Control-Allow-Origin: *\r\nConnection: close\r\n\r\n{}",
+        status,
+        content_type,
+        body.len(),
+        body
+    )
+}
+
+fn frame(text: &str) -> Vec<u8> {
+    let payload = text.as_bytes();
+    let mut frame = vec![0x81];
+    match payload.len() {
+        length if length < 126 => frame.push(length as u8),
+        length if length <= u16::MAX as usize => {
+            frame.push(126);
+            frame.extend_from_slice(&(length as u16).to_be_bytes());
+        }
+        length => {
+            frame.push(127);
+            frame.extend_from_slice(&(length as u64).to_be_bytes());
+        }
+    }
+    frame.extend_from_slice(payload);
+    frame
+}
+
+fn upgrade(mut stream: TcpStream, key: &str) -> io::Result<()> {
+    let accept = STANDARD.encode(Sha1::digest(format!("{}{}", key, WEBSOCKET_GUID)));
+    write!(
+        stream,
+        "HTTP/1.1 101 Switching Protocols\r\nUpgrade: websocket\r\nConnection: Upgrade\r\nSec-WebSocket-Accept: {}\r\n\r\n",
+        accept
+    )?;
+    stream.set_write_timeout(Some(WRITE_TIMEOUT))?;
+    if let Some(latest) = LATEST.lock().ok().and_then(|latest| latest.clone()) {
+        stream.write_all(&frame(&latest))?;
+    }
+    if let Ok(mut viewers) = VIEWERS.lock() {
+        viewers.push(stream);
+    }
+    Ok(())
+}
+
+fn handle(stream: TcpStream) -> io::Result<()> {
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+    let request = read_request(&stream)?;
+    match (request.path.as_str(), request.websocket_key) {
+        ("/ws", Some(key)) => upgrade(stream, &key),
+        ("/" | "/index.html", _) => respond(&stream, "200 OK", "text/html; charset=utf-8", VIEWER),
+        ("/board", _) => {
+            let latest = LATEST.lock().ok().and_then(|latest| latest.clone());
+            respond(
+                &stream,
+                "200 OK",
+                "application/json",
+                latest.as_deref().unwrap_or("null"),
+            )
+        }
+        _ => respond(&stream, "404 Not Found", "text/plain", "not found"),
+    }
+}
+
+/// Hands `event` to every viewer, dropping those that cannot keep up.
+fn deliver(event: &str, board: bool) {
+    if board {
+        if let Ok(mut latest) = LATEST.lock() {
+            *latest = Some(event.to_string());
+        }
+    }
+    let Ok(mut viewers) = VIEWERS.lock() else {
+        return;
+    };
+    let frame = frame(event);
+    viewers.retain_mut(|viewer| viewer.write_all(&frame).is_ok());
+}
+
+/// Serves the viewer page and streams every broadcast event to it over a WebSocket. The
+/// game only queues each event; background threads answer requests and do the writing,
+/// so the game never waits on a slow browser.
+pub fn start(address: &str) -> io::Result<()> {
+    let listener = TcpListener::bind(address)?;
+    let (sender, receiver) = mpsc::channel();
+    if OUTBOX.set(sender).is_err() {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            t("web.running"),
+        ));
+    }
+    thread::spawn(move || {
+        for (event, board) in receiver {
+            deliver(&event, board);
+        }
+    });
+    thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            if let Err(e) = handle(stream) {
+                tracing::debug!(error = %e, "web viewer request failed");
+            }
+        }
+    });
+    Ok(())
+}
+
+pub fn serving() -> bool {
+    OUTBOX.get().is_some()
+}
+
+/// Queues `event` for the viewers, `board` marking it as the latest board to show.
+pub fn publish(event: &str, board: bool) {
+    if let Some(outbox) = OUTBOX.get() {
+        let _ = outbox.send((event.to_string(), board));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frames_pick_the_shortest_length_header() {
+        assert_eq!(frame("hi"), [0x81, 2, b'h', b'i']);
+
+        let medium = "x".repeat(126);
+        assert_eq!(frame(&medium)[..4], [0x81, 126, 0, 126]);
+        assert_eq!(frame(&medium).len(), 4 + 126);
+
+        let long = "x".repeat(70_000);
+        let long_frame = frame(&long);
+        assert_eq!(long_frame[..2], [0x81, 127]);
+        assert_eq!(long_frame[2..10], 70_000u64.to_be_bytes());
+        assert_eq!(long_frame.len(), 10 + 70_000);
+    }
+}
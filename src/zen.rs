@@ -0,0 +1,110 @@
+use crate::accessibility::Narrator;
+use crate::broadcast::BroadcastObserver;
+use crate::config::load_config;
+use crate::help;
+use crate::i18n::{label, t, tf};
+use crate::sound::Sound;
+use crate::theme::initialize_colors;
+use crate::{campaign, integrations, key_direction, render_board};
+use clap::ValueEnum;
+use crossterm::{
+    event::{read, Event, KeyCode},
+    terminal::{disable_raw_mode, enable_raw_mode},
+};
+use rust_2048_game::engine::max_tile;
+use rust_2048_game::game::Game;
+use rust_2048_game::policy::{ClearLowest, GameOverPolicy, Shuffle};
+use std::collections::HashMap;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, ValueEnum)]
+pub enum Relief {
+    Clear,
+    Shuffle,
+}
+
+impl Relief {
+    fn policy(self) -> Box<dyn GameOverPolicy> {
+        match self {
+            Relief::Clear => Box::new(ClearLowest),
+            Relief::Shuffle => Box::new(Shuffle::new()),
+        }
+    }
+
+    fn describe(self) -> &'static str {
+        match self {
+            Relief::Clear => t("zen.clear"),
+            Relief::Shuffle => t("zen.shuffle"),
+        }
+    }
+}
+
+pub fn run(relief: Relief) -> crossterm::Result<()> {
+    let mut colors: HashMap<u64, colored::Color> = HashMap::new();
+    initialize_colors(&mut colors, campaign::active_theme());
+    let mut sound = Sound::new(&load_config().sound);
+    let mut show_risk = false;
+
+    let mut game = Game::new(4);
+    game.set_policy(relief.policy());
+    game.subscribe(Box::new(BroadcastObserver));
+    game.subscribe(Box::new(Narrator));
+    for observer in integrations(t("mode.zen")) {
+        game.subscribe(observer);
+    }
+    let mut moves: u32 = 0;
+
+    enable_raw_mode()?;
+    loop {
+        let messages = vec![
+            format!(
+                "{} {}",
+                label("label.zen"),
+                tf("zen.status", &[("relief", &relief.describe())])
+            ),
+            format!("{} {}", label("label.moves"), moves),
+            format!(
+                "{} {}",
+                label("label.relieved"),
+                tf("zen.relieved", &[("count", &game.rescues())])
+            ),
+        ];
+        let best = max_tile(game.board());
+        render_board(
+            game.board(),
+            &colors,
+            game.score(),
+            best,
+            show_risk,
+            &messages,
+        )?;
+        if game.is_over() {
+            println!(" >> {} <<", t("zen.over"));
+            break;
+        }
+
+        if let Event::Key(key_event) = read()? {
+            match key_event.code {
+                KeyCode::Char('e') | KeyCode::Char('E') => break,
+                KeyCode::Char('r') | KeyCode::Char('R') => show_risk = !show_risk,
+                KeyCode::Char('m') | KeyCode::Char('M') => {
+                    sound.toggle_mute();
+                }
+                KeyCode::Char('?') | KeyCode::F(1) => {
+                    help::show(t("mode.zen"), t("zen.objective"), &[])?;
+                }
+                code => {
+                    let Some(direction) = key_direction(code) else {
+                        continue;
+                    };
+                    let result = game.play(direction);
+                    if result.moved {
+                        sound.play_move(&result);
+                        moves += 1;
+                    }
+                }
+            }
+        }
+    }
+    disable_raw_mode()?;
+    Ok(())
+}
@@ -0,0 +1,48 @@
+use rust_2048_game::conformance::{check, engine, load, reference, run, vectors, Divergence};
+use rust_2048_game::engine::{Direction, OBSTACLE};
+use std::path::Path;
+
+#[test]
+fn engine_passes_every_vector() {
+    let vectors = vectors();
+    let report = run(engine, &vectors);
+    assert!(report.is_ok(), "{:#?}", report.failures);
+    assert_eq!(report.passed, vectors.len());
+}
+
+#[test]
+fn engine_without_merges_is_reported() {
+    let no_merges = |board: &[Vec<u64>], _: Direction| (board.to_vec(), 0);
+    let report = run(no_merges, &vectors());
+    assert!(!report.is_ok());
+    assert!(report
+        .failures
+        .iter()
+        .any(|failure| failure.name == "merge a pair"));
+}
+
+#[test]
+fn vector_file_loads_like_builtin_set() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("conformance/moves.json");
+    assert_eq!(load(&path).unwrap(), vectors());
+}
+
+#[test]
+fn reference_passes_every_vector() {
+    let report = run(reference, &vectors());
+    assert!(report.is_ok(), "{:#?}", report.failures);
+}
+
+#[test]
+fn engines_agree_on_every_vector_board() {
+    for vector in vectors() {
+        assert_eq!(
+            check(&vector.board),
+            Vec::<Divergence>::new(),
+            "{}",
+            vector.name
+        );
+    }
+    let blocked = vec![vec![2, OBSTACLE, 2], vec![4, 4, OBSTACLE]];
+    assert!(check(&blocked).is_empty());
+}
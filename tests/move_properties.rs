@@ -0,0 +1,660 @@
+use proptest::prelude::*;
+use rust_2048_game::ai::canonical_hash;
+use rust_2048_game::engine::{
+    apply_move, legal_moves, Board, Direction, Merge, MoveResult, Spawner, OBSTACLE,
+};
+use rust_2048_game::game::{Game, GameObserver};
+use rust_2048_game::journal::Journal;
+use rust_2048_game::link::{BoardLink, MAX_TILE};
+use rust_2048_game::mask::Mask;
+use rust_2048_game::policy::{ClearLowest, GameOverPolicy, Shuffle};
+use rust_2048_game::powerup::{self, Special, Tile};
+use rust_2048_game::scoring::Combo;
+use rust_2048_game::spawn::{Evil, Gentle, Minimax, SpawnStrategy, Uniform};
+use std::{cell::RefCell, rc::Rc};
+
+fn tile() -> impl Strategy<Value = u64> + Clone {
+    prop_oneof![
+        3 => Just(0),
+        6 => (1u32..=11).prop_map(|exponent| 1 << exponent),
+    ]
+}
+
+fn cell() -> impl Strategy<Value = u64> + Clone {
+    prop_oneof![
+        12 => tile(),
+        1 => Just(OBSTACLE),
+    ]
+}
+
+fn board_of<T: std::fmt::Debug>(
+    cell: impl Strategy<Value = T> + Clone,
+) -> impl Strategy<Value = Vec<Vec<T>>> {
+    (2usize..=6).prop_flat_map(move |size| {
+        prop::collection::vec(prop::collection::vec(cell.clone(), size), size)
+    })
+}
+
+fn rectangle() -> impl Strategy<Value = Vec<Vec<u64>>> {
+    (1usize..=6, 1usize..=6).prop_flat_map(|(rows, cols)| {
+        prop::collection::vec(prop::collection::vec(cell(), cols), rows)
+    })
+}
+
+fn direction() -> impl Strategy<Value = Direction> {
+    prop::sample::select(Direction::ALL.to_vec())
+}
+
+fn tiles(game_board: &[Vec<u64>]) -> Vec<u64> {
+    let mut tiles: Vec<u64> = game_board
+        .iter()
+        .flatten()
+        .copied()
+        .filter(|&val| val != 0 && val != OBSTACLE)
+        .collect();
+    tiles.sort_unstable();
+    tiles
+}
+
+fn obstacles(game_board: &[Vec<u64>]) -> Vec<(usize, usize)> {
+    let mut positions = Vec::new();
+    for (i, row) in game_board.iter().enumerate() {
+        for (j, &val) in row.iter().enumerate() {
+            if val == OBSTACLE {
+                positions.push((i, j));
+            }
+        }
+    }
+    positions
+}
+
+#[derive(Default)]
+struct Events {
+    moves: usize,
+    merges: Vec<Merge>,
+    spawns: Vec<((usize, usize), u64)>,
+    game_over: Option<u64>,
+    players: Vec<usize>,
+}
+
+struct Recorder(Rc<RefCell<Events>>);
+
+impl GameObserver for Recorder {
+    fn on_move(&mut self, _direction: Direction, _result: &MoveResult) {
+        self.0.borrow_mut().moves += 1;
+    }
+
+    fn on_player_move(&mut self, player: usize, _direction: Direction, _result: &MoveResult) {
+        self.0.borrow_mut().players.push(player);
+    }
+
+    fn on_merge(&mut self, merge: &Merge) {
+        self.0.borrow_mut().merges.push(*merge);
+    }
+
+    fn on_spawn(&mut self, position: (usize, usize), value: u64) {
+        self.0.borrow_mut().spawns.push((position, value));
+    }
+
+    fn on_game_over(&mut self, score: u64) {
+        self.0.borrow_mut().game_over = Some(score);
+    }
+}
+
+/// The four hand-written moves that `apply_move` replaced with rotations of one left shift.
+mod original {
+    use rust_2048_game::engine::OBSTACLE;
+
+    pub fn move_left(game_board: &mut [Vec<u64>]) {
+        for row in game_board.iter_mut() {
+            for i in 1..row.len() {
+                let mut k = i;
+                while k > 0 && row[k] != OBSTACLE && row[k - 1] == 0 {
+                    row.swap(k, k - 1);
+                    k -= 1;
+                }
+            }
+            for i in 0..row.len() - 1 {
+                if row[i] != 0 && row[i] != OBSTACLE && row[i] == row[i + 1] {
+                    row[i] *= 2;
+                    row[i + 1] = 0;
+                }
+            }
+            for i in 1..row.len() {
+                let mut k = i;
+                while k > 0 && row[k] != OBSTACLE && row[k - 1] == 0 {
+                    row.swap(k, k - 1);
+                    k -= 1;
+                }
+            }
+        }
+    }
+
+    pub fn move_right(game_board: &mut [Vec<u64>]) {
+        for row in game_board.iter_mut() {
+            for i in (0..row.len() - 1).rev() {
+                let mut k = i;
+                while k < row.len() - 1 && row[k] != OBSTACLE && row[k + 1] == 0 {
+                    row.swap(k, k + 1);
+                    k += 1;
+                }
+            }
+            for i in (0..row.len() - 1).rev() {
+                if row[i] != 0 && row[i] != OBSTACLE && row[i] == row[i + 1] {
+                    row[i + 1] *= 2;
+                    row[i] = 0;
+                }
+            }
+            for i in (0..row.len() - 1).rev() {
+                let mut k = i;
+                while k < row.len() - 1 && row[k] != OBSTACLE && row[k + 1] == 0 {
+                    row.swap(k, k + 1);
+                    k += 1;
+                }
+            }
+        }
+    }
+
+    pub fn move_up(game_board: &mut [Vec<u64>]) {
+        for col in 0..game_board[0].len() {
+            for row in 1..game_board.len() {
+                let mut k = row;
+                while k > 0 && game_board[k][col] != OBSTACLE && game_board[k - 1][col] == 0 {
+                    game_board[k - 1][col] = game_board[k][col];
+                    game_board[k][col] = 0;
+                    k -= 1;
+                }
+            }
+            for row in 0..game_board.len() - 1 {
+                if game_board[row][col] != 0
+                    && game_board[row][col] != OBSTACLE
+                    && game_board[row][col] == game_board[row + 1][col]
+                {
+                    game_board[row][col] *= 2;
+                    game_board[row + 1][col] = 0;
+                }
+            }
+            for row in 1..game_board.len() {
+                let mut k = row;
+                while k > 0 && game_board[k][col] != OBSTACLE && game_board[k - 1][col] == 0 {
+                    game_board[k - 1][col] = game_board[k][col];
+                    game_board[k][col] = 0;
+                    k -= 1;
+                }
+            }
+        }
+    }
+
+    pub fn move_down(game_board: &mut [Vec<u64>]) {
+        for col in 0..game_board[0].len() {
+            for row in (0..game_board.len() - 1).rev() {
+                let mut k = row;
+                while k < game_board.len() - 1
+                    && game_board[k][col] != OBSTACLE
+                    && game_board[k + 1][col] == 0
+                {
+                    game_board[k + 1][col] = game_board[k][col];
+                    game_board[k][col] = 0;
+                    k += 1;
+                }
+            }
+            for row in (0..game_board.len() - 1).rev() {
+                if game_board[row][col] != 0
+                    && game_board[row][col] != OBSTACLE
+                    && game_board[row][col] == game_board[row + 1][col]
+                {
+                    game_board[row + 1][col] *= 2;
+                    game_board[row][col] = 0;
+                }
+            }
+            for row in (0..game_board.len() - 1).rev() {
+                let mut k = row;
+                while k < game_board.len() - 1
+                    && game_board[k][col] != OBSTACLE
+                    && game_board[k + 1][col] == 0
+                {
+                    game_board[k + 1][col] = game_board[k][col];
+                    game_board[k][col] = 0;
+                    k += 1;
+                }
+            }
+        }
+    }
+}
+
+proptest! {
+    #[test]
+    fn rotated_moves_match_the_original_four(board in rectangle(), direction in direction()) {
+        let mut expected = board.clone();
+        match direction {
+            Direction::Left => original::move_left(&mut expected),
+            Direction::Right => original::move_right(&mut expected),
+            Direction::Up => original::move_up(&mut expected),
+            Direction::Down => original::move_down(&mut expected),
+        }
+        let mut actual = board.clone();
+        let result = apply_move(&mut actual, direction);
+        prop_assert_eq!(&actual, &expected);
+        prop_assert_eq!(result.moved, actual != board);
+    }
+
+    #[test]
+    fn rotations_and_mirrors_undo_each_other(board in rectangle()) {
+        prop_assert_eq!(board.rotate_cw().rotate_ccw(), board.clone());
+        prop_assert_eq!(board.rotate_ccw().rotate_cw(), board.clone());
+        prop_assert_eq!(board.mirror().mirror(), board.clone());
+        let mut half_turn = board.mirror();
+        half_turn.reverse();
+        prop_assert_eq!(board.rotate_cw().rotate_cw(), half_turn);
+    }
+
+    #[test]
+    fn tiles_change_only_through_merges(board in board_of(cell()), direction in direction()) {
+        let mut next = board.clone();
+        let result = apply_move(&mut next, direction);
+
+        let mut expected = tiles(&board);
+        for merge in &result.merges {
+            for _ in 0..2 {
+                let index = expected.iter().position(|&val| val == merge.value / 2);
+                prop_assert!(index.is_some(), "merge of {} has no sources", merge.value);
+                expected.remove(index.unwrap());
+            }
+            expected.push(merge.value);
+        }
+        expected.sort_unstable();
+        prop_assert_eq!(tiles(&next), expected);
+    }
+
+    #[test]
+    fn total_value_is_conserved(board in board_of(cell()), direction in direction()) {
+        let mut next = board.clone();
+        apply_move(&mut next, direction);
+        let total = |b: &[Vec<u64>]| tiles(b).iter().sum::<u64>();
+        prop_assert_eq!(total(&next), total(&board));
+    }
+
+    #[test]
+    fn obstacles_never_move(board in board_of(cell()), direction in direction()) {
+        let mut next = board.clone();
+        apply_move(&mut next, direction);
+        prop_assert_eq!(obstacles(&next), obstacles(&board));
+    }
+
+    #[test]
+    fn moved_flag_matches_board_change(board in board_of(cell()), direction in direction()) {
+        let mut next = board.clone();
+        let result = apply_move(&mut next, direction);
+        prop_assert_eq!(result.moved, next != board);
+    }
+
+    // A repeated move can still merge tiles that the first move brought
+    // together, so only the sliding part is idempotent.
+    #[test]
+    fn repeated_move_only_changes_board_by_merging(
+        board in board_of(cell()),
+        direction in direction(),
+    ) {
+        let mut next = board.clone();
+        apply_move(&mut next, direction);
+        let again = apply_move(&mut next, direction);
+        prop_assert_eq!(again.moved, !again.merges.is_empty());
+    }
+
+    #[test]
+    fn repeated_moves_reach_a_fixed_point(board in board_of(cell()), direction in direction()) {
+        let mut next = board.clone();
+        let size = board.len();
+        for _ in 0..size {
+            apply_move(&mut next, direction);
+        }
+        let settled = next.clone();
+        prop_assert!(!apply_move(&mut next, direction).moved);
+        prop_assert_eq!(next, settled);
+    }
+
+    #[test]
+    fn legal_moves_are_exactly_the_moves_that_change_the_board(
+        board in board_of(cell()),
+        direction in direction(),
+    ) {
+        let moved = apply_move(&mut board.clone(), direction).moved;
+        prop_assert_eq!(legal_moves(&board).contains(&direction), moved);
+    }
+
+    #[test]
+    fn full_boards_without_neighbours_are_over(board in board_of(tile())) {
+        let full = board.iter().flatten().all(|&val| val != 0);
+        let size = board.len();
+        let has_pair = (0..size).any(|i| {
+            (0..size).any(|j| {
+                (j + 1 < size && board[i][j] == board[i][j + 1])
+                    || (i + 1 < size && board[i][j] == board[i + 1][j])
+            })
+        });
+        if full {
+            prop_assert_eq!(legal_moves(&board).is_empty(), !has_pair);
+        }
+    }
+
+    #[test]
+    fn spawner_places_the_previewed_tile(board in board_of(cell()), seed in any::<u64>()) {
+        let mut spawner = Spawner::seeded(seed);
+        let value = spawner.next_value();
+        let cell = spawner.next_cell(&board);
+        let mut spawned = board.clone();
+        prop_assert_eq!(spawner.spawn(&mut spawned), cell);
+        match cell {
+            Some((i, j)) => {
+                prop_assert_eq!(board[i][j], 0);
+                spawned[i][j] = 0;
+                prop_assert_eq!(&spawned, &board);
+                prop_assert!(value == 2 || value == 4);
+            }
+            None => prop_assert!(board.iter().flatten().all(|&val| val != 0)),
+        }
+    }
+
+    #[test]
+    fn the_first_tile_follows_the_spawners_odds(seed in any::<u64>()) {
+        prop_assert_eq!(Spawner::with_four_chance(seed, 1.0).next_value(), 4);
+        prop_assert_eq!(Spawner::with_four_chance(seed, 0.0).next_value(), 2);
+    }
+
+    #[test]
+    fn a_resumed_spawner_carries_on_where_it_was_checkpointed(
+        seed in any::<u64>(),
+        four_chance in 0.0f64..=1.0,
+        directions in prop::collection::vec(direction(), 0..30),
+    ) {
+        let mut game_board = vec![vec![0; 4]; 4];
+        let mut spawner = Spawner::with_four_chance(seed, four_chance);
+        spawner.spawn(&mut game_board);
+        for direction in directions {
+            if apply_move(&mut game_board, direction).moved {
+                spawner.spawn(&mut game_board);
+            }
+        }
+        let (seed, draws) = spawner.checkpoint();
+        let mut resumed = Spawner::resume(seed, draws, four_chance);
+        prop_assert_eq!(resumed.checkpoint(), (seed, draws));
+        for _ in 0..5 {
+            prop_assert_eq!(resumed.next_value(), spawner.next_value());
+            prop_assert_eq!(resumed.next_cell(&game_board), spawner.next_cell(&game_board));
+            let mut copy = game_board.clone();
+            resumed.spawn(&mut copy);
+            spawner.spawn(&mut game_board);
+            prop_assert_eq!(&copy, &game_board);
+        }
+    }
+
+    #[test]
+    fn spawn_strategies_only_pick_empty_cells(
+        board in board_of(cell()),
+        value in prop::sample::select(vec![2u64, 4]),
+        slot in 0.0..1.0f64,
+    ) {
+        let strategies: [&dyn SpawnStrategy; 4] = [&Uniform, &Gentle, &Evil, &Minimax { depth: 1 }];
+        let has_room = board.iter().flatten().any(|&val| val == 0);
+        for strategy in strategies {
+            let cell = strategy.place(&board, value, slot);
+            prop_assert_eq!(cell.is_some(), has_room);
+            if let Some((i, j)) = cell {
+                prop_assert_eq!(board[i][j], 0);
+            }
+        }
+    }
+
+    #[test]
+    fn observers_see_every_event_of_a_move(
+        board in board_of(cell()),
+        direction in direction(),
+        seed in any::<u64>(),
+    ) {
+        let events = Rc::new(RefCell::new(Events::default()));
+        let mut game = Game::from_board(board.clone(), Spawner::seeded(seed));
+        game.subscribe(Box::new(Recorder(events.clone())));
+        let mut moved = board.clone();
+        let expected = apply_move(&mut moved, direction);
+        let result = game.play(direction);
+
+        let events = events.borrow();
+        prop_assert_eq!(events.moves, usize::from(result.moved));
+        prop_assert_eq!(&events.merges, &expected.merges);
+        if !result.moved {
+            prop_assert!(events.spawns.is_empty());
+            prop_assert_eq!(game.board(), &board[..]);
+            return Ok(());
+        }
+        let mut spawned = moved.clone();
+        for &((i, j), value) in &events.spawns {
+            prop_assert_eq!(moved[i][j], 0);
+            spawned[i][j] = value;
+        }
+        prop_assert_eq!(game.board(), &spawned[..]);
+        prop_assert_eq!(events.game_over, game.is_over().then(|| game.score()));
+    }
+
+    #[test]
+    fn turns_pass_only_on_moves_that_change_the_board(
+        board in board_of(cell()),
+        seed in any::<u64>(),
+        players in 1usize..=4,
+        directions in prop::collection::vec(direction(), 1..30),
+    ) {
+        let events = Rc::new(RefCell::new(Events::default()));
+        let mut game = Game::from_board(board, Spawner::seeded(seed));
+        game.set_players(players);
+        game.subscribe(Box::new(Recorder(events.clone())));
+        let mut expected = Vec::new();
+        for direction in directions {
+            let player = game.player();
+            if game.play(direction).moved {
+                expected.push(player);
+                prop_assert_eq!(game.player(), (player + 1) % players);
+            } else {
+                prop_assert_eq!(game.player(), player);
+            }
+        }
+        prop_assert_eq!(&events.borrow().players, &expected);
+    }
+
+    #[test]
+    fn combo_streak_counts_consecutive_merging_moves(
+        board in board_of(cell()),
+        directions in prop::collection::vec(direction(), 1..20),
+    ) {
+        let mut board = board;
+        let mut combo = Combo::default();
+        let mut streak = 0;
+        for direction in directions {
+            let result = apply_move(&mut board, direction);
+            let merged: u64 = result.merges.iter().map(|merge| merge.value).sum();
+            streak = if result.merges.is_empty() { 0 } else { streak + 1 };
+            let bonus = combo.record(&result);
+            prop_assert_eq!(combo.streak, streak);
+            prop_assert_eq!(bonus, merged * (u64::from(streak.max(1)) - 1));
+        }
+    }
+
+    #[test]
+    fn rescued_boards_can_move_again(
+        board in board_of((1u32..=30).prop_map(|exponent| 1u64 << exponent)),
+        seed in any::<u64>(),
+    ) {
+        prop_assume!(legal_moves(&board).is_empty());
+        let mut cleared = board.clone();
+        prop_assert!(ClearLowest.rescue(&mut cleared));
+        prop_assert!(!legal_moves(&cleared).is_empty());
+        prop_assert!(cleared
+            .iter()
+            .flatten()
+            .zip(board.iter().flatten())
+            .all(|(&after, &before)| after == before || after == 0));
+
+        let mut shuffled = board.clone();
+        prop_assert!(Shuffle::seeded(seed).rescue(&mut shuffled));
+        prop_assert!(!legal_moves(&shuffled).is_empty());
+    }
+
+    #[test]
+    fn canonical_hash_ignores_rotations_and_reflections(board in board_of(cell())) {
+        let size = board.len();
+        let transposed: Vec<Vec<u64>> = (0..size)
+            .map(|i| (0..size).map(|j| board[j][i]).collect())
+            .collect();
+        let mut mirrored = board.clone();
+        for row in &mut mirrored {
+            row.reverse();
+        }
+        let mut flipped = board.clone();
+        flipped.reverse();
+        prop_assert_eq!(canonical_hash(&board), canonical_hash(&transposed));
+        prop_assert_eq!(canonical_hash(&board), canonical_hash(&mirrored));
+        prop_assert_eq!(canonical_hash(&board), canonical_hash(&flipped));
+    }
+
+    #[test]
+    fn links_round_trip_every_board(
+        board in board_of(cell()),
+        seed in prop::option::of(any::<u64>()),
+        draws in 0u64..1000,
+    ) {
+        let draws = if seed.is_some() { draws } else { 0 };
+        let link = BoardLink::new("classic-hard-combo".to_string(), board, seed, draws).unwrap();
+        prop_assert_eq!(link.to_string().parse::<BoardLink>(), Ok(link));
+    }
+
+    #[test]
+    fn links_refuse_tiles_they_cannot_write(
+        board in board_of(cell()),
+        at in any::<(usize, usize)>(),
+        tile in prop_oneof![
+            (36u32..64).prop_map(|exponent| 1u64 << exponent),
+            Just(1u64),
+            3u64..1000,
+        ],
+    ) {
+        prop_assume!(tile == 1 || tile > MAX_TILE || !tile.is_power_of_two());
+        let mut board = board;
+        let size = board.len();
+        board[at.0 % size][at.1 % size] = tile;
+        prop_assert_eq!(BoardLink::new("classic".to_string(), board, None, 0), Err(tile));
+    }
+
+    #[test]
+    fn walls_of_a_board_rebuild_its_obstacles(board in board_of(cell())) {
+        let walls: Vec<Vec<u64>> = board
+            .iter()
+            .map(|row| row.iter().map(|&val| if val == OBSTACLE { OBSTACLE } else { 0 }).collect())
+            .collect();
+        prop_assert_eq!(Mask::walls(&board).board(), walls);
+    }
+
+    #[test]
+    fn masked_cells_stay_walls_through_play(
+        open in board_of(prop::bool::weighted(0.8)),
+        seed in any::<u64>(),
+        directions in prop::collection::vec(direction(), 1..40),
+    ) {
+        let text: String = open
+            .iter()
+            .map(|row| row.iter().map(|&open| if open { '.' } else { '#' }).collect::<String>() + "\n")
+            .collect();
+        let Ok(mask) = text.parse::<Mask>() else {
+            return Ok(());
+        };
+        let mut game = Game::from_board(mask.board(), Spawner::seeded(seed));
+        for direction in directions {
+            game.play(direction);
+            for (i, row) in game.board().iter().enumerate() {
+                for (j, &val) in row.iter().enumerate() {
+                    prop_assert_eq!(val == OBSTACLE, !open[i][j]);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn lucky_moves_without_specials_match_the_classic_engine(
+        board in board_of(cell()),
+        direction in direction(),
+    ) {
+        let mut classic = board.clone();
+        let result = apply_move(&mut classic, direction);
+        let mut lucky: Vec<Vec<Tile>> = board
+            .iter()
+            .map(|row| row.iter().map(|&val| Tile::Number(val)).collect())
+            .collect();
+        let lucky_result = powerup::apply_move(&mut lucky, direction);
+        prop_assert_eq!(lucky_result.moved, result.moved);
+        prop_assert!(lucky_result.activations.is_empty());
+        for (lucky_row, classic_row) in lucky.iter().zip(&classic) {
+            for (&tile, &val) in lucky_row.iter().zip(classic_row) {
+                prop_assert_eq!(tile, Tile::Number(val));
+            }
+        }
+    }
+
+    #[test]
+    fn specials_go_off_when_they_merge_and_never_cost_points(
+        board in board_of(prop_oneof![
+            8 => cell().prop_map(Tile::Number),
+            1 => prop::sample::select(vec![Special::Wildcard, Special::Bomb, Special::Doubler])
+                .prop_map(Tile::Special),
+        ]),
+        direction in direction(),
+    ) {
+        let count = |board: &[Vec<Tile>]| {
+            board.iter().flatten().filter(|tile| matches!(tile, Tile::Special(_))).count()
+        };
+        let before = count(&board);
+        let mut after = board.clone();
+        let result = powerup::apply_move(&mut after, direction);
+        prop_assert!(count(&after) + result.activations.len() <= before);
+        prop_assert!(powerup::score(&after) + result.cleared >= powerup::score(&board));
+    }
+
+    #[test]
+    fn the_journal_rebuilds_every_position_after_thinning_and_undo(
+        board in board_of(cell()),
+        moves in prop::collection::vec(
+            (direction(), prop::option::of((any::<usize>(), any::<usize>(), prop_oneof![
+                4 => tile(),
+                1 => Just(OBSTACLE),
+                1 => 1u64..1000,
+            ]))),
+            0..120,
+        ),
+        interval in 0usize..8,
+        max_checkpoints in 1usize..4,
+        undo in any::<usize>(),
+    ) {
+        let size = board.len();
+        let mut journal = Journal::new(&board, interval, max_checkpoints);
+        let mut positions = vec![board.clone()];
+        let mut game_board = board;
+        for (direction, spawn) in moves {
+            let spawn = spawn.map(|(i, j, value)| (i % size, j % size, value));
+            apply_move(&mut game_board, direction);
+            if let Some((i, j, value)) = spawn {
+                game_board[i][j] = value;
+            }
+            journal.push(direction, spawn);
+            positions.push(game_board.clone());
+            prop_assert!(journal.checkpoints() <= max_checkpoints);
+        }
+        for (step, position) in positions.iter().enumerate() {
+            prop_assert_eq!(&journal.position(step), position);
+        }
+        prop_assert!(journal.positions().eq(positions.iter().cloned()));
+
+        let step = undo % positions.len();
+        journal.truncate(step);
+        prop_assert_eq!(journal.len(), step);
+        prop_assert_eq!(journal.current(), &positions[step][..]);
+        for (step, position) in positions.iter().take(step + 1).enumerate() {
+            prop_assert_eq!(&journal.position(step), position);
+        }
+    }
+}
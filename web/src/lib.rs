@@ -0,0 +1,32 @@
+use rust_2048_game::engine::Direction;
+use rust_2048_game::game::Game;
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen]
+pub struct WebGame {
+    game: Game,
+}
+
+#[wasm_bindgen]
+pub fn new_game(size: usize) -> WebGame {
+    WebGame {
+        game: Game::new(size.clamp(2, 8)),
+    }
+}
+
+#[wasm_bindgen]
+impl WebGame {
+    pub fn apply_move(&mut self, direction: &str) -> Result<bool, JsError> {
+        let direction: Direction = direction.parse().map_err(|e: String| JsError::new(&e))?;
+        Ok(self.game.play(direction).moved)
+    }
+
+    pub fn board_json(&self) -> String {
+        serde_json::json!({
+            "board": self.game.board(),
+            "score": self.game.score(),
+            "over": self.game.is_over(),
+        })
+        .to_string()
+    }
+}